@@ -1,21 +1,57 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use crate::FieldMap;
+
+/// Consecutive connection failures before the breaker opens
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+/// How long an open breaker rejects calls before letting a probe through
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
 #[derive(Clone)]
 pub struct AnkiConnectClient {
     base_url: String,
     client: reqwest::Client,
+    breaker: Arc<Mutex<CircuitBreaker>>,
 }
 
 impl AnkiConnectClient {
     pub fn new(base_url: String) -> Self {
+        Self::from_client(base_url, reqwest::Client::new())
+    }
+
+    /// Create a client whose requests fail with [`AnkiError::Timeout`] after `timeout`
+    pub fn with_timeout(base_url: String, timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("Failed to build AnkiConnect HTTP client")?;
+        Ok(Self::from_client(base_url, client))
+    }
+
+    fn from_client(base_url: String, client: reqwest::Client) -> Self {
         Self {
             base_url,
-            client: reqwest::Client::new(),
+            client,
+            breaker: Arc::new(Mutex::new(CircuitBreaker::new(
+                DEFAULT_FAILURE_THRESHOLD,
+                DEFAULT_COOLDOWN,
+            ))),
         }
     }
 
+    /// Fail fast with [`AnkiError::Unavailable`] for `cooldown` after `threshold`
+    /// consecutive connection failures
+    pub fn with_circuit_breaker(mut self, threshold: u32, cooldown: Duration) -> Self {
+        self.breaker = Arc::new(Mutex::new(CircuitBreaker::new(threshold, cooldown)));
+        self
+    }
+
     /// Check if AnkiConnect is available
     pub async fn check_connection(&self) -> Result<u32> {
         let response: AnkiResponse<u32> = self.invoke("version", json!({})).await?;
@@ -34,30 +70,42 @@ impl AnkiConnectClient {
         response.into_result()
     }
 
+    /// Get the field names of a model, in order
+    pub async fn model_field_names(&self, model: &str) -> Result<Vec<String>> {
+        let response: AnkiResponse<Vec<String>> = self
+            .invoke("modelFieldNames", json!({ "modelName": model }))
+            .await?;
+        response.into_result()
+    }
+
     /// Add a note to Anki
     pub async fn add_note(
         &self,
         deck: &str,
         model: &str,
-        front: &str,
-        back: &str,
+        fields: &FieldMap,
+        tags: &[String],
     ) -> Result<u64> {
-        let params = json!({
-            "note": {
-                "deckName": deck,
-                "modelName": model,
-                "fields": {
-                    "Front": front,
-                    "Back": back
-                },
-                "tags": ["saya"]
-            }
-        });
+        let params = note_params(deck, model, fields, tags);
 
         let response: AnkiResponse<u64> = self.invoke("addNote", params).await?;
         response.into_result()
     }
 
+    /// Find note IDs matching an Anki search query
+    pub async fn find_notes(&self, query: &str) -> Result<Vec<u64>> {
+        let response: AnkiResponse<Vec<u64>> =
+            self.invoke("findNotes", json!({ "query": query })).await?;
+        response.into_result()
+    }
+
+    /// Get fields of the given notes
+    pub async fn notes_info(&self, notes: &[u64]) -> Result<Vec<NoteInfo>> {
+        let response: AnkiResponse<Vec<NoteInfo>> =
+            self.invoke("notesInfo", json!({ "notes": notes })).await?;
+        response.into_result()
+    }
+
     /// Invoke an AnkiConnect API action
     async fn invoke<T>(&self, action: &str, params: serde_json::Value) -> Result<AnkiResponse<T>>
     where
@@ -69,21 +117,120 @@ impl AnkiConnectClient {
             params,
         };
 
-        let response = self
-            .client
-            .post(&self.base_url)
-            .json(&request)
-            .send()
-            .await
+        if !self.breaker.lock().unwrap().allow(Instant::now()) {
+            return Err(AnkiError::Unavailable.into());
+        }
+
+        let sent = self.client.post(&self.base_url).json(&request).send().await;
+        self.breaker
+            .lock()
+            .unwrap()
+            .record(sent.is_ok(), Instant::now());
+        let response = sent
+            .map_err(request_error)
             .context("Failed to send request to AnkiConnect")?;
 
         response
             .json::<AnkiResponse<T>>()
             .await
+            .map_err(request_error)
             .context("Failed to parse AnkiConnect response")
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum AnkiError {
+    #[error("AnkiConnect request timed out")]
+    Timeout,
+    #[error("Anki unavailable, retrying later")]
+    Unavailable,
+    #[error(transparent)]
+    Request(reqwest::Error),
+}
+
+fn request_error(e: reqwest::Error) -> AnkiError {
+    if e.is_timeout() {
+        AnkiError::Timeout
+    } else {
+        AnkiError::Request(e)
+    }
+}
+
+/// Stops calling a closed Anki after repeated connection failures
+///
+/// Once the cooldown has passed the next call is let through as a probe; it
+/// closes the breaker on success and reopens it on failure.
+struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    failures: u32,
+    open_until: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            cooldown,
+            failures: 0,
+            open_until: None,
+        }
+    }
+
+    fn allow(&mut self, now: Instant) -> bool {
+        match self.open_until {
+            Some(until) if now < until => false,
+            Some(_) => {
+                // Half-open: hold further calls off while this probe runs
+                self.open_until = Some(now + self.cooldown);
+                true
+            }
+            None => true,
+        }
+    }
+
+    fn record(&mut self, connected: bool, now: Instant) {
+        if connected {
+            self.failures = 0;
+            self.open_until = None;
+            return;
+        }
+
+        self.failures += 1;
+        if self.failures >= self.threshold {
+            self.open_until = Some(now + self.cooldown);
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteInfo {
+    pub note_id: u64,
+    pub fields: HashMap<String, NoteFieldValue>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NoteFieldValue {
+    pub value: String,
+    pub order: u32,
+}
+
+impl NoteInfo {
+    /// The first field of the note type, "Front" on Basic notes
+    pub fn front(&self) -> Option<&str> {
+        self.fields
+            .values()
+            .min_by_key(|field| field.order)
+            .map(|field| field.value.as_str())
+    }
+
+    /// Value of the field named `name`
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(|field| field.value.as_str())
+    }
+}
+
 #[derive(Serialize)]
 struct AnkiRequest {
     action: String,
@@ -107,3 +254,161 @@ impl<T> AnkiResponse<T> {
             .context("AnkiConnect returned null result")
     }
 }
+
+/// `addNote` parameters for a note with `fields` filled in
+fn note_params(deck: &str, model: &str, fields: &FieldMap, tags: &[String]) -> serde_json::Value {
+    json!({
+        "note": {
+            "deckName": deck,
+            "modelName": model,
+            "fields": fields,
+            "tags": tags
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_params_include_tags() {
+        let tags = vec!["saya".to_string(), "N5".to_string(), "2026-01-31".to_string()];
+        let fields = FieldMap::from([
+            ("Front".to_string(), "本".to_string()),
+            ("Back".to_string(), "book".to_string()),
+        ]);
+        let params = note_params("Japanese", "Basic", &fields, &tags);
+
+        assert_eq!(params["note"]["tags"], json!(["saya", "N5", "2026-01-31"]));
+        assert_eq!(params["note"]["fields"]["Front"], "本");
+    }
+
+    /// Unused local address; connections are refused until something binds it
+    async fn free_addr() -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap()
+    }
+
+    /// Answer every request on `addr` with AnkiConnect version 6
+    async fn serve_version(addr: std::net::SocketAddr) {
+        serve_result(addr, "6").await;
+    }
+
+    /// Answer every request on `addr` with `result`
+    async fn serve_result(addr: std::net::SocketAddr, result: &'static str) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                while !String::from_utf8_lossy(&request).contains("\"params\"") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let body = format!(r#"{{"result":{},"error":null}}"#, result);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+    }
+
+    fn is_unavailable(err: &anyhow::Error) -> bool {
+        matches!(err.downcast_ref::<AnkiError>(), Some(AnkiError::Unavailable))
+    }
+
+    #[tokio::test]
+    async fn test_breaker_opens_after_repeated_failures() {
+        let addr = free_addr().await;
+        let client = AnkiConnectClient::new(format!("http://{}", addr))
+            .with_circuit_breaker(2, Duration::from_secs(60));
+
+        for _ in 0..2 {
+            let err = client.check_connection().await.unwrap_err();
+            assert!(!is_unavailable(&err));
+        }
+
+        let err = client.check_connection().await.unwrap_err();
+        assert!(is_unavailable(&err));
+    }
+
+    #[tokio::test]
+    async fn test_successful_probe_closes_breaker() {
+        let addr = free_addr().await;
+        let client = AnkiConnectClient::new(format!("http://{}", addr))
+            .with_circuit_breaker(1, Duration::from_millis(100));
+
+        assert!(!is_unavailable(&client.check_connection().await.unwrap_err()));
+        assert!(is_unavailable(&client.check_connection().await.unwrap_err()));
+
+        // Anki comes back; after the cooldown a probe gets through
+        serve_version(addr).await;
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(client.check_connection().await.unwrap(), 6);
+        assert_eq!(client.check_connection().await.unwrap(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_model_field_names_build_field_map() {
+        let addr = free_addr().await;
+        serve_result(addr, r#"["Expression","Reading","Meaning","Sentence","Notes"]"#).await;
+        let client = AnkiConnectClient::new(format!("http://{}", addr));
+
+        let fields = client.model_field_names("Japanese").await.unwrap();
+        let map = crate::auto_field_map(&fields);
+
+        assert_eq!(fields.len(), 5);
+        assert_eq!(map["Expression"], "{term}");
+        assert_eq!(map["Reading"], "{reading}");
+        assert_eq!(map["Meaning"], "{definition}");
+        assert_eq!(map["Sentence"], "{example}");
+        assert!(!map.contains_key("Notes"));
+    }
+
+    #[test]
+    fn test_failed_probe_reopens_breaker() {
+        let start = Instant::now();
+        let cooldown = Duration::from_secs(10);
+        let mut breaker = CircuitBreaker::new(2, cooldown);
+
+        breaker.record(false, start);
+        assert!(breaker.allow(start));
+        breaker.record(false, start);
+        assert!(!breaker.allow(start));
+
+        let later = start + cooldown;
+        assert!(breaker.allow(later));
+        assert!(!breaker.allow(later), "only one probe at a time");
+        breaker.record(false, later);
+        assert!(!breaker.allow(later + Duration::from_secs(1)));
+    }
+
+    #[tokio::test]
+    async fn test_hung_server_times_out() {
+        // Accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+
+        let client = AnkiConnectClient::with_timeout(url, Duration::from_millis(200)).unwrap();
+        let started = std::time::Instant::now();
+        let err = client.check_connection().await.unwrap_err();
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(matches!(err.downcast_ref::<AnkiError>(), Some(AnkiError::Timeout)));
+    }
+}
@@ -1,8 +1,11 @@
 mod client;
 mod template;
 
-pub use client::AnkiConnectClient;
-pub use template::{CardTemplate, NoteField};
+pub use client::{AnkiConnectClient, AnkiError, NoteFieldValue, NoteInfo};
+pub use template::{
+    CardTemplate, CardValues, DEFAULT_TAG, FieldMap, NoteField, TagValues, auto_field_map,
+    escape_html,
+};
 
 use anyhow::Result;
 
@@ -10,14 +13,134 @@ use anyhow::Result;
 pub async fn add_card(
     client: &AnkiConnectClient,
     template: &CardTemplate,
-    term: &str,
-    reading: &str,
-    definition: &str,
+    values: &CardValues,
+    tags: &TagValues,
 ) -> Result<u64> {
-    let front = template.format_front(term, reading, definition);
-    let back = template.format_back(term, reading, definition);
+    let fields = template.format_fields(values);
+    let tags = template.expand_tags(tags);
 
     client
-        .add_note(&template.deck, &template.model, &front, &back)
+        .add_note(&template.deck, &template.model, &fields, &tags)
         .await
 }
+
+/// Terms on the front of every note in `deck`
+pub async fn deck_terms(client: &AnkiConnectClient, deck: &str) -> Result<Vec<String>> {
+    let query = format!("deck:\"{}\"", deck.replace('"', "\\\""));
+    let ids = client.find_notes(&query).await?;
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let notes = client.notes_info(&ids).await?;
+    Ok(notes
+        .iter()
+        .filter_map(|note| note.front())
+        .filter_map(front_term)
+        .collect())
+}
+
+/// Whether a note in `deck` has `term` as the headword of `field`, or of its
+/// first field without one
+pub async fn deck_has_term(
+    client: &AnkiConnectClient,
+    deck: &str,
+    field: Option<&str>,
+    term: &str,
+) -> Result<bool> {
+    let ids = client.find_notes(&term_query(deck, field, term)).await?;
+    if ids.is_empty() {
+        return Ok(false);
+    }
+
+    // The query also matches longer words starting with `term`
+    let notes = client.notes_info(&ids).await?;
+    Ok(notes
+        .iter()
+        .filter_map(|note| match field {
+            Some(field) => note.field(field),
+            None => note.front(),
+        })
+        .filter_map(front_term)
+        .any(|front| front == term))
+}
+
+/// Search for notes in `deck` whose `field` starts with `term`, or with `term`
+/// in any field
+fn term_query(deck: &str, field: Option<&str>, term: &str) -> String {
+    let escape = |text: &str| {
+        text.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('*', "\\*")
+            .replace('_', "\\_")
+    };
+    match field {
+        Some(field) => format!(
+            "deck:\"{}\" \"{}:{}*\"",
+            escape(deck),
+            escape(field),
+            escape(term)
+        ),
+        None => format!("deck:\"{}\" \"{}\"", escape(deck), escape(term)),
+    }
+}
+
+/// Headword from a front field: its first line, without HTML markup
+pub fn front_term(front: &str) -> Option<String> {
+    let first_line = front.lines().next()?.split("<br").next()?;
+
+    let mut term = String::new();
+    let mut in_tag = false;
+    for c in first_line.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => term.push(c),
+            _ => {}
+        }
+    }
+
+    let term = term.replace("&nbsp;", " ").trim().to_string();
+    (!term.is_empty()).then_some(term)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_front_term_takes_first_line() {
+        assert_eq!(front_term("食べる\nたべる").as_deref(), Some("食べる"));
+        assert_eq!(front_term("食べる<br>たべる").as_deref(), Some("食べる"));
+    }
+
+    #[test]
+    fn test_term_query_escapes_wildcards() {
+        assert_eq!(
+            term_query("Japanese", Some("Front"), "食べる"),
+            r#"deck:"Japanese" "Front:食べる*""#
+        );
+        assert_eq!(
+            term_query("My \"Deck\"", Some("Front"), "a_*"),
+            r#"deck:"My \"Deck\"" "Front:a\_\**""#
+        );
+    }
+
+    #[test]
+    fn test_term_query_searches_term_field() {
+        assert_eq!(
+            term_query("Japanese", Some("Expression"), "食べる"),
+            r#"deck:"Japanese" "Expression:食べる*""#
+        );
+        assert_eq!(
+            term_query("Japanese", None, "食べる"),
+            r#"deck:"Japanese" "食べる""#
+        );
+    }
+
+    #[test]
+    fn test_front_term_strips_html() {
+        assert_eq!(front_term("<b>日本</b>&nbsp;").as_deref(), Some("日本"));
+        assert_eq!(front_term("<div></div>"), None);
+    }
+}
@@ -1,11 +1,71 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+/// Tag added to every note
+pub const DEFAULT_TAG: &str = "saya";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CardTemplate {
     pub deck: String,
     pub model: String,
     pub front_template: String,
     pub back_template: String,
+    /// Placeholders: {jlpt}, {date}, {profile}
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Templates by note field; without one the note's Front and Back get
+    /// the front and back templates
+    #[serde(default)]
+    pub field_map: Option<FieldMap>,
+}
+
+/// Values substituted into tag placeholders
+#[derive(Debug, Clone, Default)]
+pub struct TagValues {
+    pub jlpt: Option<String>,
+    /// `YYYY-MM-DD`, in local time
+    pub date: String,
+    pub profile: String,
+}
+
+impl TagValues {
+    /// Values for a card created now
+    pub fn today(profile: &str, jlpt: Option<&str>) -> Self {
+        Self {
+            jlpt: jlpt.map(str::to_string),
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            profile: profile.to_string(),
+        }
+    }
+}
+
+/// Values substituted into the front and back templates
+///
+/// Metadata the result didn't show is left empty.
+#[derive(Debug, Clone, Default)]
+pub struct CardValues {
+    pub term: String,
+    pub reading: String,
+    pub definition: String,
+    pub example: String,
+    /// Frequency stars
+    pub frequency: String,
+    pub pitch: String,
+    pub jlpt: String,
+    pub conjugation: String,
+}
+
+impl CardValues {
+    pub fn new(term: &str, reading: &str, definition: &str, example: &str) -> Self {
+        Self {
+            term: term.to_string(),
+            reading: reading.to_string(),
+            definition: definition.to_string(),
+            example: example.to_string(),
+            ..Self::default()
+        }
+    }
 }
 
 impl CardTemplate {
@@ -16,6 +76,8 @@ impl CardTemplate {
             model: "Basic".to_string(),
             front_template: "{term}\n{reading}".to_string(),
             back_template: "{definition}".to_string(),
+            tags: Vec::new(),
+            field_map: None,
         }
     }
 
@@ -26,24 +88,176 @@ impl CardTemplate {
             model,
             front_template: front,
             back_template: back,
+            tags: Vec::new(),
+            field_map: None,
+        }
+    }
+
+    /// Set the tag templates added alongside the default tag
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Expand the tag templates, always starting with [`DEFAULT_TAG`]
+    ///
+    /// Tags whose placeholders have no value are dropped, and spaces become
+    /// underscores since Anki splits tags on whitespace.
+    pub fn expand_tags(&self, values: &TagValues) -> Vec<String> {
+        let mut tags = vec![DEFAULT_TAG.to_string()];
+
+        for template in &self.tags {
+            if template.contains("{jlpt}") && values.jlpt.is_none() {
+                continue;
+            }
+            let tag = template
+                .replace("{jlpt}", values.jlpt.as_deref().unwrap_or_default())
+                .replace("{date}", &values.date)
+                .replace("{profile}", &values.profile)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join("_");
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+
+        tags
+    }
+
+    /// Fill the note's fields from `field_map` instead of Front and Back
+    pub fn with_field_map(mut self, field_map: Option<FieldMap>) -> Self {
+        self.field_map = field_map;
+        self
+    }
+
+    /// Fields of the note exactly as [`crate::add_card`] sends them
+    pub fn format_fields(&self, values: &CardValues) -> FieldMap {
+        match &self.field_map {
+            Some(field_map) => field_map
+                .iter()
+                .map(|(field, template)| (field.clone(), fill(template, values)))
+                .collect(),
+            None => FieldMap::from([
+                ("Front".to_string(), self.format_front(values)),
+                ("Back".to_string(), self.format_back(values)),
+            ]),
+        }
+    }
+
+    /// Term and definition fields of the note [`Self::format_fields`] fills
+    ///
+    /// Without a field map these are Front and Back.
+    pub fn preview(&self, values: &CardValues) -> (String, String) {
+        let back = match &self.field_map {
+            Some(field_map) => field_with(field_map, "{definition}"),
+            None => Some("Back".to_string()),
+        };
+
+        let mut fields = self.format_fields(values);
+        let mut take = |field: Option<String>| {
+            field
+                .and_then(|field| fields.remove(&field))
+                .unwrap_or_default()
+        };
+        (take(self.term_field()), take(back))
+    }
+
+    /// Field the term is written to, Front without a field map
+    ///
+    /// `None` when the field map leaves the term out.
+    pub fn term_field(&self) -> Option<String> {
+        match &self.field_map {
+            Some(field_map) => field_with(field_map, "{term}"),
+            None => Some("Front".to_string()),
         }
     }
 
     /// Format the front of the card
-    pub fn format_front(&self, term: &str, reading: &str, definition: &str) -> String {
-        self.front_template
-            .replace("{term}", term)
-            .replace("{reading}", reading)
-            .replace("{definition}", definition)
+    pub fn format_front(&self, values: &CardValues) -> String {
+        fill(&self.front_template, values)
     }
 
     /// Format the back of the card
-    pub fn format_back(&self, term: &str, reading: &str, definition: &str) -> String {
-        self.back_template
-            .replace("{term}", term)
-            .replace("{reading}", reading)
-            .replace("{definition}", definition)
+    pub fn format_back(&self, values: &CardValues) -> String {
+        fill(&self.back_template, values)
+    }
+}
+
+/// Substitute the placeholders, escaping the values but not the template's own HTML
+fn fill(template: &str, values: &CardValues) -> String {
+    template
+        .replace("{term}", &escape_html(&values.term))
+        .replace("{reading}", &escape_html(&values.reading))
+        .replace("{definition}", &escape_html(&values.definition))
+        .replace("{example}", &escape_html(&values.example))
+        .replace("{frequency}", &escape_html(&values.frequency))
+        .replace("{pitch}", &escape_html(&values.pitch))
+        .replace("{jlpt}", &escape_html(&values.jlpt))
+        .replace("{conjugation}", &escape_html(&values.conjugation))
+}
+
+/// First field, by name, whose template uses `placeholder`
+fn field_with(field_map: &FieldMap, placeholder: &str) -> Option<String> {
+    field_map
+        .iter()
+        .filter(|(_, template)| template.contains(placeholder))
+        .map(|(field, _)| field)
+        .min()
+        .cloned()
+}
+
+/// Escape text for an Anki field, which is rendered as HTML
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
     }
+    escaped
+}
+
+/// Anki field name to the template filled into it
+pub type FieldMap = HashMap<String, String>;
+
+/// Common field names for each placeholder, compared case-insensitively
+const FIELD_ALIASES: &[(&str, &[&str])] = &[
+    ("{term}", &["expression", "word", "vocab", "term", "front"]),
+    ("{reading}", &["reading", "kana", "furigana"]),
+    ("{definition}", &["meaning", "definition", "glossary", "english", "back"]),
+    ("{example}", &["sentence", "example"]),
+];
+
+/// Guess which of a note type's fields hold the term, reading, definition and example
+///
+/// Fields that match no known name are left out; if nothing looks like a term the
+/// first field gets it.
+pub fn auto_field_map(field_names: &[String]) -> FieldMap {
+    let mut map = FieldMap::new();
+
+    for (placeholder, aliases) in FIELD_ALIASES {
+        let field = aliases.iter().find_map(|alias| {
+            field_names
+                .iter()
+                .find(|name| name.eq_ignore_ascii_case(alias) && !map.contains_key(*name))
+        });
+        if let Some(field) = field {
+            map.insert(field.clone(), placeholder.to_string());
+        }
+    }
+
+    if !map.values().any(|template| template == "{term}")
+        && let Some(first) = field_names.iter().find(|name| !map.contains_key(*name))
+    {
+        map.insert(first.clone(), "{term}".to_string());
+    }
+
+    map
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,3 +265,102 @@ pub struct NoteField {
     pub name: String,
     pub value: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_placeholder() {
+        let template = CardTemplate::new(
+            "Japanese".to_string(),
+            "Basic".to_string(),
+            "{term}".to_string(),
+            "{definition}\n{example}".to_string(),
+        );
+
+        let example = "家族と食べる。\nI eat with my family.";
+        let back = template.format_back(&CardValues::new("家族", "かぞく", "family", example));
+        assert_eq!(back, "family\n家族と食べる。\nI eat with my family.");
+        let front = template.format_front(&CardValues::new("家族", "かぞく", "family", ""));
+        assert_eq!(front, "家族");
+    }
+
+    #[test]
+    fn test_values_are_html_escaped() {
+        let template = CardTemplate::new(
+            "Japanese".to_string(),
+            "Basic".to_string(),
+            "<b>{term}</b>".to_string(),
+            "{definition}".to_string(),
+        );
+
+        let back = template.format_back(&CardValues::new("AかB", "", "to compare <A> & <B>", ""));
+        assert_eq!(back, "to compare &lt;A&gt; &amp; &lt;B&gt;");
+        let front = template.format_front(&CardValues::new("a<b", "", "", ""));
+        assert_eq!(front, "<b>a&lt;b</b>");
+    }
+
+    #[test]
+    fn test_metadata_placeholders() {
+        let template = CardTemplate::new(
+            "Japanese".to_string(),
+            "Basic".to_string(),
+            "{term} {frequency}".to_string(),
+            "{definition}<br>{pitch} {jlpt}<br>{conjugation}".to_string(),
+        );
+        let values = CardValues {
+            pitch: "◎".to_string(),
+            jlpt: "N4".to_string(),
+            ..CardValues::new("学校", "がっこう", "school", "")
+        };
+
+        assert_eq!(template.format_back(&values), "school<br>◎ N4<br>");
+        assert_eq!(template.format_front(&values), "学校 ");
+    }
+
+    #[test]
+    fn test_expand_tags() {
+        let template = CardTemplate::default_japanese().with_tags(vec![
+            "{jlpt}".to_string(),
+            "mined::{date}".to_string(),
+            "{profile}".to_string(),
+            "saya".to_string(),
+        ]);
+        let values = TagValues {
+            jlpt: Some("N5".to_string()),
+            date: "2026-01-31".to_string(),
+            profile: "Ace Attorney".to_string(),
+        };
+
+        assert_eq!(
+            template.expand_tags(&values),
+            ["saya", "N5", "mined::2026-01-31", "Ace_Attorney"]
+        );
+    }
+
+    #[test]
+    fn test_tags_without_value_are_dropped() {
+        let template = CardTemplate::default_japanese().with_tags(vec!["jlpt::{jlpt}".to_string()]);
+        assert_eq!(template.expand_tags(&TagValues::default()), ["saya"]);
+    }
+
+    fn names(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|f| f.to_string()).collect()
+    }
+
+    #[test]
+    fn test_auto_field_map_basic() {
+        let map = auto_field_map(&names(&["Front", "Back"]));
+        assert_eq!(map["Front"], "{term}");
+        assert_eq!(map["Back"], "{definition}");
+    }
+
+    #[test]
+    fn test_auto_field_map_falls_back_to_first_field() {
+        let map = auto_field_map(&names(&["Kanji", "Kana", "Notes"]));
+        assert_eq!(map["Kanji"], "{term}");
+        assert_eq!(map["Kana"], "{reading}");
+        assert!(!map.contains_key("Notes"));
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use std::sync::Arc;
+#[cfg(test)]
+use std::sync::Mutex;
+
+use saya_ocr::{MonitorInfo, OcrBackend, RawImage};
+use saya_types::{CaptureMonitor, CaptureRegion};
+
+/// Screen capture and text recognition used by the OCR handlers
+///
+/// Methods are called from a blocking thread.
+pub trait Capturer: Send + Sync {
+    /// Capture `region` from `monitor`, falling back to `monitor_index` when
+    /// it's [`CaptureMonitor::Auto`] and the region spans monitors
+    fn capture_region(
+        &self,
+        region: CaptureRegion,
+        monitor: &CaptureMonitor,
+        monitor_index: usize,
+    ) -> Result<Vec<u8>>;
+
+    /// [`Capturer::capture_region`] with the image's [`saya_ocr::average_hash`]
+    fn capture_hashed(
+        &self,
+        region: CaptureRegion,
+        monitor: &CaptureMonitor,
+        monitor_index: usize,
+    ) -> Result<(Vec<u8>, u64)> {
+        let png = self.capture_region(region, monitor, monitor_index)?;
+        let hash = saya_ocr::average_hash(&RawImage::from_png(&png)?);
+        Ok((png, hash))
+    }
+
+    /// Capture a window, or the whole of `monitor_index` when `window_id` is `None`
+    fn capture_window(&self, window_id: Option<u32>, monitor_index: usize) -> Result<Vec<u8>>;
+
+    /// Recognize the text in a PNG-encoded image
+    fn recognize(&self, image: &[u8], language: &str) -> Result<String>;
+
+    /// Monitors connected now; none means capture regions can't be checked
+    fn monitors(&self) -> Result<Vec<MonitorInfo>> {
+        Ok(Vec::new())
+    }
+}
+
+/// xcap capture with the configured OCR backend
+pub struct ScreenCapturer {
+    backend: Arc<dyn OcrBackend>,
+}
+
+impl ScreenCapturer {
+    pub fn new(backend: Arc<dyn OcrBackend>) -> Self {
+        Self { backend }
+    }
+}
+
+impl Capturer for ScreenCapturer {
+    fn capture_region(
+        &self,
+        region: CaptureRegion,
+        monitor: &CaptureMonitor,
+        monitor_index: usize,
+    ) -> Result<Vec<u8>> {
+        let _com = saya_ocr::ComGuard::initialize()?;
+        saya_ocr::capture_screen_region(region, monitor, monitor_index)
+    }
+
+    fn capture_hashed(
+        &self,
+        region: CaptureRegion,
+        monitor: &CaptureMonitor,
+        monitor_index: usize,
+    ) -> Result<(Vec<u8>, u64)> {
+        let _com = saya_ocr::ComGuard::initialize()?;
+        let (image, hash) = saya_ocr::capture_and_hash(region, monitor, monitor_index)?;
+        Ok((image.to_png()?, hash))
+    }
+
+    fn capture_window(&self, window_id: Option<u32>, monitor_index: usize) -> Result<Vec<u8>> {
+        let _com = saya_ocr::ComGuard::initialize()?;
+        match window_id {
+            Some(id) => {
+                tracing::debug!(">>> [OCR] Capturing window {}", id);
+                saya_ocr::capture_window(id)
+            }
+            None => {
+                tracing::debug!(">>> [OCR] Capturing monitor {}", monitor_index);
+                saya_ocr::capture_full_screen(monitor_index)
+            }
+        }
+    }
+
+    fn recognize(&self, image: &[u8], language: &str) -> Result<String> {
+        let _com = saya_ocr::ComGuard::initialize()?;
+        let image = RawImage::from_png(image)?;
+        self.backend.recognize(&image, language)
+    }
+
+    fn monitors(&self) -> Result<Vec<MonitorInfo>> {
+        saya_ocr::list_monitors()
+    }
+}
+
+/// Capturer returning canned text without touching the screen
+#[cfg(test)]
+pub struct FakeCapturer {
+    text: String,
+    pub regions: Mutex<Vec<CaptureRegion>>,
+    /// Language of each `recognize` call
+    pub languages: Mutex<Vec<String>>,
+    /// What `monitors` reports
+    pub monitors: Mutex<Vec<MonitorInfo>>,
+}
+
+#[cfg(test)]
+impl FakeCapturer {
+    pub fn new(text: &str) -> Arc<Self> {
+        Arc::new(Self {
+            text: text.to_string(),
+            regions: Mutex::new(Vec::new()),
+            languages: Mutex::new(Vec::new()),
+            monitors: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+#[cfg(test)]
+impl Capturer for FakeCapturer {
+    /// A blank PNG the size of `region`
+    fn capture_region(
+        &self,
+        region: CaptureRegion,
+        _monitor: &CaptureMonitor,
+        _monitor_index: usize,
+    ) -> Result<Vec<u8>> {
+        self.regions.lock().unwrap().push(region);
+        RawImage {
+            data: vec![255; region.width as usize * region.height as usize * 4],
+            width: region.width,
+            height: region.height,
+        }
+        .to_png()
+    }
+
+    fn capture_window(&self, _window_id: Option<u32>, _monitor_index: usize) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn recognize(&self, _image: &[u8], language: &str) -> Result<String> {
+        self.languages.lock().unwrap().push(language.to_string());
+        Ok(self.text.clone())
+    }
+
+    fn monitors(&self) -> Result<Vec<MonitorInfo>> {
+        Ok(self.monitors.lock().unwrap().clone())
+    }
+}
@@ -2,14 +2,21 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use kanal::{AsyncReceiver, AsyncSender};
-use saya_lang_japanese::{JapaneseProcessor, JapaneseTranslator};
+use saya_core::language::LanguageProcessor;
+use saya_translator::Translator;
 use saya_types::AppEvent;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 
+use crate::events::create_card::anki_client;
+use crate::events::dictionary_status::report_dictionary_status;
 use crate::events::event_loop;
 use crate::io::watcher_io;
+use crate::known_words::load_from_anki;
+use crate::languages::{DeferredProcessor, load_processor};
+use crate::setup::run_setup;
 use crate::state::AppState;
+use crate::status::status_server;
 use crate::ui::ui_loop;
 
 /// Centralized channel management
@@ -27,6 +34,48 @@ impl ChannelSet {
     }
 }
 
+/// How often channel occupancy is sampled
+const CHANNEL_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Share of a channel's capacity at which sends are about to block
+pub const NEAR_CAPACITY_RATIO: f64 = 0.8;
+
+/// Log how full `tx` is, warning and returning `true` when it is near capacity
+pub fn sample_channel<T>(name: &str, tx: &AsyncSender<T>) -> bool {
+    let len = tx.len();
+    let capacity = tx.capacity();
+    tracing::debug!("[CHANNELS] {}: {}/{}", name, len, capacity);
+
+    let near_capacity = capacity > 0 && len as f64 >= capacity as f64 * NEAR_CAPACITY_RATIO;
+    if near_capacity {
+        tracing::warn!(
+            "[CHANNELS] {} near capacity ({}/{}), sends may block",
+            name,
+            len,
+            capacity
+        );
+    }
+    near_capacity
+}
+
+/// Sample both event channels until cancelled
+async fn channel_monitor(
+    app_to_ui: AsyncSender<AppEvent>,
+    ui_to_app: AsyncSender<AppEvent>,
+    cancel: CancellationToken,
+) {
+    let mut interval = tokio::time::interval(CHANNEL_SAMPLE_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            _ = interval.tick() => {
+                sample_channel("app_to_ui", &app_to_ui);
+                sample_channel("ui_to_app", &ui_to_app);
+            }
+        }
+    }
+}
+
 /// Application controller for task spawning and lifecycle
 pub struct AppController {
     channels: ChannelSet,
@@ -45,16 +94,28 @@ impl AppController {
 
     pub fn spawn_tasks(
         &self,
-        processor: Arc<JapaneseProcessor>,
-        translator: Arc<Option<JapaneseTranslator>>,
+        processor: Arc<DeferredProcessor>,
+        translator: Option<Arc<dyn Translator>>,
     ) -> JoinSet<anyhow::Result<()>> {
         let mut tasks = JoinSet::new();
 
+        // Dictionary loading (detached: big dictionaries take seconds to build)
+        let load_state = self.state.clone();
+        let load_tx = self.channels.app_to_ui.0.clone();
+        let deferred = processor.clone();
+        tokio::spawn(async move {
+            let config = load_state.config.read().await.clone();
+            let status = load_processor(deferred, config, &load_tx).await;
+            load_state.status.mark_dictionary_ready();
+            report_dictionary_status(load_state, load_tx, status);
+        });
+        let processor: Arc<dyn LanguageProcessor> = processor;
+
         // Event loop
         tasks.spawn(event_loop(
             self.state.clone(),
             self.channels.ui_to_app.1.clone(),
-            self.channels.app_to_ui.0.clone(),
+            self.channels.app_to_ui.clone(),
             processor,
             translator,
         ));
@@ -75,6 +136,56 @@ impl AppController {
             self.channels.app_to_ui.0.clone(),
         ));
 
+        // Channel occupancy, for diagnosing stalls under OCR bursts (detached)
+        tokio::spawn(channel_monitor(
+            self.channels.app_to_ui.0.clone(),
+            self.channels.ui_to_app.0.clone(),
+            self.cancel_token.child_token(),
+        ));
+
+        // Status server (detached: returns immediately when disabled)
+        let status_state = self.state.clone();
+        let status_cancel = self.cancel_token.child_token();
+        tokio::spawn(async move {
+            if let Err(e) = status_server(status_state, status_cancel).await {
+                tracing::error!("Status server error: {e}");
+            }
+        });
+
+        // Known words from the Anki collection (detached, skipped if Anki isn't running)
+        let anki_state = self.state.clone();
+        tokio::spawn(async move {
+            let (client, deck) = {
+                let config = anki_state.config.read().await;
+                if !config.anki.enabled {
+                    return;
+                }
+                let Some(deck) = config.anki.known_words_deck.clone() else {
+                    return;
+                };
+                match anki_client(&config) {
+                    Ok(client) => (client, deck),
+                    Err(e) => {
+                        tracing::warn!("Skipping known words from Anki: {e:#}");
+                        return;
+                    }
+                }
+            };
+            load_from_anki(&anki_state.known_words, &client, &deck).await;
+        });
+
+        // First-run setup (detached: finishing it must not shut the app down)
+        let setup_state = self.state.clone();
+        let setup_tx = self.channels.app_to_ui.0.clone();
+        tokio::spawn(async move {
+            if setup_state.config.read().await.setup_complete {
+                return;
+            }
+            if let Err(e) = run_setup(setup_state, setup_tx).await {
+                tracing::error!("Setup error: {e}");
+            }
+        });
+
         tasks
     }
 
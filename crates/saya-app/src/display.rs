@@ -0,0 +1,445 @@
+use kanal::{AsyncReceiver, AsyncSender};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use saya_config::Config;
+use saya_config::dictionary::SortBy;
+use saya_config::ui::{Badge, ReadingDisplay};
+use saya_core::language::{LanguageProcessor, LookupResult};
+use saya_types::{
+    AppEvent, DisplayResult, JlptLevel, PREVIEW_DEFINITIONS, is_hiragana, is_kanji, is_katakana,
+    truncate_display,
+};
+
+use crate::known_words::KnownWords;
+
+/// Tokens looked up per text
+const MAX_TOKENS: usize = 10;
+/// Results kept per token
+const MAX_RESULTS_PER_TOKEN: usize = 5;
+/// Results per streamed event
+const BATCH_SIZE: usize = 5;
+
+/// Config-driven choices for building display results
+#[derive(Debug, Clone)]
+pub struct DisplayOptions {
+    pub reading_display: ReadingDisplay,
+    /// Input beyond this many chars is ignored
+    pub max_input_chars: usize,
+    pub sort_by: SortBy,
+    pub jlpt_harder_first: bool,
+    /// Words to hide, unless `show_known` is set
+    pub known_words: Option<Arc<KnownWords>>,
+    pub show_known: bool,
+    /// Fall back to [`unmatched_results`] when nothing matched
+    pub show_unmatched: bool,
+    /// Metadata badges to fill in, the rest are left `None`
+    pub badges: Vec<Badge>,
+    /// Collapsed definitions are cut to this many chars, 0 never cuts them
+    pub definition_max_chars: usize,
+}
+
+impl DisplayOptions {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            reading_display: config.ui.reading_display,
+            max_input_chars: config.dictionary.max_input_chars,
+            sort_by: config.dictionary.sort_by,
+            jlpt_harder_first: config.dictionary.jlpt_harder_first,
+            known_words: None,
+            show_known: config.dictionary.show_known,
+            show_unmatched: config.dictionary.show_unmatched,
+            badges: config.ui.show_badges.clone(),
+            definition_max_chars: config.ui.definition_max_chars,
+        }
+    }
+
+    /// Hide results for words in `known_words`
+    pub fn with_known_words(mut self, known_words: Arc<KnownWords>) -> Self {
+        self.known_words = Some(known_words);
+        self
+    }
+
+    /// Whether `result` is a known word that should be hidden
+    pub fn is_hidden(&self, result: &LookupResult) -> bool {
+        let Some(known) = self.known_words.as_ref().filter(|_| !self.show_known) else {
+            return false;
+        };
+        known.contains(&result.term)
+            || result.metadata.get("base_form").is_some_and(|base| known.contains(base))
+    }
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self::from_config(&Config::default())
+    }
+}
+
+/// Cut `text` to at most `max_chars` chars
+pub fn cap_input(text: &str, max_chars: usize) -> &str {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => {
+            tracing::info!("Input truncated to {} chars", max_chars);
+            &text[..end]
+        }
+        None => text,
+    }
+}
+
+/// Collapsed preview of `definitions` and whether it leaves anything out
+///
+/// Shows the first [`PREVIEW_DEFINITIONS`], cut with an ellipsis to at most
+/// `max_chars` columns when that's nonzero, see [`truncate_display`].
+pub fn preview_definition(definitions: &[String], max_chars: usize) -> (String, bool) {
+    let shown = PREVIEW_DEFINITIONS.min(definitions.len());
+    let mut preview = definitions[..shown].join("; ");
+
+    if max_chars > 0 {
+        let cut = truncate_display(&preview, max_chars);
+        if cut != preview {
+            return (cut, true);
+        }
+    }
+
+    if shown < definitions.len() {
+        preview.push_str("; …");
+        (preview, true)
+    } else {
+        (preview, false)
+    }
+}
+
+/// Convert a processor lookup into what the UI shows
+pub fn to_display_result(result: &LookupResult, options: &DisplayOptions) -> DisplayResult {
+    let mut readings = result.readings.clone();
+
+    // Kana-only words have nothing to swap
+    let term = if readings.is_empty() || readings[0] == result.term {
+        result.term.clone()
+    } else {
+        match options.reading_display {
+            ReadingDisplay::KanjiFirst => result.term.clone(),
+            ReadingDisplay::ReadingFirst => {
+                let reading = readings.remove(0);
+                readings.insert(0, result.term.clone());
+                reading
+            }
+            ReadingDisplay::ReadingOnly => readings.remove(0),
+        }
+    };
+
+    let badge = |badge: Badge, key: &str| {
+        options
+            .badges
+            .contains(&badge)
+            .then(|| result.metadata.get(key).cloned())
+            .flatten()
+    };
+
+    let (definition, truncated) =
+        preview_definition(&result.definitions, options.definition_max_chars);
+
+    DisplayResult {
+        term,
+        readings,
+        definitions: result.definitions.clone(),
+        definition,
+        full_definition: result.definitions.join("; "),
+        truncated,
+        frequency: badge(Badge::Frequency, "frequency_stars"),
+        pitch_accent: badge(Badge::Pitch, "pitch_accent"),
+        jlpt_level: badge(Badge::Jlpt, "jlpt_level"),
+        jlpt: jlpt_level(result),
+        conjugation: badge(Badge::Conjugation, "conjugation"),
+        conjugation_confidence: badge(Badge::Conjugation, "conjugation_confidence")
+            .and_then(|c| c.parse().ok()),
+        part_of_speech: badge(Badge::Pos, "part_of_speech"),
+        fields: badge(Badge::Field, "fields"),
+        source: result.metadata.get("source").cloned(),
+        breakdown: breakdown(result),
+        examples: result
+            .metadata
+            .get("examples")
+            .map(|examples| parse_examples(examples))
+            .unwrap_or_default(),
+        entry_id: result.metadata.get("entry_id").cloned(),
+        base_form: Some(
+            result
+                .metadata
+                .get("base_form")
+                .unwrap_or(&result.term)
+                .clone(),
+        ),
+        surface: result.metadata.get("surface").cloned(),
+        metadata: options
+            .badges
+            .iter()
+            .filter_map(|badge| match badge {
+                Badge::Metadata(key) => Some((key.clone(), result.metadata.get(key)?.clone())),
+                _ => None,
+            })
+            .collect(),
+    }
+}
+
+/// Summary line for `character_info` results
+fn breakdown(result: &LookupResult) -> Option<String> {
+    let strokes = result.metadata.get("strokes")?;
+    let mut parts = vec![format!("{} strokes", strokes)];
+
+    for (key, label) in [
+        ("radicals", "Radicals"),
+        ("on_readings", "On"),
+        ("kun_readings", "Kun"),
+    ] {
+        if let Some(value) = result.metadata.get(key).filter(|v| !v.is_empty()) {
+            parts.push(format!("{}: {}", label, value));
+        }
+    }
+
+    Some(parts.join(" · "))
+}
+
+/// Decode the processor's "japanese\ttranslation" lines
+fn parse_examples(examples: &str) -> Vec<(String, String)> {
+    examples
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(japanese, translation)| (japanese.to_string(), translation.to_string()))
+        .collect()
+}
+
+/// Order results by `options.sort_by`
+///
+/// The sort is stable, so ties keep tokenizer order. Results missing the sort
+/// key (no frequency rank, no JLPT level) go last.
+pub fn sort_results(results: &mut [LookupResult], options: &DisplayOptions) {
+    match options.sort_by {
+        SortBy::Frequency => results.sort_by_key(|r| {
+            r.metadata
+                .get("frequency_rank")
+                .and_then(|rank| rank.parse::<u32>().ok())
+                .unwrap_or(u32::MAX)
+        }),
+        SortBy::Jlpt => results.sort_by_key(|r| match jlpt_level(r).map(|l| l.number()) {
+            Some(level) if options.jlpt_harder_first => level,
+            // N5 is easiest
+            Some(level) => 5u8.saturating_sub(level),
+            None => u8::MAX,
+        }),
+        SortBy::Length => results.sort_by_key(|r| Reverse(r.term.chars().count())),
+        SortBy::None => {}
+    }
+}
+
+/// JLPT level from the processor's badge
+fn jlpt_level(result: &LookupResult) -> Option<JlptLevel> {
+    JlptLevel::from_badge(result.metadata.get("jlpt_level")?)
+}
+
+/// Display results per token, looked up lazily so they can be streamed
+///
+/// With a sort order every token is looked up first and the results come as one batch.
+pub fn token_results<'a>(
+    processor: &'a dyn LanguageProcessor,
+    text: &str,
+    options: DisplayOptions,
+) -> Box<dyn Iterator<Item = Vec<DisplayResult>> + 'a> {
+    let text = cap_input(text, options.max_input_chars);
+    let normalized = processor.normalize(&text);
+    let tokens = processor.tokenize(&normalized);
+    tracing::debug!("Tokenized into {} tokens", tokens.len());
+
+    let filter = options.clone();
+    let lookups = tokens.into_iter().take(MAX_TOKENS).map(move |token| {
+        let mut results = processor.lookup(&token);
+        results.retain(|result| !filter.is_hidden(result));
+        results.truncate(MAX_RESULTS_PER_TOKEN);
+        results
+    });
+
+    if options.sort_by == SortBy::None {
+        return Box::new(lookups.map(move |results| {
+            results
+                .iter()
+                .map(|result| to_display_result(result, &options))
+                .collect()
+        }));
+    }
+
+    let mut results: Vec<LookupResult> = lookups.flatten().collect();
+    sort_results(&mut results, &options);
+    let sorted = results
+        .iter()
+        .map(|result| to_display_result(result, &options))
+        .collect();
+    Box::new(std::iter::once(sorted))
+}
+
+/// Definition shown for segments without a dictionary entry
+pub const UNMATCHED_DEFINITION: &str = "(no dictionary entry)";
+
+#[derive(PartialEq)]
+enum Script {
+    Kanji,
+    Hiragana,
+    Katakana,
+    Other,
+}
+
+fn script(ch: char) -> Option<Script> {
+    match ch {
+        c if c == '々' || is_kanji(c) => Some(Script::Kanji),
+        c if is_hiragana(c) => Some(Script::Hiragana),
+        c if is_katakana(c) => Some(Script::Katakana),
+        c if c.is_alphanumeric() => Some(Script::Other),
+        _ => None,
+    }
+}
+
+/// Split `text` into runs of the same script, dropping punctuation and spaces
+///
+/// Without a dictionary hit script changes are the best guess at word boundaries.
+pub fn segment_unmatched(text: &str) -> Vec<String> {
+    let mut segments: Vec<String> = Vec::new();
+    let mut current: Option<Script> = None;
+
+    for ch in text.chars() {
+        let Some(kind) = script(ch) else {
+            current = None;
+            continue;
+        };
+        match segments.last_mut() {
+            Some(last) if current.as_ref() == Some(&kind) => last.push(ch),
+            _ => segments.push(ch.to_string()),
+        }
+        current = Some(kind);
+    }
+
+    segments
+}
+
+/// Placeholder results for text the dictionary knows nothing about
+pub fn unmatched_results(
+    processor: &dyn LanguageProcessor,
+    text: &str,
+    options: &DisplayOptions,
+) -> Vec<DisplayResult> {
+    let text = cap_input(text, options.max_input_chars);
+    segment_unmatched(&processor.normalize(text))
+        .into_iter()
+        .take(MAX_TOKENS)
+        .map(|term| DisplayResult {
+            term,
+            readings: vec![],
+            definitions: vec![UNMATCHED_DEFINITION.to_string()],
+            definition: UNMATCHED_DEFINITION.to_string(),
+            full_definition: UNMATCHED_DEFINITION.to_string(),
+            truncated: false,
+            frequency: None,
+            pitch_accent: None,
+            jlpt_level: None,
+            jlpt: None,
+            conjugation: None,
+            conjugation_confidence: None,
+            part_of_speech: None,
+            fields: None,
+            source: None,
+            breakdown: None,
+            examples: vec![],
+            entry_id: None,
+            base_form: None,
+            surface: None,
+            metadata: HashMap::new(),
+        })
+        .collect()
+}
+
+/// Send results as they resolve: `ShowResults` for the first batch, then `AppendResults`
+///
+/// Stops early once `is_current` returns false. Returns the number of results sent.
+///
+/// With `queue`, the receiving end of `tx`, the first batch is sent with
+/// [`send_latest`] so a full channel can't stall the caller.
+pub async fn stream_results(
+    batches: impl Iterator<Item = Vec<DisplayResult>>,
+    tx: &AsyncSender<AppEvent>,
+    queue: Option<&AsyncReceiver<AppEvent>>,
+    is_current: impl Fn() -> bool,
+) -> anyhow::Result<usize> {
+    let mut pending = Vec::new();
+    let mut sent = 0;
+
+    for batch in batches {
+        pending.extend(batch);
+        if pending.len() >= BATCH_SIZE {
+            if !is_current() {
+                return Ok(sent);
+            }
+            sent += send_batch(tx, queue, std::mem::take(&mut pending), sent == 0).await?;
+        }
+    }
+
+    if !pending.is_empty() && is_current() {
+        sent += send_batch(tx, queue, pending, sent == 0).await?;
+    }
+
+    Ok(sent)
+}
+
+async fn send_batch(
+    tx: &AsyncSender<AppEvent>,
+    queue: Option<&AsyncReceiver<AppEvent>>,
+    results: Vec<DisplayResult>,
+    first: bool,
+) -> anyhow::Result<usize> {
+    let count = results.len();
+    match (first, queue) {
+        (true, Some(queue)) => send_latest(tx, queue, AppEvent::ShowResults(results)).await?,
+        (true, None) => tx.send(AppEvent::ShowResults(results)).await?,
+        (false, _) => tx.send(AppEvent::AppendResults(results)).await?,
+    }
+
+    Ok(count)
+}
+
+/// Send `event` without waiting on a full channel
+///
+/// While `tx` is full, results still queued on `queue`, the receiving end of
+/// `tx`, are dropped: they are superseded by `event`, so a slow UI skips to
+/// the newest results instead of blocking OCR. Every other queued event is
+/// put back in order ahead of `event`.
+pub async fn send_latest(
+    tx: &AsyncSender<AppEvent>,
+    queue: &AsyncReceiver<AppEvent>,
+    event: AppEvent,
+) -> anyhow::Result<()> {
+    // A rendezvous channel is never "full", there is nothing to drop
+    if tx.capacity() == 0 {
+        tx.send(event).await?;
+        return Ok(());
+    }
+
+    let mut pending = Some(event);
+    if tx.try_send_option(&mut pending)? {
+        return Ok(());
+    }
+
+    let mut kept = Vec::new();
+    while let Some(queued) = queue.try_recv()? {
+        if matches!(queued, AppEvent::ShowResults(_) | AppEvent::AppendResults(_)) {
+            tracing::debug!("[CHANNELS] UI behind, dropped queued {}", queued.name());
+        } else {
+            kept.push(queued);
+        }
+    }
+    for queued in kept {
+        tx.send(queued).await?;
+    }
+    if let Some(event) = pending {
+        tx.send(event).await?;
+    }
+    Ok(())
+}
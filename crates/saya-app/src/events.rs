@@ -1,44 +1,93 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use kanal::{AsyncReceiver, AsyncSender};
 use saya_anki::AnkiConnectClient;
-use saya_lang_japanese::{JapaneseProcessor, JapaneseTranslator};
-use saya_types::AppEvent;
+use saya_core::language::LanguageProcessor;
+use saya_translator::Translator;
+use saya_types::{AppEvent, ErrorKind};
 use trigger_auto_ocr::start_auto_ocr_loop;
 
+use crate::display::DisplayOptions;
 use crate::ocr_context::OcrContext;
-use crate::profile::{save_config, update_config_field};
+use crate::profile::{check_capture_region, save_config, update_config_field};
 use crate::state::AppState;
 
+pub mod anki_status;
+pub mod capture_preview;
 pub mod capture_window;
+pub mod copy_to_clipboard;
 pub mod create_card;
+pub mod dictionary_status;
+pub mod lookup_by_id;
+pub mod lookup_kanji;
+pub mod ocr_result;
+pub mod search_query;
+pub mod set_watchers_paused;
 pub mod text_input;
+pub mod toggle_click_through;
+pub mod translate_text;
 pub mod trigger_auto_ocr;
 pub mod trigger_ocr;
+pub mod update_capture_region;
 
+use anki_status::anki_health_loop;
+use capture_preview::handle_capture_preview;
 use capture_window::handle_window_capture;
-use create_card::handle_card_creation;
+use copy_to_clipboard::handle_copy_to_clipboard;
+use create_card::{handle_card_creation, handle_card_preview};
+use lookup_by_id::handle_lookup_by_id;
+use lookup_kanji::handle_lookup_kanji;
+use search_query::handle_search_query;
+use set_watchers_paused::handle_set_watchers_paused;
 use text_input::handle_text_input;
+use toggle_click_through::handle_toggle_click_through;
+use translate_text::handle_translate_text;
 use trigger_ocr::handle_ocr_trigger;
+use update_capture_region::handle_capture_region_update;
+
+/// Tell the UI about a failure
+pub async fn send_error(tx: &AsyncSender<AppEvent>, kind: ErrorKind, message: impl Into<String>) {
+    let message = message.into();
+    if let Err(e) = tx.send(AppEvent::Error { kind, message }).await {
+        tracing::warn!("Failed to report {}: {}", kind.as_str(), e);
+    }
+}
 
 /// App's main loop
 pub async fn event_loop(
     state: Arc<AppState>,
     ui_to_app_rx: AsyncReceiver<AppEvent>,
-    app_to_ui_tx: AsyncSender<AppEvent>,
-    processor: Arc<JapaneseProcessor>,
-    translator: Arc<Option<JapaneseTranslator>>,
+    (app_to_ui_tx, app_to_ui_rx): (AsyncSender<AppEvent>, AsyncReceiver<AppEvent>),
+    processor: Arc<dyn LanguageProcessor>,
+    translator: Option<Arc<dyn Translator>>,
 ) -> anyhow::Result<()> {
     // Initialize Anki client
-    let anki_client = {
+    let (anki_client, health_check_interval) = {
         let config = state.config.read().await;
-        if config.anki.enabled {
-            Some(saya_anki::AnkiConnectClient::new(config.anki.url.clone()))
-        } else {
-            None
-        }
+        let client = config
+            .anki
+            .enabled
+            .then(|| create_card::anki_client(&config))
+            .and_then(|client| {
+                client
+                    .inspect_err(|e| tracing::error!("Anki disabled: {e:#}"))
+                    .ok()
+            });
+        (client, config.anki.health_check_interval_ms)
     };
 
+    // Live Anki indicator, sharing the card client's circuit breaker
+    if let Some(client) = &anki_client
+        && health_check_interval > 0
+    {
+        tokio::spawn(anki_health_loop(
+            client.clone(),
+            Duration::from_millis(health_check_interval),
+            app_to_ui_tx.clone(),
+        ));
+    }
+
     tracing::info!("[EVENT_LOOP] Starting main loop, waiting for events");
 
     // Create OcrContext once for all OCR operations
@@ -47,16 +96,14 @@ pub async fn event_loop(
         app_to_ui_tx.clone(),
         processor.clone(),
         translator.clone(),
-    );
+    )
+    .with_ui_queue(app_to_ui_rx);
 
     loop {
         tracing::info!("[EVENT_LOOP] Calling recv().await...");
         let event = ui_to_app_rx.recv().await?;
 
-        tracing::info!(
-            "[EVENT_LOOP] EVENT RECEIVED: {:?}",
-            std::mem::discriminant(&event)
-        );
+        tracing::info!("[EVENT_LOOP] EVENT RECEIVED: {}", event.name());
 
         handle_events(
             state.clone(),
@@ -75,8 +122,8 @@ async fn handle_events(
     state: Arc<AppState>,
     event: AppEvent,
     app_to_ui_tx: &AsyncSender<AppEvent>,
-    processor: &Arc<JapaneseProcessor>,
-    _translator: &Arc<Option<JapaneseTranslator>>,
+    processor: &Arc<dyn LanguageProcessor>,
+    _translator: &Option<Arc<dyn Translator>>,
     anki_client: Option<&AnkiConnectClient>,
     ocr_ctx: &OcrContext,
 ) -> anyhow::Result<()> {
@@ -90,11 +137,40 @@ async fn handle_events(
         AppEvent::ConfigUpdate { field, value } => {
             tracing::info!("Config update: {} = {}", field, value);
 
+            // Listed before locking, enumerating monitors can be slow
+            let monitors = if field.starts_with("ocr.") {
+                Some(state.monitors().await)
+            } else {
+                None
+            };
+
             let mut config = state.config.write().await;
+            let language = config.dictionary_language().to_string();
             update_config_field(&mut config, &field, &value)?;
+            if let Some(monitors) = monitors {
+                check_capture_region(&mut config, monitors);
+            }
+
+            // The processor is built once at startup, see `DeferredProcessor`
+            if config.dictionary_language() != language {
+                tracing::warn!(
+                    "Dictionary language changed to {}, takes effect after a restart",
+                    config.dictionary_language()
+                );
+                let status = format!(
+                    "Restart to switch the dictionary to {}",
+                    config.dictionary_language()
+                );
+                app_to_ui_tx
+                    .send(AppEvent::OcrStatusUpdate {
+                        status,
+                        capturing: false,
+                    })
+                    .await?;
+            }
 
             // Persist config to disk
-            save_config(config.clone(), "main")?;
+            save_config(config.clone(), &state.profile)?;
 
             // Broadcast config change to components
             app_to_ui_tx.send(AppEvent::ConfigChanged).await?;
@@ -102,12 +178,22 @@ async fn handle_events(
         AppEvent::UiEvent(_event) => {}
         AppEvent::ApiRequest(_event) => {}
         AppEvent::ShowResults(_) => {}
+        AppEvent::AppendResults(_) => {}
         AppEvent::RawTextInput { text: _, source: _ } => {
             // RawTextInput events are handled by UI layer, no processing needed here
         }
         AppEvent::CreateCard(result) => {
             // Anki Card Creation
-            handle_card_creation(state, result, anki_client).await?;
+            handle_card_creation(state, result, anki_client, app_to_ui_tx).await?;
+        }
+        AppEvent::PreviewCard(result) => {
+            handle_card_preview(state, result, anki_client, app_to_ui_tx).await?;
+        }
+        AppEvent::CardPreview { .. } => {
+            // UI-only event, ignore in backend
+        }
+        AppEvent::CopyToClipboard(text) => {
+            handle_copy_to_clipboard(&state, text).await?;
         }
         AppEvent::TriggerOcr(region) => {
             tracing::debug!(">>> [OCR] Triggered");
@@ -124,16 +210,20 @@ async fn handle_events(
             start_auto_ocr_loop(ocr_ctx, region);
         }
         AppEvent::UpdateCaptureRegion(region) => {
-            // Update region while auto OCR is running (window moved/resized)
-            *state.current_capture_region.write().await = Some(region);
-            tracing::debug!(">>> [OCR] Region updated: {}x{} at ({}, {})",
-                region.width, region.height, region.x, region.y);
+            // Sent by the region picker and while auto OCR follows the window
+            handle_capture_region_update(&state, region).await?;
         }
         AppEvent::CaptureWindow { window_id } => {
             tracing::debug!(">>> [OCR] CaptureWindow: {:?} <<<", window_id);
 
             handle_window_capture(ocr_ctx, window_id).await?;
         }
+        AppEvent::PreviewCapture(region) => {
+            handle_capture_preview(&state, region, app_to_ui_tx).await?;
+        }
+        AppEvent::CapturePreview(_) => {
+            // UI-only event, ignore in backend
+        }
         AppEvent::OcrStatusUpdate { status, capturing } => {
             tracing::info!("OCR status: {} (capturing: {})", status, capturing);
         }
@@ -141,7 +231,30 @@ async fn handle_events(
             tracing::debug!("TextInput received: '{}' chars", text.len());
             tracing::info!("Processing text: {}", text);
 
-            handle_text_input(text, processor, app_to_ui_tx).await?;
+            let options = DisplayOptions::from_config(&*state.config.read().await)
+                .with_known_words(state.known_words.clone());
+            handle_text_input(text, processor.as_ref(), app_to_ui_tx, options).await?;
+        }
+        AppEvent::LookupById(id) => {
+            let options = DisplayOptions::from_config(&*state.config.read().await);
+            handle_lookup_by_id(id, processor.as_ref(), app_to_ui_tx, options).await?;
+        }
+        AppEvent::LookupKanji(ch) => {
+            let options = DisplayOptions::from_config(&*state.config.read().await);
+            handle_lookup_kanji(ch, processor.as_ref(), app_to_ui_tx, options).await?;
+        }
+        AppEvent::SearchQuery(query) => {
+            let options = DisplayOptions::from_config(&*state.config.read().await)
+                .with_known_words(state.known_words.clone());
+            // Taken in arrival order, then spawned so the next keystroke can overtake it
+            let generation = state.next_search_generation();
+            let (processor, tx) = (processor.clone(), app_to_ui_tx.clone());
+            tokio::spawn(async move {
+                let search = handle_search_query(state, generation, query, processor, tx, options);
+                if let Err(e) = search.await {
+                    tracing::warn!("Search failed: {}", e);
+                }
+            });
         }
         AppEvent::BackendReady => {
             // UI-only event, ignore in backend
@@ -149,6 +262,35 @@ async fn handle_events(
         AppEvent::ShowTranslation { .. } => {
             // UI-only event, ignore in backend
         }
+        AppEvent::TranslateText(text) => {
+            handle_translate_text(ocr_ctx, text).await?;
+        }
+        AppEvent::DictionaryLoading
+        | AppEvent::DictionaryProgress { .. }
+        | AppEvent::DictionaryReady { .. } => {
+            // UI-only event, ignore in backend
+        }
+        AppEvent::SetupStep { .. } => {
+            // UI-only event, ignore in backend
+        }
+        AppEvent::Error { kind, message } => {
+            tracing::debug!("{}: {}", kind.as_str(), message);
+        }
+        AppEvent::ToggleClickThrough => {
+            handle_toggle_click_through(&state, app_to_ui_tx).await?;
+        }
+        AppEvent::ClickThroughChanged { .. } => {
+            // UI-only event, ignore in backend
+        }
+        AppEvent::SetWatchersPaused(paused) => {
+            handle_set_watchers_paused(&state, paused, app_to_ui_tx).await?;
+        }
+        AppEvent::WatchersPausedChanged { .. } => {
+            // UI-only event, ignore in backend
+        }
+        AppEvent::AnkiStatus { .. } => {
+            // UI-only event, ignore in backend
+        }
         AppEvent::HotkeyOcrTriggered => {
             tracing::debug!(">>> [EVENT] Hotkey OCR triggered");
 
@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+use kanal::AsyncSender;
+use saya_anki::AnkiConnectClient;
+use saya_types::AppEvent;
+
+/// Check AnkiConnect every `interval`, sending `AnkiStatus` whenever it changes
+///
+/// `client` is the one cards are added with, so the check shares its circuit
+/// breaker: while the breaker is open Anki reads as unavailable without a
+/// request, and the probe after the cooldown is what notices Anki opening.
+/// Returns once the UI side of `tx` is gone.
+pub async fn anki_health_loop(
+    client: AnkiConnectClient,
+    interval: Duration,
+    tx: AsyncSender<AppEvent>,
+) {
+    let mut last = None;
+
+    loop {
+        let version = client.check_connection().await.ok();
+        if last != Some(version) {
+            let ok = version.is_some();
+            tracing::info!("Anki {}", if ok { "connected" } else { "unavailable" });
+            if tx.send(AppEvent::AnkiStatus { ok, version }).await.is_err() {
+                return;
+            }
+            last = Some(version);
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
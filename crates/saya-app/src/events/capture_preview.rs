@@ -0,0 +1,53 @@
+use kanal::AsyncSender;
+use saya_types::{AppEvent, CaptureRegion, ErrorKind};
+
+use super::send_error;
+use crate::state::AppState;
+
+/// Capture `region` and send the image back without running OCR
+///
+/// Shows exactly what a `TriggerOcr` on the same region would read.
+pub async fn handle_capture_preview(
+    state: &AppState,
+    region: CaptureRegion,
+    app_to_ui_tx: &AsyncSender<AppEvent>,
+) -> anyhow::Result<()> {
+    if region.width == 0 || region.height == 0 {
+        app_to_ui_tx
+            .send(AppEvent::OcrStatusUpdate {
+                status: "Preview failed: empty region".to_string(),
+                capturing: false,
+            })
+            .await?;
+        return Ok(());
+    }
+
+    let (monitor, monitor_index) = {
+        let config = state.config.read().await;
+        (config.ocr.capture_monitor.clone(), config.ocr.monitor_index)
+    };
+    let capturer = state.capturer.clone();
+    let captured = tokio::task::spawn_blocking(move || {
+        capturer.capture_region(region, &monitor, monitor_index)
+    })
+    .await?;
+
+    match captured {
+        Ok(png) => {
+            tracing::debug!(">>> [OCR] Preview captured {} bytes", png.len());
+            app_to_ui_tx.send(AppEvent::CapturePreview(png)).await?;
+        }
+        Err(e) => {
+            tracing::warn!(">>> [OCR] Preview capture failed: {}", e);
+            app_to_ui_tx
+                .send(AppEvent::OcrStatusUpdate {
+                    status: format!("Preview failed: {}", e),
+                    capturing: false,
+                })
+                .await?;
+            send_error(app_to_ui_tx, ErrorKind::CaptureFailed, e.to_string()).await;
+        }
+    }
+
+    Ok(())
+}
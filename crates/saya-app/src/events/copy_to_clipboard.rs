@@ -0,0 +1,17 @@
+use crate::state::AppState;
+
+/// Copy a term to the system clipboard without feeding it back through the watcher
+pub async fn handle_copy_to_clipboard(state: &AppState, text: String) -> anyhow::Result<()> {
+    let self_writes = state.clipboard_writes.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        saya_io::clipboard::copy_to_system(&text, &self_writes)
+    })
+    .await?;
+
+    match result {
+        Ok(()) => tracing::debug!("Copied term to clipboard"),
+        Err(e) => tracing::warn!("Failed to copy to clipboard: {}", e),
+    }
+
+    Ok(())
+}
@@ -1,37 +1,216 @@
 use std::sync::Arc;
+use std::time::Duration;
 
-use saya_types::DisplayResult;
+use kanal::AsyncSender;
+use saya_anki::{AnkiConnectClient, CardTemplate, CardValues};
+use saya_config::Config;
+use saya_config::anki::DuplicatePolicy;
+use saya_types::{AppEvent, DisplayResult, ErrorKind};
 
+use super::send_error;
 use crate::AppState;
 
+/// AnkiConnect client with the configured timeout and circuit breaker
+pub fn anki_client(config: &Config) -> anyhow::Result<AnkiConnectClient> {
+    let client =
+        AnkiConnectClient::with_timeout(config.anki.url.clone(), config.request_timeout())?;
+    Ok(client.with_circuit_breaker(
+        config.anki.failure_threshold,
+        Duration::from_millis(config.anki.cooldown_ms),
+    ))
+}
+
+/// Template for `result`: its JLPT level's override, or the default one
+fn card_template(config: &Config, result: &DisplayResult) -> CardTemplate {
+    let anki = &config.anki;
+    let overridden = result
+        .jlpt
+        .and_then(|level| anki.template_overrides.get(&level));
+
+    let template = match overridden {
+        Some(o) => CardTemplate::new(
+            anki.deck.clone(),
+            o.model.clone().unwrap_or_else(|| anki.model.clone()),
+            o.front_template.clone(),
+            o.back_template.clone(),
+        ),
+        None => CardTemplate::new(
+            anki.deck.clone(),
+            anki.model.clone(),
+            anki.front_template.clone(),
+            anki.back_template.clone(),
+        ),
+    };
+    // The field map names the configured note type's fields
+    let field_map = if template.model == anki.model {
+        anki.field_map.clone()
+    } else {
+        None
+    };
+    template
+        .with_tags(anki.tags.clone())
+        .with_field_map(field_map)
+}
+
+/// Map the fields of `template`'s note type when none are configured
+///
+/// Note types with Front and Back fields keep the front and back templates.
+async fn with_detected_fields(client: &AnkiConnectClient, template: CardTemplate) -> CardTemplate {
+    if template.field_map.is_some() {
+        return template;
+    }
+
+    match client.model_field_names(&template.model).await {
+        Ok(fields) if !has_front_and_back(&fields) => {
+            let field_map = saya_anki::auto_field_map(&fields);
+            tracing::debug!("Detected fields of {}: {:?}", template.model, field_map);
+            template.with_field_map(Some(field_map))
+        }
+        Ok(_) => template,
+        Err(e) => {
+            tracing::warn!("Failed to read the fields of {}: {}", template.model, e);
+            template
+        }
+    }
+}
+
+fn has_front_and_back(fields: &[String]) -> bool {
+    ["Front", "Back"]
+        .iter()
+        .all(|name| fields.iter().any(|field| field.eq_ignore_ascii_case(name)))
+}
+
+/// Values filled into the template, metadata as the result showed it
+fn card_values(result: &DisplayResult) -> CardValues {
+    let example = result
+        .examples
+        .iter()
+        .map(|(japanese, translation)| format!("{}\n{}", japanese, translation))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let shown = |value: Option<&str>| value.unwrap_or_default().to_string();
+
+    CardValues {
+        frequency: shown(result.frequency.as_deref()),
+        pitch: shown(result.pitch_accent.as_deref()),
+        jlpt: shown(
+            result
+                .jlpt
+                .filter(|_| result.jlpt_level.is_some())
+                .map(|level| level.as_str()),
+        ),
+        conjugation: shown(result.conjugation.as_deref()),
+        ..CardValues::new(
+            &result.term,
+            &result.readings.join(", "),
+            &result.definitions.join("; "),
+            &example,
+        )
+    }
+}
+
+/// Word a card is de-duplicated on: its dictionary form with `by_base_form`,
+/// otherwise the text it was captured as
+fn duplicate_key(result: &DisplayResult, by_base_form: bool) -> &str {
+    let key = if by_base_form {
+        result.base_form.as_deref()
+    } else {
+        result.surface.as_deref()
+    };
+    key.unwrap_or(&result.term)
+}
+
+/// Headword a card for `key` would have in its term field
+///
+/// Fronts show the dictionary form as displayed, which is the reading with
+/// [`ReadingDisplay::ReadingFirst`](saya_config::ui::ReadingDisplay).
+fn deck_term<'a>(result: &'a DisplayResult, key: &'a str, by_base_form: bool) -> &'a str {
+    if by_base_form { &result.term } else { key }
+}
+
+/// Whether a card for `key` was added this session, or `template`'s deck has
+/// a card with `term` in the field it writes the term to
+async fn is_duplicate(
+    state: &AppState,
+    client: &AnkiConnectClient,
+    template: &CardTemplate,
+    key: &str,
+    term: &str,
+) -> bool {
+    if state.added_cards.lock().unwrap().contains(key) {
+        return true;
+    }
+
+    let field = template.term_field();
+    saya_anki::deck_has_term(client, &template.deck, field.as_deref(), term)
+        .await
+        .inspect_err(|e| tracing::warn!("Failed to check Anki for duplicates: {}", e))
+        .unwrap_or(false)
+}
+
+/// Render the card `result` would create, without adding it
+///
+/// With a client the note type's fields are detected as for
+/// [`handle_card_creation`], so the preview shows the fields the note gets.
+pub async fn handle_card_preview(
+    state: Arc<AppState>,
+    result: DisplayResult,
+    anki_client: Option<&AnkiConnectClient>,
+    app_to_ui_tx: &AsyncSender<AppEvent>,
+) -> anyhow::Result<()> {
+    let template = card_template(&*state.config.read().await, &result);
+    let template = match anki_client {
+        Some(client) => with_detected_fields(client, template).await,
+        None => template,
+    };
+    let (front, back) = template.preview(&card_values(&result));
+
+    app_to_ui_tx.send(AppEvent::CardPreview { front, back }).await?;
+    Ok(())
+}
+
 pub async fn handle_card_creation(
     state: Arc<AppState>,
     result: DisplayResult,
-    anki_client: Option<&saya_anki::AnkiConnectClient>,
+    anki_client: Option<&AnkiConnectClient>,
+    app_to_ui_tx: &AsyncSender<AppEvent>,
 ) -> anyhow::Result<()> {
     if let Some(client) = anki_client {
-        let config = state.config.read().await;
-        let template = saya_anki::CardTemplate::new(
-            config.anki.deck.clone(),
-            config.anki.model.clone(),
-            "{term}\n{reading}".to_string(),
-            "{definition}".to_string(),
-        );
-
-        match saya_anki::add_card(
-            client,
-            &template,
-            &result.term,
-            &result.reading,
-            &result.definition,
-        )
-        .await
+        let (template, policy, by_base_form) = {
+            let config = state.config.read().await;
+            (
+                card_template(&config, &result),
+                config.anki.duplicate_policy,
+                config.anki.dedup_by_base_form,
+            )
+        };
+        let template = with_detected_fields(client, template).await;
+        let key = duplicate_key(&result, by_base_form).to_string();
+        let term = deck_term(&result, &key, by_base_form);
+        if policy == DuplicatePolicy::Skip
+            && is_duplicate(&state, client, &template, &key, term).await
         {
+            tracing::info!("Card for {} already in Anki, skipping", key);
+            let _ = app_to_ui_tx
+                .send(AppEvent::OcrStatusUpdate {
+                    status: format!("{} is already in Anki", key),
+                    capturing: false,
+                })
+                .await;
+            return Ok(());
+        }
+
+        let values = card_values(&result);
+        let tags = saya_anki::TagValues::today(&state.profile, result.jlpt.map(|l| l.as_str()));
+
+        match saya_anki::add_card(client, &template, &values, &tags).await {
             Ok(note_id) => {
                 tracing::info!("Added card to Anki: note_id={}", note_id);
+                state.added_cards.lock().unwrap().insert(key);
             }
             Err(e) => {
                 tracing::error!("Failed to add card to Anki: {}", e);
+                send_error(app_to_ui_tx, ErrorKind::AnkiFailed, e.to_string()).await;
             }
         }
     } else {
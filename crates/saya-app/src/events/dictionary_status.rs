@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use kanal::AsyncSender;
+use saya_core::dictionary::DictionaryStatus;
+use saya_types::AppEvent;
+
+use crate::state::AppState;
+
+/// Tell the user the dictionary didn't load, instead of silently returning no results
+///
+/// Waits for the backend ready signal so the UI's "Ready" status doesn't replace it.
+pub fn report_dictionary_status(
+    state: Arc<AppState>,
+    tx: AsyncSender<AppEvent>,
+    status: DictionaryStatus,
+) {
+    if status.is_loaded() {
+        return;
+    }
+    tracing::error!("{}", status.message());
+
+    tokio::spawn(async move {
+        while !state.status.backend_ready.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        let _ = tx
+            .send(AppEvent::OcrStatusUpdate {
+                status: status.message(),
+                capturing: false,
+            })
+            .await;
+    });
+}
@@ -0,0 +1,33 @@
+use kanal::AsyncSender;
+use saya_core::language::LanguageProcessor;
+use saya_types::AppEvent;
+
+use crate::display::{DisplayOptions, to_display_result};
+
+/// Show the full dictionary entry for `id`
+pub async fn handle_lookup_by_id(
+    id: String,
+    processor: &dyn LanguageProcessor,
+    app_to_ui_tx: &AsyncSender<AppEvent>,
+    options: DisplayOptions,
+) -> anyhow::Result<()> {
+    match processor.lookup_by_id(&id) {
+        Some(result) => {
+            // Full entry view, never collapsed
+            let mut display = to_display_result(&result, &options);
+            display.truncated = false;
+            app_to_ui_tx.send(AppEvent::ShowResults(vec![display])).await?;
+        }
+        None => {
+            tracing::debug!("No dictionary entry with id '{}'", id);
+            let _ = app_to_ui_tx
+                .send(AppEvent::OcrStatusUpdate {
+                    status: "Entry not found".to_string(),
+                    capturing: false,
+                })
+                .await;
+        }
+    }
+
+    Ok(())
+}
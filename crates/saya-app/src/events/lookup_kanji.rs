@@ -0,0 +1,38 @@
+use kanal::AsyncSender;
+use saya_core::language::LanguageProcessor;
+use saya_config::ui::ReadingDisplay;
+use saya_types::AppEvent;
+
+use crate::display::{DisplayOptions, to_display_result};
+
+/// Show the stroke count, radicals and readings of a single kanji
+pub async fn handle_lookup_kanji(
+    ch: char,
+    processor: &dyn LanguageProcessor,
+    app_to_ui_tx: &AsyncSender<AppEvent>,
+    options: DisplayOptions,
+) -> anyhow::Result<()> {
+    match processor.character_info(ch) {
+        Some(result) => {
+            // The kanji itself always leads
+            let options = DisplayOptions {
+                reading_display: ReadingDisplay::KanjiFirst,
+                ..options
+            };
+            let mut display = to_display_result(&result, &options);
+            display.truncated = false;
+            app_to_ui_tx.send(AppEvent::ShowResults(vec![display])).await?;
+        }
+        None => {
+            tracing::debug!("No kanji data for '{}'", ch);
+            let _ = app_to_ui_tx
+                .send(AppEvent::OcrStatusUpdate {
+                    status: "No kanji data".to_string(),
+                    capturing: false,
+                })
+                .await;
+        }
+    }
+
+    Ok(())
+}
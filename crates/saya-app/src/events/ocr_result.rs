@@ -0,0 +1,159 @@
+use saya_core::dictionary::DictionaryStatus;
+use saya_ocr::{OcrError, WindowCaptureError};
+use saya_types::{AppEvent, ErrorKind, TextSource};
+use tokio::task::JoinError;
+
+use crate::display::{DisplayOptions, send_latest, stream_results, token_results};
+use crate::events::send_error;
+use crate::events::text_input::send_unmatched;
+use crate::events::translate_text::translate_and_show;
+use crate::ocr_context::OcrContext;
+use crate::translation::text_to_translate;
+
+/// Whether recognized `text` has at least `min_length` characters once trimmed
+///
+/// Blank text never does, whatever `min_length` is.
+fn is_long_enough(text: &str, min_length: usize) -> bool {
+    text.trim().chars().count() >= min_length.max(1)
+}
+
+/// Shared post-recognition pipeline for region and window captures
+///
+/// `generation` is the OCR generation the capture was started with. If a newer
+/// capture was triggered while this one was in flight, its output is dropped.
+pub async fn handle_ocr_result(
+    ctx: &OcrContext,
+    result: Result<anyhow::Result<String>, JoinError>,
+    generation: u64,
+    auto: bool,
+) -> anyhow::Result<()> {
+    let state = &ctx.state;
+    let app_to_ui_tx = &ctx.event_tx;
+    let processor = &ctx.processor;
+
+    if !state.is_current_ocr(generation) {
+        tracing::debug!(">>> [OCR] Dropping stale result (generation {})", generation);
+        return Ok(());
+    }
+
+    match result {
+        Ok(Ok(text)) => {
+            tracing::debug!(">>> [OCR] Got text: {} chars", text.len());
+            state.status.ocr.write().await.record_capture();
+
+            let min_length = state.config.read().await.ocr.min_text_length;
+            let long_enough = is_long_enough(&text, min_length);
+            let changed = if long_enough {
+                state.ocr_text_changed(&text)
+            } else {
+                state.reset_ocr_text();
+                true
+            };
+
+            // A static scene re-reads the same text, only manual captures repeat it
+            if auto && !changed {
+                tracing::debug!(">>> [OCR] Text unchanged since the last frame, skipping");
+            } else if long_enough {
+                // Show raw text
+                let raw = AppEvent::RawTextInput {
+                    text: text.clone(),
+                    source: TextSource::Ocr,
+                };
+                let sent = match &ctx.ui_queue {
+                    Some(queue) => send_latest(app_to_ui_tx, queue, raw).await,
+                    None => app_to_ui_tx.send(raw).await.map_err(Into::into),
+                };
+                if let Err(e) = sent {
+                    tracing::warn!(">>> [OCR] Failed to send raw text: {}", e);
+                }
+
+                // Dictionary processing, streamed as tokens resolve
+                let options = DisplayOptions::from_config(&*state.config.read().await)
+                    .with_known_words(state.known_words.clone());
+                let batches = token_results(processor.as_ref(), &text, options.clone());
+                let sent = stream_results(batches, app_to_ui_tx, ctx.ui_queue.as_ref(), || {
+                    state.is_current_ocr(generation)
+                })
+                .await;
+                if matches!(sent, Ok(0)) && state.is_current_ocr(generation) {
+                    send_unmatched(processor.as_ref(), &text, &options, app_to_ui_tx).await;
+                }
+
+                // Translation, of as much text as `translator.scope` allows
+                let scope = state.config.read().await.translator.scope;
+                if ctx.translator.is_some()
+                    && let Some(text) = text_to_translate(scope, &text)
+                {
+                    let state = state.clone();
+                    translate_and_show(ctx, &text, move || state.is_current_ocr(generation)).await;
+                }
+
+                let status = match processor.dictionary_status() {
+                    DictionaryStatus::Loading => DictionaryStatus::Loading.message(),
+                    _ => "Ready".to_string(),
+                };
+                let _ = app_to_ui_tx
+                    .send(AppEvent::OcrStatusUpdate {
+                        status,
+                        capturing: auto,
+                    })
+                    .await;
+            } else {
+                let _ = app_to_ui_tx
+                    .send(AppEvent::OcrStatusUpdate {
+                        status: "No text found".to_string(),
+                        capturing: auto,
+                    })
+                    .await;
+            }
+        }
+        Ok(Err(e)) => {
+            tracing::error!(">>> [OCR] Failed: {}", e);
+            state.status.ocr.write().await.record_error(e.to_string());
+            let empty_region = e.downcast_ref::<OcrError>() == Some(&OcrError::EmptyRegion);
+            let status = if empty_region {
+                "Region too small".to_string()
+            } else {
+                format!("Failed: {}", e)
+            };
+            let _ = app_to_ui_tx
+                .send(AppEvent::OcrStatusUpdate {
+                    status,
+                    capturing: false,
+                })
+                .await;
+            let kind = if empty_region || e.downcast_ref::<WindowCaptureError>().is_some() {
+                ErrorKind::CaptureFailed
+            } else {
+                ErrorKind::OcrFailed
+            };
+            send_error(app_to_ui_tx, kind, e.to_string()).await;
+        }
+        Err(e) => {
+            tracing::error!(">>> [OCR] Task error: {}", e);
+            let _ = app_to_ui_tx
+                .send(AppEvent::OcrStatusUpdate {
+                    status: "Error".to_string(),
+                    capturing: false,
+                })
+                .await;
+            send_error(app_to_ui_tx, ErrorKind::OcrFailed, e.to_string()).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Report a capture dropped because every OCR worker slot is taken
+pub async fn report_busy(ctx: &OcrContext, auto: bool) -> anyhow::Result<()> {
+    tracing::debug!(">>> [OCR] All OCR workers busy, dropping trigger");
+    let _ = ctx
+        .event_tx
+        .send(AppEvent::OcrStatusUpdate {
+            status: "Busy".to_string(),
+            capturing: auto,
+        })
+        .await;
+
+    Ok(())
+}
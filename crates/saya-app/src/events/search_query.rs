@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use kanal::AsyncSender;
+use saya_core::language::LanguageProcessor;
+use saya_types::AppEvent;
+
+use crate::display::{DisplayOptions, to_display_result};
+use crate::state::AppState;
+
+/// Prefix matches shown per query
+pub const SEARCH_LIMIT: usize = 20;
+
+/// Show the words starting with `query`, as the user types it
+///
+/// `generation` is the search generation taken when the query arrived. A query
+/// overtaken by a newer one is skipped before searching, and if it is overtaken
+/// while searching its results are dropped instead of replacing the newer ones.
+pub async fn handle_search_query(
+    state: Arc<AppState>,
+    generation: u64,
+    query: String,
+    processor: Arc<dyn LanguageProcessor>,
+    app_to_ui_tx: AsyncSender<AppEvent>,
+    options: DisplayOptions,
+) -> anyhow::Result<()> {
+    if !state.is_current_search(generation) {
+        tracing::debug!("Skipping stale search (generation {})", generation);
+        return Ok(());
+    }
+
+    let results = tokio::task::spawn_blocking(move || {
+        processor
+            .search_prefix(query.trim(), SEARCH_LIMIT)
+            .iter()
+            .filter(|result| !options.is_hidden(result))
+            .map(|result| to_display_result(result, &options))
+            .collect::<Vec<_>>()
+    })
+    .await?;
+
+    if !state.is_current_search(generation) {
+        tracing::debug!("Dropping stale search results (generation {})", generation);
+        return Ok(());
+    }
+
+    app_to_ui_tx.send(AppEvent::ShowResults(results)).await?;
+    Ok(())
+}
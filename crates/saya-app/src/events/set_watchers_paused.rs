@@ -0,0 +1,25 @@
+use std::sync::atomic::Ordering;
+
+use kanal::AsyncSender;
+use saya_types::AppEvent;
+
+use crate::state::AppState;
+
+/// Pause or resume every watcher and tell the UI
+///
+/// Paused watchers keep running so resuming is instant, they just skip
+/// whatever they pick up in the meantime.
+pub async fn handle_set_watchers_paused(
+    state: &AppState,
+    paused: bool,
+    app_to_ui_tx: &AsyncSender<AppEvent>,
+) -> anyhow::Result<()> {
+    state.watchers_paused.store(paused, Ordering::SeqCst);
+    tracing::info!("Watchers {}", if paused { "paused" } else { "resumed" });
+
+    app_to_ui_tx
+        .send(AppEvent::WatchersPausedChanged { paused })
+        .await?;
+
+    Ok(())
+}
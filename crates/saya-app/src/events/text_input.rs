@@ -1,51 +1,37 @@
 use kanal::AsyncSender;
+use saya_core::dictionary::DictionaryStatus;
 use saya_core::language::LanguageProcessor;
-use saya_types::{AppEvent, DisplayResult};
-use saya_lang_japanese::JapaneseProcessor;
+use saya_types::{AppEvent, is_japanese_char};
+
+use crate::display::{DisplayOptions, stream_results, token_results, unmatched_results};
+
+/// Status when nothing matched and `text` has no Japanese in it at all
+pub const NO_JAPANESE_STATUS: &str = "No Japanese text detected";
+/// Status when `text` has Japanese but none of it is in the dictionary
+pub const NO_MATCHES_STATUS: &str = "No dictionary matches";
 
 pub async fn handle_text_input(
     text: String,
-    processor: &JapaneseProcessor,
+    processor: &dyn LanguageProcessor,
     app_to_ui_tx: &AsyncSender<AppEvent>,
+    options: DisplayOptions,
 ) -> anyhow::Result<()> {
-    let normalized = processor.normalize(&text);
-    tracing::debug!("Normalized: '{}'", normalized);
-
-    let tokens = processor.tokenize(&normalized);
-    tracing::debug!("Tokenized into {} tokens", tokens.len());
-
-    let mut display_results = Vec::new();
-
-    for token in tokens.iter().take(10) {
-        let results = processor.lookup(token);
-        tracing::debug!("Token '{:?}': {} results", token, results.len());
-        if !results.is_empty() {
-            for result in results.iter().take(5) {
-                display_results.push(DisplayResult {
-                    term: result.term.clone(),
-                    reading: result.readings.join(", "),
-                    definition: result.definitions.join("; "),
-                    frequency: result.metadata.get("frequency_stars").cloned(),
-                    pitch_accent: result.metadata.get("pitch_accent").cloned(),
-                    jlpt_level: result.metadata.get("jlpt_level").cloned(),
-                    conjugation: result.metadata.get("conjugation").cloned(),
-                });
-            }
-        }
-    }
+    let batches = token_results(processor, &text, options.clone());
+    let sent = stream_results(batches, app_to_ui_tx, None, || true).await?;
 
-    tracing::debug!("Total display results: {}", display_results.len());
+    tracing::debug!("Total display results: {}", sent);
 
-    if !display_results.is_empty() {
-        tracing::debug!("Sending ShowResults event");
-        app_to_ui_tx
-            .send(AppEvent::ShowResults(display_results))
-            .await?;
-    } else {
+    if sent == 0 {
         tracing::debug!("No results found for input text");
+        send_unmatched(processor, &text, &options, app_to_ui_tx).await;
+        let status = match processor.dictionary_status() {
+            DictionaryStatus::Loading => DictionaryStatus::Loading.message(),
+            _ if !text.chars().any(is_japanese_char) => NO_JAPANESE_STATUS.to_string(),
+            _ => NO_MATCHES_STATUS.to_string(),
+        };
         let _ = app_to_ui_tx
             .send(AppEvent::OcrStatusUpdate {
-                status: "Japanese text only".to_string(),
+                status,
                 capturing: false,
             })
             .await;
@@ -53,3 +39,20 @@ pub async fn handle_text_input(
 
     Ok(())
 }
+
+/// With `show_unmatched`, show the segmented text when the dictionary found nothing
+pub async fn send_unmatched(
+    processor: &dyn LanguageProcessor,
+    text: &str,
+    options: &DisplayOptions,
+    app_to_ui_tx: &AsyncSender<AppEvent>,
+) {
+    if !options.show_unmatched || processor.dictionary_status() == DictionaryStatus::Loading {
+        return;
+    }
+
+    let results = unmatched_results(processor, text, options);
+    if !results.is_empty() {
+        let _ = app_to_ui_tx.send(AppEvent::ShowResults(results)).await;
+    }
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use kanal::AsyncSender;
+use saya_types::AppEvent;
+
+use crate::state::AppState;
+
+/// Flip `ui.click_through`, persist it and tell the overlay
+///
+/// Returns the new setting.
+pub async fn handle_toggle_click_through(
+    state: &Arc<AppState>,
+    app_to_ui_tx: &AsyncSender<AppEvent>,
+) -> anyhow::Result<bool> {
+    let enabled = {
+        let mut config = state.config.write().await;
+        config.ui.click_through = !config.ui.click_through;
+        config.ui.click_through
+    };
+    tracing::info!("Overlay click-through {}", if enabled { "on" } else { "off" });
+
+    state.config_saver.schedule(state.config.clone(), &state.profile);
+    app_to_ui_tx
+        .send(AppEvent::ClickThroughChanged { enabled })
+        .await?;
+
+    Ok(enabled)
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use saya_types::{AppEvent, ErrorKind};
+
+use crate::events::send_error;
+use crate::ocr_context::OcrContext;
+
+/// Translate `text` on request, whatever `translator.scope` is
+pub async fn handle_translate_text(ctx: &OcrContext, text: String) -> anyhow::Result<()> {
+    if ctx.translator.is_none() {
+        send_error(
+            &ctx.event_tx,
+            ErrorKind::TranslationFailed,
+            "Translation is not configured",
+        )
+        .await;
+        return Ok(());
+    }
+
+    translate_and_show(ctx, &text, || true).await;
+    Ok(())
+}
+
+/// Translate `text` and show it, unless `is_current` says it's stale by then
+///
+/// A request already stale by its turn in the dispatcher is never sent.
+pub async fn translate_and_show(
+    ctx: &OcrContext,
+    text: &str,
+    is_current: impl Fn() -> bool + Send + Sync + 'static,
+) {
+    let Some(translator) = &ctx.translator else {
+        return;
+    };
+
+    let config = ctx.state.config.read().await;
+    let from = config.translator.from_lang.clone();
+    let to = config.translator.to_lang.clone();
+    drop(config);
+
+    let is_current = Arc::new(is_current);
+    let still_current = is_current.clone();
+    let result = translator
+        .translate_if_current(text, &from, &to, move || still_current())
+        .await;
+
+    match result {
+        Ok(Some(translation)) if is_current() => {
+            let _ = ctx
+                .event_tx
+                .send(AppEvent::ShowTranslation {
+                    text: translation.text,
+                    from_lang: translation.from,
+                    to_lang: to,
+                    alternatives: translation.alternatives,
+                })
+                .await;
+        }
+        Ok(None) => {
+            tracing::debug!("Text already in {}, no translation", to);
+        }
+        Ok(Some(_)) => {
+            tracing::debug!("Dropping stale translation");
+        }
+        Err(_) if !is_current() => {
+            tracing::debug!("Skipped translating stale text");
+        }
+        Err(e) => {
+            tracing::warn!("Translation failed: {}", e);
+            send_error(&ctx.event_tx, ErrorKind::TranslationFailed, e.to_string()).await;
+        }
+    }
+}
@@ -3,15 +3,18 @@ use std::sync::atomic::Ordering;
 use saya_types::CaptureRegion;
 
 use crate::ocr_context::OcrContext;
+use crate::state::AppState;
 
 use super::trigger_ocr::handle_ocr_trigger;
 
-pub fn start_auto_ocr_loop(ctx: &OcrContext, region: CaptureRegion) {
+/// Start the auto OCR loop, returning `false` if it was already running
+pub fn start_auto_ocr_loop(ctx: &OcrContext, region: CaptureRegion) -> bool {
     let state = &ctx.state;
 
     // Don't start again if already running
     if state.auto_ocr_running.swap(true, Ordering::SeqCst) {
-        return;
+        tracing::debug!(">>> [OCR] Auto OCR already running");
+        return false;
     }
 
     let ctx_clone = ctx.clone();
@@ -28,20 +31,27 @@ pub fn start_auto_ocr_loop(ctx: &OcrContext, region: CaptureRegion) {
                 break;
             }
 
-            // Read current region from state (updated when UI window moves)
-            let current_region = {
-                let region_lock = ctx_clone.state.current_capture_region.read().await;
-                region_lock.clone()
-            };
-
-            // Use current region if available, otherwise fallback to initial
-            let active_region = current_region.unwrap_or(region);
+            if ctx_clone.state.watchers_paused() {
+                tracing::trace!(">>> [OCR] Watchers paused, skipping auto OCR cycle");
+            } else {
+                let active_region = active_region(&ctx_clone.state, region).await;
 
-            // Run one OCR cycle with fresh region
-            let _ = handle_ocr_trigger(&ctx_clone, active_region, true).await;
+                // Run one OCR cycle with fresh region
+                let _ = handle_ocr_trigger(&ctx_clone, active_region, true).await;
+            }
 
             tokio::task::yield_now().await;
             tokio::time::sleep(std::time::Duration::from_millis(interval_ms)).await;
         }
     });
+
+    true
+}
+
+/// Region for the next auto OCR cycle
+///
+/// Reads the current region from state (updated when the UI window moves),
+/// falling back to the region auto OCR was started with.
+pub async fn active_region(state: &AppState, initial: CaptureRegion) -> CaptureRegion {
+    state.current_capture_region.read().await.unwrap_or(initial)
 }
@@ -1,9 +1,9 @@
-use saya_core::language::LanguageProcessor;
-use saya_translator::Translator;
-use saya_types::{AppEvent, CaptureRegion, DisplayResult, TextSource};
+use saya_types::CaptureRegion;
 
 use crate::ocr_context::OcrContext;
 
+use super::ocr_result::{handle_ocr_result, report_busy};
+
 /// handles ocr and loops it if ocr.auto is enabled
 pub async fn handle_ocr_trigger(
     ctx: &OcrContext,
@@ -11,12 +11,17 @@ pub async fn handle_ocr_trigger(
     auto: bool,
 ) -> anyhow::Result<()> {
     let state = &ctx.state;
-    let app_to_ui_tx = &ctx.event_tx;
-    let processor = &ctx.processor;
-    let translator = &ctx.translator;
-    let ocr_language = {
+    let (ocr_language, monitor, monitor_index, frame_threshold) = {
         let config = state.config.read().await;
-        config.ocr.language.clone()
+        // Only auto OCR sees the same scene over and over
+        let frame_threshold =
+            (auto && config.ocr.skip_unchanged_frames).then_some(config.ocr.frame_change_threshold);
+        (
+            config.ocr.language.clone(),
+            config.ocr.capture_monitor.clone(),
+            config.ocr.monitor_index,
+            frame_threshold,
+        )
     };
 
     let region = CaptureRegion {
@@ -26,114 +31,54 @@ pub async fn handle_ocr_trigger(
         height: region.height,
     };
 
-    let state_clone = state.clone();
-    let result = tokio::task::spawn_blocking(move || {
-        let _com = saya_ocr::ComGuard::initialize()?;
-
-        let image_data = saya_ocr::capture_screen_region(region)?;
-        let text = saya_ocr::recognize_sync(&state_clone.ocr_engine, &image_data, &ocr_language)?;
-        Ok::<_, anyhow::Error>(text)
-    })
-    .await;
-
-    match result {
-        Ok(Ok(text)) => {
-            tracing::debug!(">>> [OCR] Got text: {} chars", text.len());
-
-            if !text.trim().is_empty() {
-                // Show raw text
-                let _ = app_to_ui_tx
-                    .send(AppEvent::RawTextInput {
-                        text: text.clone(),
-                        source: TextSource::Ocr,
-                    })
-                    .await;
-
-                // Dictionary processing
-                let normalized = processor.normalize(&text);
-                let tokens = processor.tokenize(&normalized);
-                let mut display_results = Vec::new();
-
-                for token in tokens.iter().take(10) {
-                    let results = processor.lookup(token);
-                    if !results.is_empty() {
-                        for result in results.iter().take(5) {
-                            display_results.push(DisplayResult {
-                                term: result.term.clone(),
-                                reading: result.readings.join(", "),
-                                definition: result.definitions.join("; "),
-                                frequency: result.metadata.get("frequency_stars").cloned(),
-                                pitch_accent: result.metadata.get("pitch_accent").cloned(),
-                                jlpt_level: result.metadata.get("jlpt_level").cloned(),
-                                conjugation: result.metadata.get("conjugation").cloned(),
-                            });
-                        }
-                    }
-                }
-
-                if !display_results.is_empty() {
-                    let _ = app_to_ui_tx
-                        .send(AppEvent::ShowResults(display_results))
-                        .await;
-                }
+    // Bound concurrent captures; triggers beyond the limit are dropped
+    let Some(_permit) = state.try_acquire_ocr() else {
+        return report_busy(ctx, auto).await;
+    };
 
-                // Translation
-                if let Some(t) = (**translator).as_ref() {
-                    let config = state.config.read().await;
-                    let from = config.translator.from_lang.clone();
-                    let to = config.translator.to_lang.clone();
-                    drop(config);
+    // Invalidate any capture still in flight
+    let generation = state.next_ocr_generation();
 
-                    match t.translate(&text, from.clone(), to.clone()).await {
-                        Ok(translation) => {
-                            let _ = app_to_ui_tx
-                                .send(AppEvent::ShowTranslation {
-                                    text: translation.text,
-                                    from_lang: from,
-                                    to_lang: to,
-                                })
-                                .await;
-                        }
-                        Err(e) => {
-                            tracing::warn!("Translation failed: {}", e);
-                        }
-                    }
+    let capturer = state.capturer.clone();
+    let frame_state = state.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let (image_data, hash) = match frame_threshold {
+            Some(threshold) => {
+                let (image_data, hash) =
+                    capturer.capture_hashed(region, &monitor, monitor_index)?;
+                if !frame_state.frame_changed(hash, threshold) {
+                    return Ok(None);
                 }
-
-                let _ = app_to_ui_tx
-                    .send(AppEvent::OcrStatusUpdate {
-                        status: "Ready".to_string(),
-                        capturing: auto,
-                    })
-                    .await;
-            } else {
-                let _ = app_to_ui_tx
-                    .send(AppEvent::OcrStatusUpdate {
-                        status: "No text found".to_string(),
-                        capturing: auto,
-                    })
-                    .await;
+                (image_data, Some(hash))
             }
+            None => (
+                capturer.capture_region(region, &monitor, monitor_index)?,
+                None,
+            ),
+        };
+        capturer
+            .recognize(&image_data, &ocr_language)
+            .map(|text| Some((text, hash)))
+    })
+    .await;
+
+    let result = match result {
+        Ok(Ok(None)) => {
+            tracing::debug!(">>> [OCR] Frame unchanged, skipping recognition");
+            return Ok(());
         }
-        Ok(Err(e)) => {
-            tracing::error!(">>> [OCR] Failed: {}", e);
-            let _ = app_to_ui_tx
-                .send(AppEvent::OcrStatusUpdate {
-                    status: format!("Failed: {}", e),
-                    capturing: false,
-                })
-                .await;
-        }
-        Err(e) => {
-            tracing::error!(">>> [OCR] Task error: {}", e);
-            let _ = app_to_ui_tx
-                .send(AppEvent::OcrStatusUpdate {
-                    status: "Error".to_string(),
-                    capturing: false,
-                })
-                .await;
+        Ok(Ok(Some((text, hash)))) => {
+            // A stale capture's frame may not be what's on screen any more
+            if let Some(hash) = hash
+                && state.is_current_ocr(generation)
+            {
+                state.record_frame(hash);
+            }
+            Ok(Ok(text))
         }
-    }
+        Ok(Err(e)) => Ok(Err(e)),
+        Err(e) => Err(e),
+    };
 
-    Ok(())
+    handle_ocr_result(ctx, result, generation, auto).await
 }
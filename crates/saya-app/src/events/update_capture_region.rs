@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use saya_config::Config;
+use saya_types::CaptureRegion;
+
+use crate::state::AppState;
+
+/// Clamp `region` to the screen and store it as the configured capture region
+///
+/// Returns the clamped region when it differs from the stored one and needs persisting.
+/// Empty regions aren't clamped, so capturing them reports the region as too small.
+pub fn apply_capture_region(
+    config: &mut Config,
+    region: CaptureRegion,
+    screen: Option<CaptureRegion>,
+) -> Option<CaptureRegion> {
+    let empty = region.width == 0 || region.height == 0;
+    let region = match screen {
+        Some(bounds) if !empty => region.clamp_to(bounds),
+        _ => region,
+    };
+
+    if config.ocr.capture_region == Some(region) {
+        return None;
+    }
+    config.ocr.capture_region = Some(region);
+    Some(region)
+}
+
+pub async fn handle_capture_region_update(
+    state: &Arc<AppState>,
+    region: CaptureRegion,
+) -> anyhow::Result<()> {
+    let screen = match state.monitors().await {
+        Ok(monitors) => saya_ocr::monitors_bounds(&monitors),
+        Err(e) => {
+            tracing::warn!("Failed to read screen bounds, region left unclamped: {}", e);
+            None
+        }
+    };
+
+    let mut config = state.config.write().await;
+    let changed = apply_capture_region(&mut config, region, screen);
+    let region = config.ocr.capture_region.unwrap_or(region);
+
+    // Update region while auto OCR is running (window moved/resized)
+    *state.current_capture_region.write().await = Some(region);
+    tracing::debug!(">>> [OCR] Region updated: {}x{} at ({}, {})",
+        region.width, region.height, region.x, region.y);
+
+    // Window moves arrive every 500ms during auto OCR, so saves are debounced
+    if changed.is_some() {
+        state.config_saver.schedule(state.config.clone(), &state.profile);
+    }
+
+    Ok(())
+}
@@ -1,12 +1,86 @@
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use kanal::AsyncSender;
-use saya_types::{AppEvent, TextSource};
+use saya_config::clipboard::{ClipboardConfig, ClipboardMode};
+use saya_io::clipboard::WatchMode;
+use saya_types::{AppEvent, TextSource, contains_target_script};
 use tokio_util::sync::CancellationToken;
 
 use crate::state::AppState;
 
+/// Send `BackendReady` once the dictionary has loaded
+///
+/// The OCR engine is created with [`AppState`], so the dictionary is the
+/// last piece to finish initializing.
+pub async fn signal_backend_ready(state: Arc<AppState>, tx: AsyncSender<AppEvent>) {
+    state.status.dictionary_loaded().await;
+    state.status.backend_ready.store(true, Ordering::SeqCst);
+    let _ = tx.send(AppEvent::BackendReady).await;
+    tracing::info!("Backend ready signal sent");
+}
+
+/// Whether clipboard `text` is worth a lookup in `language`
+///
+/// With `require_target_script`, text like URLs or code that has no character
+/// of the language's script is skipped.
+pub fn accepts_clipboard_text(clipboard: &ClipboardConfig, language: &str, text: &str) -> bool {
+    !clipboard.require_target_script || contains_target_script(text, language)
+}
+
+/// IDs of the global hotkeys, to tell presses apart
+pub struct HotkeyIds {
+    /// `None` with OCR disabled
+    pub ocr: Option<u32>,
+    pub click_through: Option<u32>,
+    pub pause: Option<u32>,
+}
+
+impl HotkeyIds {
+    /// Event for a press of hotkey `id`
+    ///
+    /// While `paused` only the pause toggle and click-through still act.
+    pub fn event_for(&self, id: u32, paused: bool) -> Option<AppEvent> {
+        if Some(id) == self.pause {
+            Some(AppEvent::SetWatchersPaused(!paused))
+        } else if Some(id) == self.click_through {
+            Some(AppEvent::ToggleClickThrough)
+        } else if Some(id) == self.ocr && !paused {
+            Some(AppEvent::HotkeyOcrTriggered)
+        } else {
+            None
+        }
+    }
+}
+
+/// Send watched `text` to the backend, unless watchers are paused
+///
+/// Returns whether the text was forwarded.
+pub fn forward_text(
+    state: &AppState,
+    tx: &AsyncSender<AppEvent>,
+    text: String,
+    source: TextSource,
+) -> bool {
+    if state.watchers_paused() {
+        tracing::debug!("Watchers paused, dropping {:?} text", source);
+        return false;
+    }
+
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let _ = tx
+            .send(AppEvent::RawTextInput {
+                text: text.clone(),
+                source,
+            })
+            .await;
+        let _ = tx.send(AppEvent::TextInput(text)).await;
+    });
+    true
+}
+
 pub async fn watcher_io(
     state: Arc<AppState>,
     _delta_time: Duration,
@@ -15,13 +89,7 @@ pub async fn watcher_io(
 ) -> anyhow::Result<()> {
     tracing::info!("watcher_io started");
 
-    // Signal backend ready after brief initialization delay
-    let ready_tx = event_tx.clone();
-    tokio::spawn(async move {
-        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-        let _ = ready_tx.send(AppEvent::BackendReady).await;
-        tracing::info!("Backend ready signal sent");
-    });
+    tokio::spawn(signal_backend_ready(state.clone(), event_tx.clone()));
 
     let (listen_to_ws, ocr_enabled, hotkey_poll_interval_ms) = {
         let config = state.config.read().await;
@@ -32,36 +100,76 @@ pub async fn watcher_io(
         )
     };
 
-    // Spawn OCR hotkey listener if enabled
-    if ocr_enabled {
+    // Spawn the hotkey listener; Ctrl+Shift+J is only registered with OCR enabled
+    {
         let tx = event_tx.clone();
         let cancel_clone = cancel.clone();
+        let state = state.clone();
 
         tokio::task::spawn_blocking(move || {
             tracing::info!(">>> [HOTKEY] Starting hotkey listener...");
 
-            let hotkey_manager = match saya_ocr::HotkeyManager::new() {
+            // Ctrl+Shift+P pauses and resumes every watcher, OCR or not
+            let mut hotkey_manager = match saya_ocr::HotkeyManager::with_hotkey(
+                saya_ocr::Modifiers::CONTROL | saya_ocr::Modifiers::SHIFT,
+                saya_ocr::Code::KeyP,
+            ) {
                 Ok(m) => m,
                 Err(e) => {
                     tracing::error!(">>> [HOTKEY] Failed to create hotkey: {}", e);
                     return;
                 }
             };
+            let pause_id = hotkey_manager.id();
+
+            let ocr_id = if ocr_enabled {
+                hotkey_manager
+                    .add_hotkey(
+                        saya_ocr::Modifiers::CONTROL | saya_ocr::Modifiers::SHIFT,
+                        saya_ocr::Code::KeyJ,
+                    )
+                    .inspect_err(|e| {
+                        tracing::warn!(">>> [HOTKEY] Failed to register Ctrl+Shift+J: {}", e)
+                    })
+                    .ok()
+            } else {
+                None
+            };
+
+            // Ctrl+Shift+T toggles click-through, so results stay clickable on demand
+            let click_through_id = hotkey_manager
+                .add_hotkey(
+                    saya_ocr::Modifiers::CONTROL | saya_ocr::Modifiers::SHIFT,
+                    saya_ocr::Code::KeyT,
+                )
+                .inspect_err(|e| {
+                    tracing::warn!(">>> [HOTKEY] Failed to register Ctrl+Shift+T: {}", e)
+                })
+                .ok();
+
+            let ids = HotkeyIds {
+                ocr: ocr_id,
+                click_through: click_through_id,
+                pause: Some(pause_id),
+            };
 
-            tracing::info!(">>> [HOTKEY] Ctrl+Shift+J registered, polling...");
+            tracing::info!(">>> [HOTKEY] Hotkeys registered, polling...");
 
             loop {
                 if cancel_clone.is_cancelled() {
                     break;
                 }
 
-                if hotkey_manager.poll() {
-                    tracing::debug!(">>> [HOTKEY] Hotkey pressed!");
+                let event = hotkey_manager
+                    .poll_id()
+                    .and_then(|id| ids.event_for(id, state.watchers_paused()));
+                if let Some(event) = event {
+                    tracing::debug!(">>> [HOTKEY] Hotkey pressed: {}", event.name());
 
                     // Send simple event - let event loop handle it
                     let tx_clone = tx.clone();
                     tokio::spawn(async move {
-                        let _ = tx_clone.send(AppEvent::HotkeyOcrTriggered).await;
+                        let _ = tx_clone.send(event).await;
                     });
                 }
 
@@ -78,33 +186,36 @@ pub async fn watcher_io(
             config.ws_url.clone()
         };
 
+        let ws_state = state.clone();
         saya_io::ws::start_ws_listener(&ws_url, move |text| {
-            let tx = event_tx.clone();
-            tokio::spawn(async move {
-                let _ = tx
-                    .send(AppEvent::RawTextInput {
-                        text: text.clone(),
-                        source: TextSource::Websocket,
-                    })
-                    .await;
-                let _ = tx.send(AppEvent::TextInput(text)).await;
-            });
+            forward_text(&ws_state, &event_tx, text, TextSource::Websocket);
         })
         .await?;
 
         cancel.cancelled().await;
     } else {
         let tx = event_tx.clone();
+        let self_writes = state.clipboard_writes.clone();
+        let (clipboard, language, poll_interval) = {
+            let config = state.config.read().await;
+            (
+                config.clipboard.clone(),
+                config.dictionary_language().to_string(),
+                Duration::from_millis(config.clipboard.poll_interval_ms),
+            )
+        };
+        let clipboard_state = state.clone();
+        let mode = match clipboard.mode {
+            ClipboardMode::Events => WatchMode::Events,
+            ClipboardMode::Poll => WatchMode::Poll,
+        };
         tokio::select! {
-            result = saya_io::clipboard::watch_clipboard(move |text| {
-                let tx = tx.clone();
-                tokio::spawn(async move {
-                    let _ = tx.send(AppEvent::RawTextInput {
-                        text: text.clone(),
-                        source: TextSource::Clipboard,
-                    }).await;
-                    let _ = tx.send(AppEvent::TextInput(text)).await;
-                });
+            result = saya_io::clipboard::watch_clipboard(self_writes, mode, poll_interval, move |text| {
+                if !accepts_clipboard_text(&clipboard, &language, &text) {
+                    tracing::debug!("Ignoring clipboard text without {} script", language);
+                    return;
+                }
+                forward_text(&clipboard_state, &tx, text, TextSource::Clipboard);
             }) => {
                 if let Err(e) = result {
                     tracing::error!("Clipboard watcher error: {}", e);
@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+use std::sync::RwLock;
+
+use saya_anki::AnkiConnectClient;
+
+/// Words the user already knows, hidden from results
+#[derive(Debug, Default)]
+pub struct KnownWords {
+    words: RwLock<HashSet<String>>,
+}
+
+impl KnownWords {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a newline-delimited word list, skipping blank lines
+    pub fn load_from_file(path: &str) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let known = Self::new();
+        known.extend(content.lines());
+        Ok(known)
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.read().unwrap().contains(word)
+    }
+
+    /// Add a word, returning `false` if it was already known
+    pub fn insert(&self, word: &str) -> bool {
+        self.words.write().unwrap().insert(word.to_string())
+    }
+
+    pub fn extend<'a>(&self, words: impl IntoIterator<Item = &'a str>) {
+        let mut set = self.words.write().unwrap();
+        set.extend(
+            words
+                .into_iter()
+                .map(str::trim)
+                .filter(|w| !w.is_empty())
+                .map(str::to_string),
+        );
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Add the terms of every note in `deck` to `known`, returning how many were read
+///
+/// Anki not running is expected, so failures are only logged at debug level.
+pub async fn load_from_anki(known: &KnownWords, client: &AnkiConnectClient, deck: &str) -> usize {
+    match saya_anki::deck_terms(client, deck).await {
+        Ok(terms) => {
+            known.extend(terms.iter().map(String::as_str));
+            tracing::info!("Loaded {} known words from Anki deck {}", terms.len(), deck);
+            terms.len()
+        }
+        Err(e) => {
+            tracing::debug!("Skipping known words from Anki: {}", e);
+            0
+        }
+    }
+}
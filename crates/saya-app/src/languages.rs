@@ -0,0 +1,194 @@
+use std::sync::{Arc, OnceLock};
+
+use kanal::AsyncSender;
+use saya_config::Config;
+use saya_config::dictionary::DictionaryConfig;
+use saya_core::dictionary::DictionaryStatus;
+use saya_core::language::{LanguageProcessor, LookupResult, Token};
+use saya_lang_chinese::ChineseProcessor;
+use saya_lang_japanese::JapaneseProcessor;
+use saya_types::{AppEvent, ErrorKind};
+
+use crate::events::send_error;
+use crate::profile::saya_root;
+
+/// Language codes with a registered processor
+pub const SUPPORTED_LANGUAGES: &[&str] = &["ja", "zh"];
+
+/// Build the processor for `code` from the dictionary settings
+pub fn create_processor(
+    code: &str,
+    dictionary: &DictionaryConfig,
+) -> anyhow::Result<Arc<dyn LanguageProcessor>> {
+    create_processor_with_progress(code, dictionary, &mut |_, _| {})
+}
+
+/// [`create_processor`], calling `progress` with `(done, total)` while
+/// dictionaries are parsed
+pub fn create_processor_with_progress(
+    code: &str,
+    dictionary: &DictionaryConfig,
+    progress: &mut dyn FnMut(usize, usize),
+) -> anyhow::Result<Arc<dyn LanguageProcessor>> {
+    match code {
+        "ja" => {
+            let gloss_langs = dictionary.gloss_chain();
+            let cache_dir = dictionary.cache.then(|| saya_root().join("cache"));
+            let processor = JapaneseProcessor::with_progress(
+                &dictionary.additional_paths,
+                &gloss_langs,
+                cache_dir.as_deref(),
+                progress,
+            );
+            let mut processor = processor
+                .with_min_deconj_confidence(dictionary.min_deconj_confidence)
+                .with_hide_rare_senses(dictionary.hide_rare_senses);
+            if let Some(path) = &dictionary.examples_path {
+                processor = processor.with_examples(path);
+            }
+            if let Some(path) = &dictionary.kanji_path {
+                processor = processor.with_kanji_file(path);
+            }
+            Ok(Arc::new(processor))
+        }
+        "zh" => {
+            if !dictionary.additional_paths.is_empty() {
+                tracing::warn!("Additional dictionaries are not supported for Chinese yet");
+            }
+            Ok(Arc::new(ChineseProcessor::new()))
+        }
+        other => anyhow::bail!(
+            "unsupported language '{}' (supported: {})",
+            other,
+            SUPPORTED_LANGUAGES.join(", ")
+        ),
+    }
+}
+
+/// Build the processor for [`Config::dictionary_language`]
+pub fn processor_from_config(config: &Config) -> anyhow::Result<Arc<dyn LanguageProcessor>> {
+    processor_from_config_with_progress(config, &mut |_, _| {})
+}
+
+/// [`processor_from_config`] with load progress, see [`create_processor_with_progress`]
+pub fn processor_from_config_with_progress(
+    config: &Config,
+    progress: &mut dyn FnMut(usize, usize),
+) -> anyhow::Result<Arc<dyn LanguageProcessor>> {
+    let mut dictionary = config.dictionary.clone();
+    if !dictionary.enabled {
+        tracing::warn!("Dictionary disabled, using empty processor");
+        dictionary.additional_paths.clear();
+    }
+
+    create_processor_with_progress(config.dictionary_language(), &dictionary, progress)
+}
+
+/// Processor whose dictionary is built in the background
+///
+/// Until the real processor is installed lookups find nothing and
+/// `dictionary_status` reports [`DictionaryStatus::Loading`]. It is installed
+/// once, so changing the dictionary language takes a restart.
+pub struct DeferredProcessor {
+    language_code: String,
+    inner: OnceLock<Result<Arc<dyn LanguageProcessor>, String>>,
+}
+
+impl DeferredProcessor {
+    pub fn new(language_code: &str) -> Self {
+        Self {
+            language_code: language_code.to_string(),
+            inner: OnceLock::new(),
+        }
+    }
+
+    /// Install the loaded processor, or the reason it couldn't be built
+    pub fn set(&self, loaded: anyhow::Result<Arc<dyn LanguageProcessor>>) {
+        if self.inner.set(loaded.map_err(|e| e.to_string())).is_err() {
+            tracing::warn!("Language processor already loaded, restart to reload it");
+        }
+    }
+
+    fn ready(&self) -> Option<&Arc<dyn LanguageProcessor>> {
+        self.inner.get()?.as_ref().ok()
+    }
+}
+
+impl LanguageProcessor for DeferredProcessor {
+    fn language_code(&self) -> &str {
+        match self.ready() {
+            Some(processor) => processor.language_code(),
+            None => &self.language_code,
+        }
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        match self.ready() {
+            Some(processor) => processor.normalize(text),
+            None => text.to_string(),
+        }
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        self.ready().map(|p| p.tokenize(text)).unwrap_or_default()
+    }
+
+    fn lookup(&self, token: &Token) -> Vec<LookupResult> {
+        self.ready().map(|p| p.lookup(token)).unwrap_or_default()
+    }
+
+    fn lookup_by_id(&self, id: &str) -> Option<LookupResult> {
+        self.ready()?.lookup_by_id(id)
+    }
+
+    fn search_prefix(&self, prefix: &str, limit: usize) -> Vec<LookupResult> {
+        self.ready()
+            .map(|p| p.search_prefix(prefix, limit))
+            .unwrap_or_default()
+    }
+
+    fn character_info(&self, ch: char) -> Option<LookupResult> {
+        self.ready()?.character_info(ch)
+    }
+
+    fn dictionary_status(&self) -> DictionaryStatus {
+        match self.inner.get() {
+            None => DictionaryStatus::Loading,
+            Some(Err(reason)) => DictionaryStatus::Failed {
+                reason: reason.clone(),
+            },
+            Some(Ok(processor)) => processor.dictionary_status(),
+        }
+    }
+}
+
+/// Build the configured processor on a blocking thread, announcing progress on `tx`
+pub async fn load_processor(
+    deferred: Arc<DeferredProcessor>,
+    config: Config,
+    tx: &AsyncSender<AppEvent>,
+) -> DictionaryStatus {
+    let _ = tx.send(AppEvent::DictionaryLoading).await;
+
+    let progress_tx = tx.clone_sync();
+    let loaded = tokio::task::spawn_blocking(move || {
+        processor_from_config_with_progress(&config, &mut |done, total| {
+            let _ = progress_tx.try_send(AppEvent::DictionaryProgress { done, total });
+        })
+    })
+    .await
+    .unwrap_or_else(|e| Err(anyhow::anyhow!("dictionary loader panicked: {}", e)));
+    deferred.set(loaded);
+
+    let status = deferred.dictionary_status();
+    if let DictionaryStatus::Failed { reason } = &status {
+        send_error(tx, ErrorKind::DictionaryFailed, reason.clone()).await;
+    }
+    let count = match status {
+        DictionaryStatus::Loaded { count } => count,
+        _ => 0,
+    };
+    let _ = tx.send(AppEvent::DictionaryReady { count }).await;
+
+    status
+}
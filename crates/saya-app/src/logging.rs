@@ -0,0 +1,176 @@
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use saya_config::logging::LoggingConfig;
+use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::writer::{MakeWriter, OptionalWriter};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{Layer, Registry, reload};
+
+/// Daily log files kept before the oldest is deleted
+const MAX_LOG_FILES: usize = 7;
+
+/// Directory the log files go to
+pub fn log_dir(config: &LoggingConfig) -> PathBuf {
+    match &config.dir {
+        Some(dir) => PathBuf::from(dir),
+        None => crate::profile::saya_root().join("logs"),
+    }
+}
+
+/// Shared slot for the file writer, swapped when the settings change
+#[derive(Clone, Default)]
+struct FileWriter(Arc<RwLock<Option<NonBlocking>>>);
+
+impl<'a> MakeWriter<'a> for FileWriter {
+    type Writer = OptionalWriter<NonBlocking>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.0.read().unwrap().clone().into()
+    }
+}
+
+/// Where the file layer writes, applied after the layer is installed
+pub struct FileLog {
+    writer: FileWriter,
+    level: reload::Handle<LevelFilter, Registry>,
+    dir: Option<PathBuf>,
+    /// Flushes the current file when replaced or dropped
+    guard: Option<WorkerGuard>,
+}
+
+impl FileLog {
+    /// Point the file layer at the directory and level in `config`
+    ///
+    /// Lines already buffered for a previous directory are flushed there.
+    pub fn apply(&mut self, config: &LoggingConfig) {
+        let level = config
+            .level
+            .parse::<LevelFilter>()
+            .unwrap_or(LevelFilter::INFO);
+        if let Err(e) = self.level.reload(level) {
+            tracing::warn!("Can't change the log file level: {}", e);
+        }
+
+        let dir = config.file_enabled.then(|| log_dir(config));
+        if dir == self.dir {
+            return;
+        }
+        let Some(dir) = dir else {
+            self.set_writer(None, None);
+            return;
+        };
+
+        match open_dir(&dir) {
+            Ok((writer, guard)) => {
+                self.set_writer(Some(writer), Some(guard));
+                self.dir = Some(dir.clone());
+                tracing::info!("Logging to {}", dir.display());
+            }
+            Err(e) => {
+                self.set_writer(None, None);
+                tracing::warn!("File logging disabled, {} unusable: {}", dir.display(), e);
+            }
+        }
+    }
+
+    fn set_writer(&mut self, writer: Option<NonBlocking>, guard: Option<WorkerGuard>) {
+        *self.writer.0.write().unwrap() = writer;
+        self.guard = guard;
+        self.dir = None;
+    }
+}
+
+/// Rotating `saya.<date>.log` writer in `dir`, creating the directory
+///
+/// Logs are written on a background thread; keep the guard alive to flush them.
+fn open_dir(dir: &Path) -> anyhow::Result<(NonBlocking, WorkerGuard)> {
+    std::fs::create_dir_all(dir)?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("saya")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(dir)?;
+    Ok(tracing_appender::non_blocking(appender))
+}
+
+/// Layer writing log files, silent until [`FileLog::apply`] gives it a directory
+pub fn file_layer() -> (impl Layer<Registry>, FileLog) {
+    let writer = FileWriter::default();
+    let (level, handle) = reload::Layer::new(LevelFilter::INFO);
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer.clone())
+        .with_ansi(false)
+        .with_filter(level);
+
+    let log = FileLog {
+        writer,
+        level: handle,
+        dir: None,
+        guard: None,
+    };
+    (layer, log)
+}
+
+/// Log to stdout, and to a rotating file when enabled
+///
+/// Starts before the profile is loaded, so its errors are logged too; apply the
+/// profile's settings to the returned [`FileLog`] once it is. Keep it alive
+/// until shutdown so buffered lines are written.
+pub fn init(config: &LoggingConfig) -> FileLog {
+    let stdout = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stdout)
+        .with_ansi(atty::is(atty::Stream::Stdout))
+        .with_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("debug")),
+        );
+    let (file, mut log) = file_layer();
+
+    tracing_subscriber::registry()
+        .with(file)
+        .with(stdout)
+        .init();
+
+    log.apply(config);
+    log
+}
+
+/// Log every panic through tracing, so it reaches the log file with a backtrace
+///
+/// The previous hook still runs, keeping the usual stderr report.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        log_panic(info);
+        previous(info);
+    }));
+}
+
+/// Log a panic with its thread, location and backtrace
+pub fn log_panic(info: &PanicHookInfo<'_>) {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("non-string panic payload");
+    let location = info
+        .location()
+        .map(|l| l.to_string())
+        .unwrap_or_else(|| "unknown location".to_string());
+    let thread = std::thread::current();
+    let backtrace = std::backtrace::Backtrace::force_capture();
+
+    tracing::error!(
+        thread = thread.name().unwrap_or("unnamed"),
+        %location,
+        "panic: {}\n{}",
+        message,
+        backtrace
+    );
+}
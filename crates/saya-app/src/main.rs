@@ -2,18 +2,23 @@ use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
-use saya_lang_japanese::JapaneseProcessor;
 use tokio::signal;
 use tokio_util_watchdog::Watchdog;
-use tracing_subscriber::util::SubscriberInitExt;
 
+pub mod capture;
 pub mod controller;
+pub mod display;
 pub mod events;
 pub mod io;
+pub mod known_words;
+pub mod languages;
+pub mod logging;
 pub mod ocr_context;
 pub mod profile;
+pub mod setup;
 pub mod state;
 pub mod status;
+pub mod translation;
 pub mod ui;
 
 #[cfg(test)]
@@ -24,22 +29,28 @@ use state::AppState;
 
 #[tokio::main(worker_threads = 4)]
 async fn main() {
-    // Initialize tracing subscriber for console logging
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("debug")),
-        )
-        .with_writer(std::io::stdout)
-        .with_ansi(atty::is(atty::Stream::Stdout))
-        .finish()
-        .init();
+    // Log with the defaults until the profile says where logs go, so errors
+    // loading it are logged too
+    let mut file_log = logging::init(&Default::default());
+    logging::install_panic_hook();
 
-    tracing::info!("Saya starting...");
+    let profile_name = "main";
+    let first_run = profile::init_user_config().expect("failed to load user config");
+    let mut config = profile::load_user_profile(profile_name).expect("failed to load user profile");
+    file_log.apply(&config.logging);
 
-    profile::init_user_config().expect("failed to load user config");
-    let config = profile::load_user_profile("main").expect("failed to load user profile");
-    let state = Arc::new(AppState::new(config));
+    tracing::info!("Saya starting...");
+    profile::check_capture_region(&mut config, saya_ocr::list_monitors());
+    if first_run {
+        config.setup_complete = false;
+    }
+    let state = match AppState::new(config, profile_name) {
+        Ok(state) => Arc::new(state),
+        Err(e) => {
+            tracing::error!("failed to initialize OCR engine: {:?}", e);
+            panic!("Exiting due to OCR init failure");
+        }
+    };
 
     let watchdog_timeout = {
         let config = state.config.read().await;
@@ -60,34 +71,18 @@ async fn main() {
 pub async fn run(state: Arc<AppState>, shutdown: impl Future<Output = ()>) {
     tracing::info!("Application starting");
 
-    // Initialize processor and translator
+    // Initialize processor (dictionary loads in the background) and translator
     let processor = {
         let config = state.config.read().await;
-        if config.dictionary.enabled {
-            JapaneseProcessor::with_additional_dicts(&config.dictionary.additional_paths)
-        } else {
-            tracing::warn!("Dictionary disabled, using empty processor");
-            JapaneseProcessor::with_additional_dicts(&[])
-        }
+        Arc::new(languages::DeferredProcessor::new(
+            config.dictionary_language(),
+        ))
     };
 
-    let translator = {
-        let config = state.config.read().await;
-        if config.translator.enabled && !config.translator.api_key.is_empty() {
-            Some(saya_lang_japanese::JapaneseTranslator::new(
-                config.translator.api_key.clone(),
-                config.translator.api_url.clone(),
-            ))
-        } else {
-            None
-        }
-    };
-
-    let processor = Arc::new(processor);
-    let translator = Arc::new(translator);
+    let translator = translation::translator_from_config(&*state.config.read().await);
 
     // Use controller for centralized task management
-    let controller = AppController::new(state);
+    let controller = AppController::new(state.clone());
     let mut tasks = controller.spawn_tasks(processor, translator);
 
     tokio::select! {
@@ -120,6 +115,12 @@ pub async fn run(state: Arc<AppState>, shutdown: impl Future<Output = ()>) {
         }
     }
 
+    // A debounced save may still be waiting
+    state
+        .config_saver
+        .flush(&state.config, &state.profile)
+        .await;
+
     tracing::info!("Application shutdown complete");
 }
 
@@ -1,10 +1,12 @@
 use std::sync::Arc;
 
-use kanal::AsyncSender;
-use saya_lang_japanese::{JapaneseProcessor, JapaneseTranslator};
+use kanal::{AsyncReceiver, AsyncSender};
+use saya_core::language::LanguageProcessor;
+use saya_translator::Translator;
 use saya_types::AppEvent;
 
 use crate::AppState;
+use crate::translation::TranslationDispatcher;
 
 /// Encapsulates OCR dependencies to reduce parameter passing
 ///
@@ -13,25 +15,38 @@ use crate::AppState;
 pub struct OcrContext {
     pub state: Arc<AppState>,
     pub event_tx: AsyncSender<AppEvent>,
-    pub processor: Arc<JapaneseProcessor>,
-    pub translator: Arc<Option<JapaneseTranslator>>,
+    pub processor: Arc<dyn LanguageProcessor>,
+    /// Rate-limited queue in front of the translator, if one is configured
+    pub translator: Option<TranslationDispatcher>,
+    /// Receiving end of `event_tx`, lets result sends drop stale events on a full channel
+    pub ui_queue: Option<AsyncReceiver<AppEvent>>,
 }
 
 impl OcrContext {
     pub fn new(
         state: Arc<AppState>,
         event_tx: AsyncSender<AppEvent>,
-        processor: Arc<JapaneseProcessor>,
-        translator: Arc<Option<JapaneseTranslator>>,
+        processor: Arc<dyn LanguageProcessor>,
+        translator: Option<Arc<dyn Translator>>,
     ) -> Self {
+        let translator =
+            translator.map(|t| TranslationDispatcher::spawn(t, state.config.clone()));
+
         Self {
             state,
             event_tx,
             processor,
             translator,
+            ui_queue: None,
         }
     }
 
+    /// Send results with [`crate::display::send_latest`] through `queue`
+    pub fn with_ui_queue(mut self, queue: AsyncReceiver<AppEvent>) -> Self {
+        self.ui_queue = Some(queue);
+        self
+    }
+
     /// Clone the context for passing to async tasks
     ///
     /// This clones all Arc references, incrementing reference counts
@@ -42,6 +57,7 @@ impl OcrContext {
             event_tx: self.event_tx.clone(),
             processor: self.processor.clone(),
             translator: self.translator.clone(),
+            ui_queue: self.ui_queue.clone(),
         }
     }
 }
@@ -1,11 +1,15 @@
 use std::fs::{self, File};
 use std::io::BufReader;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use saya_config::Config;
+use saya_ocr::MonitorInfo;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use windows::Win32::UI::Shell::{FOLDERID_RoamingAppData, KF_FLAG_DEFAULT, SHGetKnownFolderPath};
+use tokio::sync::RwLock;
 
 /// Load the default config shipped in the repo
 fn load_repo_default_config() -> anyhow::Result<Config> {
@@ -17,7 +21,12 @@ fn load_repo_default_config() -> anyhow::Result<Config> {
 }
 
 /// Windows Roaming folder
+#[cfg(windows)]
 fn roaming_dir() -> PathBuf {
+    use windows::Win32::UI::Shell::{
+        FOLDERID_RoamingAppData, KF_FLAG_DEFAULT, SHGetKnownFolderPath,
+    };
+
     unsafe {
         let path = SHGetKnownFolderPath(&FOLDERID_RoamingAppData, KF_FLAG_DEFAULT, None)
             .expect("Failed to get RoamingAppData");
@@ -25,7 +34,16 @@ fn roaming_dir() -> PathBuf {
     }
 }
 
-fn saya_root() -> PathBuf {
+/// Per-user config folder elsewhere, e.g. for tests on CI
+#[cfg(not(windows))]
+fn roaming_dir() -> PathBuf {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+pub fn saya_root() -> PathBuf {
     roaming_dir().join("Saya")
 }
 
@@ -41,7 +59,9 @@ pub struct Profile {
 }
 
 /// Initialize user config folders and main profile if missing
-pub fn init_user_config() -> anyhow::Result<()> {
+///
+/// Returns `true` on first run, when the main profile had to be created.
+pub fn init_user_config() -> anyhow::Result<bool> {
     fs::create_dir_all(profiles_dir())?;
 
     let main_profile = profiles_dir().join("main.json");
@@ -55,9 +75,10 @@ pub fn init_user_config() -> anyhow::Result<()> {
         };
         fs::write(&main_profile, serde_json::to_string_pretty(&profile)?)?;
         tracing::info!("Created main profile in Roaming");
+        return Ok(true);
     }
 
-    Ok(())
+    Ok(false)
 }
 
 /// Load a user profile by name, defaulting to main if name not found
@@ -82,6 +103,46 @@ pub fn load_user_profile(name: &str) -> anyhow::Result<Config> {
     }
 }
 
+/// Clear `ocr.capture_region` if it's on none of `monitors`
+///
+/// Run when a profile is activated, since the monitor a region was saved on
+/// may since have been disconnected. Returns whether the region was cleared.
+/// With no monitors to compare against the region is left alone.
+pub fn validate_capture_region(config: &mut Config, monitors: &[MonitorInfo]) -> bool {
+    let Some(region) = config.ocr.capture_region else {
+        return false;
+    };
+    if monitors.is_empty() || monitors.iter().any(|m| m.overlaps(region)) {
+        return false;
+    }
+
+    tracing::warn!(
+        "Saved capture region {}x{} at ({}, {}) is off-screen, resetting it",
+        region.width,
+        region.height,
+        region.x,
+        region.y
+    );
+    config.ocr.capture_region = None;
+    true
+}
+
+/// [`validate_capture_region`] against the monitors listed now
+///
+/// Run at startup and again when an OCR setting changes at runtime.
+pub fn check_capture_region(
+    config: &mut Config,
+    monitors: anyhow::Result<Vec<MonitorInfo>>,
+) -> bool {
+    match monitors {
+        Ok(monitors) => validate_capture_region(config, &monitors),
+        Err(e) => {
+            tracing::warn!("Can't check the saved capture region: {}", e);
+            false
+        }
+    }
+}
+
 /// Add a new profile cloned from main (or repo default if main missing)
 pub fn add_profile_from_default(new_name: &str) -> anyhow::Result<PathBuf> {
     let default_config = load_user_profile("main")?;
@@ -107,6 +168,81 @@ pub fn save_config(config: Config, profile_name: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Minimum time between debounced profile saves
+pub const CONFIG_SAVE_DEBOUNCE: Duration = Duration::from_secs(2);
+
+type SaveFn = dyn Fn(Config, &str) -> anyhow::Result<()> + Send + Sync;
+
+/// Debounces profile saves so frequent updates write at most once per interval
+pub struct ConfigSaver {
+    delay: Duration,
+    pending: Arc<AtomicBool>,
+    save: Arc<SaveFn>,
+}
+
+impl ConfigSaver {
+    pub fn new() -> Self {
+        Self::with_sink(CONFIG_SAVE_DEBOUNCE, save_config)
+    }
+
+    /// Saver that hands the config to `save` instead of writing the profile file
+    pub fn with_sink<F>(delay: Duration, save: F) -> Self
+    where
+        F: Fn(Config, &str) -> anyhow::Result<()> + Send + Sync + 'static,
+    {
+        Self {
+            delay,
+            pending: Arc::new(AtomicBool::new(false)),
+            save: Arc::new(save),
+        }
+    }
+
+    /// Save the config after the debounce delay, coalescing calls made meanwhile
+    ///
+    /// The config is read when the delay elapses, so the latest value is written.
+    pub fn schedule(&self, config: Arc<RwLock<Config>>, profile: &str) {
+        if self.pending.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let pending = self.pending.clone();
+        let save = self.save.clone();
+        let delay = self.delay;
+        let profile = profile.to_string();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            // Already written by `flush`
+            if !pending.swap(false, Ordering::SeqCst) {
+                return;
+            }
+
+            let snapshot = config.read().await.clone();
+            if let Err(e) = save(snapshot, &profile) {
+                tracing::warn!("Failed to save profile {}: {}", profile, e);
+            }
+        });
+    }
+
+    /// Write a scheduled save now instead of after the delay, for shutdown
+    pub async fn flush(&self, config: &RwLock<Config>, profile: &str) {
+        if !self.pending.swap(false, Ordering::SeqCst) {
+            return;
+        }
+
+        let snapshot = config.read().await.clone();
+        if let Err(e) = (self.save)(snapshot, profile) {
+            tracing::warn!("Failed to save profile {}: {}", profile, e);
+        }
+    }
+}
+
+impl Default for ConfigSaver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub fn update_config_field(config: &mut Config, key: &str, value: &str) -> anyhow::Result<()> {
     // Convert config to serde_json::Value
     let mut json_val = serde_json::to_value(&mut *config)?;
@@ -130,6 +266,11 @@ pub fn update_config_field(config: &mut Config, key: &str, value: &str) -> anyho
                     Value::Number(_) => {
                         if let Ok(v) = value.parse::<i64>() {
                             *existing = Value::Number(v.into());
+                        } else if let Some(v) =
+                            value.parse::<f64>().ok().and_then(serde_json::Number::from_f64)
+                        {
+                            // Fractional settings like ui.opacity
+                            *existing = Value::Number(v);
                         }
                     }
                     Value::String(_) => *existing = Value::String(value.to_string()),
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use kanal::AsyncSender;
+use saya_config::Config;
+use saya_types::{AppEvent, SetupStatus, SetupStep};
+
+use crate::profile::save_config;
+use crate::state::AppState;
+
+/// Walks the first-run setup steps in order
+pub struct SetupWizard {
+    current: SetupStep,
+}
+
+impl SetupWizard {
+    pub fn new() -> Self {
+        Self {
+            current: SetupStep::OcrLanguage,
+        }
+    }
+
+    pub fn current(&self) -> SetupStep {
+        self.current
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.current == SetupStep::Complete
+    }
+
+    /// Record the current step's outcome and move to the next step
+    ///
+    /// Returns the event surfacing the outcome to the UI. A failed step does
+    /// not block the rest of the setup.
+    pub fn advance(&mut self, status: SetupStatus) -> AppEvent {
+        let step = self.current;
+        self.current = match step {
+            SetupStep::OcrLanguage => SetupStep::Clipboard,
+            SetupStep::Clipboard => SetupStep::TranslatorKey,
+            SetupStep::TranslatorKey => SetupStep::AnkiUrl,
+            SetupStep::AnkiUrl | SetupStep::Complete => SetupStep::Complete,
+        };
+        AppEvent::SetupStep { step, status }
+    }
+}
+
+impl Default for SetupWizard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run a single setup check against the current config
+pub fn check_step(step: SetupStep, config: &Config) -> SetupStatus {
+    match step {
+        SetupStep::OcrLanguage => match saya_ocr::create_backend(
+            &config.ocr.backend,
+            &config.ocr.language,
+            &config.ocr.tesseract_path,
+        ) {
+            Ok(_) => SetupStatus::Passed,
+            Err(e) => SetupStatus::Failed(format!(
+                "OCR language '{}' unavailable: {}",
+                config.ocr.language, e
+            )),
+        },
+        SetupStep::Clipboard => match saya_io::clipboard::check_access() {
+            Ok(()) => SetupStatus::Passed,
+            Err(e) => SetupStatus::Failed(format!("Clipboard unavailable: {}", e)),
+        },
+        SetupStep::TranslatorKey => {
+            if !config.translator.enabled {
+                SetupStatus::Skipped
+            } else if config.translator.api_key.is_empty() {
+                SetupStatus::NeedsInput("translator.api_key".to_string())
+            } else {
+                SetupStatus::Passed
+            }
+        }
+        SetupStep::AnkiUrl => {
+            if config.anki.enabled {
+                SetupStatus::NeedsInput("anki.url".to_string())
+            } else {
+                SetupStatus::Skipped
+            }
+        }
+        SetupStep::Complete => SetupStatus::Passed,
+    }
+}
+
+/// Drive the wizard through every step, emitting `SetupStep` events
+///
+/// Marks `setup_complete` once all steps have been surfaced.
+pub async fn run_wizard<F>(
+    config: &mut Config,
+    tx: &AsyncSender<AppEvent>,
+    check: F,
+) -> anyhow::Result<()>
+where
+    F: Fn(SetupStep, &Config) -> SetupStatus,
+{
+    let mut wizard = SetupWizard::new();
+
+    while !wizard.is_complete() {
+        let status = check(wizard.current(), config);
+        tx.send(wizard.advance(status)).await?;
+    }
+
+    config.setup_complete = true;
+    tx.send(AppEvent::SetupStep {
+        step: SetupStep::Complete,
+        status: SetupStatus::Passed,
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// First-run setup, persisting `setup_complete` to the profile when done
+pub async fn run_setup(state: Arc<AppState>, tx: AsyncSender<AppEvent>) -> anyhow::Result<()> {
+    tracing::info!("Running first-run setup");
+
+    let mut config = state.config.read().await.clone();
+    run_wizard(&mut config, &tx, check_step).await?;
+
+    let mut current = state.config.write().await;
+    current.setup_complete = true;
+    save_config(current.clone(), &state.profile)?;
+
+    tracing::info!("First-run setup complete");
+    Ok(())
+}
@@ -1,34 +1,158 @@
-use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
+use std::collections::HashSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 use saya_config::Config;
+use saya_io::clipboard::SelfWrites;
+use saya_ocr::MonitorInfo;
 use saya_types::CaptureRegion;
-use tokio::sync::RwLock;
-use windows::Media::Ocr::OcrEngine as WinOcrEngine;
+use tokio::sync::{RwLock, Semaphore, SemaphorePermit};
 
+use crate::capture::{Capturer, ScreenCapturer};
+use crate::known_words::KnownWords;
+use crate::profile::ConfigSaver;
 use crate::status::AppStatus;
 
 pub struct AppState {
     pub config: Arc<RwLock<Config>>,
-    pub ocr_engine: WinOcrEngine,
+    pub capturer: Arc<dyn Capturer>,
     pub auto_ocr_running: AtomicBool,
+    /// Watchers stay alive but drop what they see while set
+    pub watchers_paused: AtomicBool,
+    pub ocr_generation: AtomicU64,
+    /// Bumped by every search-as-you-type query
+    pub search_generation: AtomicU64,
+    /// Hash of the last recognized OCR text, 0 when there is none
+    pub last_ocr_hash: AtomicU64,
+    /// Image hash of the last auto OCR frame that was recognized
+    pub last_frame_hash: Mutex<Option<u64>>,
+    pub ocr_permits: Semaphore,
     pub status: AppStatus,
     pub current_capture_region: Arc<RwLock<Option<CaptureRegion>>>,
+    pub profile: String,
+    pub clipboard_writes: Arc<SelfWrites>,
+    pub config_saver: ConfigSaver,
+    pub known_words: Arc<KnownWords>,
+    /// Duplicate keys of the Anki cards added this session
+    pub added_cards: Mutex<HashSet<String>>,
 }
 
 impl AppState {
-    pub fn new(config: Config) -> Self {
-        let ocr_engine = saya_ocr::init_ocr_engine(&config.ocr.language).unwrap_or_else(|e| {
-            tracing::error!("failed to initialize OCR engine: {:?}", e);
-            panic!("Exiting due to OCR init failure");
-        });
+    /// Build the state around the configured OCR engine
+    pub fn new(config: Config, profile: &str) -> anyhow::Result<Self> {
+        let ocr_backend = saya_ocr::create_backend(
+            &config.ocr.backend,
+            &config.ocr.language,
+            &config.ocr.tesseract_path,
+        )?;
+        let capturer = Arc::new(ScreenCapturer::new(ocr_backend));
+        Ok(Self::with_capturer(config, profile, capturer))
+    }
+
+    /// Build the state around `capturer` instead of the configured OCR engine
+    pub fn with_capturer(config: Config, profile: &str, capturer: Arc<dyn Capturer>) -> Self {
+        let ocr_permits = Semaphore::new(config.ocr.max_concurrent.max(1));
+        // Start from the region saved by the last session, if any
+        let capture_region = config.ocr.capture_region;
+
+        let known_words = match &config.dictionary.known_words_path {
+            Some(path) => KnownWords::load_from_file(path).unwrap_or_else(|e| {
+                tracing::warn!("Failed to load known words from {}: {}", path, e);
+                KnownWords::new()
+            }),
+            None => KnownWords::new(),
+        };
 
         Self {
             config: Arc::new(RwLock::new(config)),
-            ocr_engine,
+            capturer,
             auto_ocr_running: AtomicBool::new(false),
+            watchers_paused: AtomicBool::new(false),
+            ocr_generation: AtomicU64::new(0),
+            search_generation: AtomicU64::new(0),
+            last_ocr_hash: AtomicU64::new(0),
+            last_frame_hash: Mutex::new(None),
+            ocr_permits,
             status: AppStatus::new(),
-            current_capture_region: Arc::new(RwLock::new(None)),
+            current_capture_region: Arc::new(RwLock::new(capture_region)),
+            profile: profile.to_string(),
+            clipboard_writes: Arc::new(SelfWrites::default()),
+            config_saver: ConfigSaver::new(),
+            known_words: Arc::new(known_words),
+            added_cards: Mutex::new(HashSet::new()),
         }
     }
+
+    /// Monitors connected now, listed on the blocking pool
+    pub async fn monitors(&self) -> anyhow::Result<Vec<MonitorInfo>> {
+        let capturer = self.capturer.clone();
+        tokio::task::spawn_blocking(move || capturer.monitors()).await?
+    }
+
+    /// Hide `term` from future results, returns `false` if it was already known
+    pub fn mark_known(&self, term: &str) -> bool {
+        self.known_words.insert(term)
+    }
+
+    /// Whether hotkeys, auto OCR, clipboard and websocket input are paused
+    pub fn watchers_paused(&self) -> bool {
+        self.watchers_paused.load(Ordering::SeqCst)
+    }
+
+    /// Reserve one of the bounded OCR worker slots, `None` if all are busy
+    pub fn try_acquire_ocr(&self) -> Option<SemaphorePermit<'_>> {
+        self.ocr_permits.try_acquire().ok()
+    }
+
+    /// Start a new OCR generation, invalidating any capture still in flight
+    pub fn next_ocr_generation(&self) -> u64 {
+        self.ocr_generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Record `text` as the latest OCR output, returning whether it differs from the last
+    pub fn ocr_text_changed(&self, text: &str) -> bool {
+        let mut hasher = DefaultHasher::new();
+        text.trim().hash(&mut hasher);
+        // 0 is reserved for "no text"
+        let hash = hasher.finish().max(1);
+        self.last_ocr_hash.swap(hash, Ordering::SeqCst) != hash
+    }
+
+    /// Forget the last OCR text, so the next one always counts as changed
+    pub fn reset_ocr_text(&self) {
+        self.last_ocr_hash.store(0, Ordering::SeqCst);
+    }
+
+    /// Whether a frame hashing to `hash` differs from the last recognized one
+    /// by more than `threshold` bits
+    pub fn frame_changed(&self, hash: u64, threshold: u32) -> bool {
+        self.last_frame_hash
+            .lock()
+            .unwrap()
+            .is_none_or(|previous| saya_ocr::hamming_distance(previous, hash) > threshold)
+    }
+
+    /// Remember `hash` as the last recognized frame
+    ///
+    /// Only frames that were recognized are recorded, so slow drift still adds
+    /// up to a change and a failed capture is retried.
+    pub fn record_frame(&self, hash: u64) {
+        *self.last_frame_hash.lock().unwrap() = Some(hash);
+    }
+
+    /// Whether `generation` still belongs to the latest OCR capture
+    pub fn is_current_ocr(&self, generation: u64) -> bool {
+        self.ocr_generation.load(Ordering::SeqCst) == generation
+    }
+
+    /// Start a new search generation, invalidating any query still in flight
+    pub fn next_search_generation(&self) -> u64 {
+        self.search_generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Whether `generation` still belongs to the latest search query
+    pub fn is_current_search(&self, generation: u64) -> bool {
+        self.search_generation.load(Ordering::SeqCst) == generation
+    }
 }
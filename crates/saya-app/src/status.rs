@@ -1,6 +1,14 @@
 use std::sync::Arc;
-use std::time::SystemTime;
-use tokio::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Notify, RwLock};
+use tokio_util::sync::CancellationToken;
+
+use crate::state::AppState;
 
 /// OCR status information
 #[derive(Clone, Debug, Default)]
@@ -12,15 +20,54 @@ pub struct OcrStatus {
     pub current_message: String,
 }
 
+impl OcrStatus {
+    /// Record a successful capture
+    pub fn record_capture(&mut self) {
+        self.last_capture_time = Some(SystemTime::now());
+        self.capture_count += 1;
+    }
+
+    /// Record a failed capture
+    pub fn record_error(&mut self, message: String) {
+        self.error_count += 1;
+        self.current_message = message;
+    }
+}
+
 /// Application status
 pub struct AppStatus {
     pub ocr: Arc<RwLock<OcrStatus>>,
+    pub backend_ready: AtomicBool,
+    /// The language processor finished loading, successfully or not
+    dictionary_ready: AtomicBool,
+    dictionary_notify: Notify,
 }
 
 impl AppStatus {
     pub fn new() -> Self {
         Self {
             ocr: Arc::new(RwLock::new(OcrStatus::default())),
+            backend_ready: AtomicBool::new(false),
+            dictionary_ready: AtomicBool::new(false),
+            dictionary_notify: Notify::new(),
+        }
+    }
+
+    /// Record that dictionary loading finished, waking [`AppStatus::dictionary_loaded`]
+    pub fn mark_dictionary_ready(&self) {
+        self.dictionary_ready.store(true, Ordering::SeqCst);
+        self.dictionary_notify.notify_waiters();
+    }
+
+    /// Wait until [`AppStatus::mark_dictionary_ready`] has been called
+    pub async fn dictionary_loaded(&self) {
+        loop {
+            // Registered before the check so a concurrent mark isn't missed
+            let notified = self.dictionary_notify.notified();
+            if self.dictionary_ready.load(Ordering::SeqCst) {
+                return;
+            }
+            notified.await;
         }
     }
 }
@@ -30,3 +77,100 @@ impl Default for AppStatus {
         Self::new()
     }
 }
+
+/// JSON body returned by `GET /status`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatusSnapshot {
+    pub backend_ready: bool,
+    pub profile: String,
+    pub ocr_language: String,
+    /// Unix timestamp (ms) of the last successful capture
+    pub last_capture_ms: Option<u64>,
+    pub capture_count: u64,
+    pub error_count: u64,
+}
+
+impl StatusSnapshot {
+    pub async fn collect(state: &AppState) -> Self {
+        let ocr_language = state.config.read().await.ocr.language.clone();
+        let ocr = state.status.ocr.read().await;
+
+        Self {
+            backend_ready: state.status.backend_ready.load(Ordering::SeqCst),
+            profile: state.profile.clone(),
+            ocr_language,
+            last_capture_ms: ocr
+                .last_capture_time
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as u64),
+            capture_count: ocr.capture_count,
+            error_count: ocr.error_count,
+        }
+    }
+}
+
+/// Optional localhost status server, enabled by `status.enabled`
+pub async fn status_server(state: Arc<AppState>, cancel: CancellationToken) -> anyhow::Result<()> {
+    let (enabled, port) = {
+        let config = state.config.read().await;
+        (config.status.enabled, config.status.port)
+    };
+
+    if !enabled {
+        return Ok(());
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    tracing::info!("Status server listening on http://127.0.0.1:{port}/status");
+
+    serve_status(listener, state, cancel).await
+}
+
+/// Accept connections until cancelled
+pub async fn serve_status(
+    listener: TcpListener,
+    state: Arc<AppState>,
+    cancel: CancellationToken,
+) -> anyhow::Result<()> {
+    loop {
+        let (mut stream, _) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            _ = cancel.cancelled() => break,
+        };
+
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = respond(&mut stream, &state).await {
+                tracing::warn!("Status request failed: {}", e);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+async fn respond(stream: &mut TcpStream, state: &AppState) -> anyhow::Result<()> {
+    let mut buf = [0u8; 1024];
+    let n = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+
+    let mut request_line = request.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+
+    let (code, body) = if method == "GET" && path == "/status" {
+        let snapshot = StatusSnapshot::collect(state).await;
+        ("200 OK", serde_json::to_string(&snapshot)?)
+    } else {
+        ("404 Not Found", r#"{"error":"not found"}"#.to_string())
+    };
+
+    let response = format!(
+        "HTTP/1.1 {code}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! Tests for filling the known words from an AnkiConnect deck
+
+use saya_anki::AnkiConnectClient;
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+
+use crate::known_words::{KnownWords, load_from_anki};
+use crate::tests::mock_http;
+
+/// Minimal AnkiConnect answering findNotes/notesInfo for two notes
+async fn mock_anki() -> String {
+    mock_http::mock_anki(|request| match request["action"].as_str() {
+        Some("findNotes") => json!([1, 2]),
+        Some("notesInfo") => json!([
+            {
+                "noteId": 1,
+                "fields": {
+                    "Front": { "value": "食べる\nたべる", "order": 0 },
+                    "Back": { "value": "to eat", "order": 1 }
+                }
+            },
+            {
+                "noteId": 2,
+                "fields": {
+                    "Front": { "value": "<b>日本</b>", "order": 0 },
+                    "Back": { "value": "Japan", "order": 1 }
+                }
+            }
+        ]),
+        _ => Value::Null,
+    })
+    .await
+}
+
+#[tokio::test]
+async fn test_deck_terms_populate_known_words() {
+    let client = AnkiConnectClient::new(mock_anki().await);
+    let known = KnownWords::new();
+
+    let loaded = load_from_anki(&known, &client, "Japanese").await;
+
+    assert_eq!(loaded, 2);
+    assert!(known.contains("食べる"));
+    assert!(known.contains("日本"));
+    assert!(!known.contains("たべる"));
+}
+
+#[tokio::test]
+async fn test_unavailable_anki_is_skipped() {
+    // Nothing listens on this port once the listener is dropped
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let known = KnownWords::new();
+    let loaded = load_from_anki(&known, &AnkiConnectClient::new(url), "Japanese").await;
+
+    assert_eq!(loaded, 0);
+    assert!(known.is_empty());
+}
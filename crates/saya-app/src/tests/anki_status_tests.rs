@@ -0,0 +1,39 @@
+//! Tests for the AnkiConnect health check behind the Anki indicator
+
+use std::time::Duration;
+
+use saya_anki::AnkiConnectClient;
+use saya_types::AppEvent;
+use serde_json::json;
+use tokio::net::TcpListener;
+use tokio::time::timeout;
+
+use crate::events::anki_status::anki_health_loop;
+use crate::tests::mock_http::serve_anki;
+
+async fn next_status(rx: &kanal::AsyncReceiver<AppEvent>) -> (bool, Option<u32>) {
+    match timeout(Duration::from_secs(5), rx.recv()).await {
+        Ok(Ok(AppEvent::AnkiStatus { ok, version })) => (ok, version),
+        Ok(other) => panic!("expected AnkiStatus, got {:?}", other.map(|e| e.name())),
+        Err(_) => panic!("no AnkiStatus from the health check"),
+    }
+}
+
+#[tokio::test]
+async fn test_status_flips_when_anki_comes_up() {
+    // Reserve a port, then close it so nothing answers yet
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let client = AnkiConnectClient::new(format!("http://{}", addr))
+        .with_circuit_breaker(1, Duration::from_millis(100));
+    let (tx, rx) = kanal::unbounded_async();
+    tokio::spawn(anki_health_loop(client, Duration::from_millis(20), tx));
+
+    assert_eq!(next_status(&rx).await, (false, None));
+
+    // Answer every request the way `version` does
+    serve_anki(TcpListener::bind(addr).await.unwrap(), |_| json!(6));
+    assert_eq!(next_status(&rx).await, (true, Some(6)));
+}
@@ -0,0 +1,50 @@
+//! Tests for auto OCR following the capture region
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use saya_config::Config;
+use saya_types::CaptureRegion;
+
+use crate::capture::FakeCapturer;
+use crate::events::trigger_auto_ocr::active_region;
+use crate::events::update_capture_region::handle_capture_region_update;
+use crate::profile::ConfigSaver;
+use crate::state::AppState;
+
+fn region(x: i32) -> CaptureRegion {
+    CaptureRegion {
+        x,
+        y: 10,
+        width: 100,
+        height: 100,
+    }
+}
+
+#[tokio::test]
+async fn test_auto_ocr_uses_initial_region_without_updates() {
+    let state = AppState::with_capturer(Config::default(), "main", FakeCapturer::new(""));
+
+    assert_eq!(active_region(&state, region(10)).await, region(10));
+}
+
+#[tokio::test]
+async fn test_auto_ocr_picks_up_updated_region() {
+    let mut state = AppState::with_capturer(Config::default(), "main", FakeCapturer::new(""));
+    // Keep the test from writing the real profile
+    state.config_saver = ConfigSaver::with_sink(Duration::ZERO, |_, _| Ok(()));
+    let state = Arc::new(state);
+
+    handle_capture_region_update(&state, region(40)).await.unwrap();
+
+    assert_eq!(active_region(&state, region(10)).await, region(40));
+}
+
+#[tokio::test]
+async fn test_saved_region_restored_on_startup() {
+    let mut config = Config::default();
+    config.ocr.capture_region = Some(region(70));
+    let state = AppState::with_capturer(config, "main", FakeCapturer::new(""));
+
+    assert_eq!(active_region(&state, region(10)).await, region(70));
+}
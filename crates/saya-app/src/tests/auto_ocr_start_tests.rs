@@ -0,0 +1,56 @@
+//! Tests for starting the auto OCR loop from TriggerAutoOcr
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use saya_config::Config;
+use saya_lang_japanese::JapaneseProcessor;
+use saya_types::{AppEvent, CaptureRegion};
+
+use crate::capture::FakeCapturer;
+use crate::events::trigger_auto_ocr::start_auto_ocr_loop;
+use crate::ocr_context::OcrContext;
+use crate::state::AppState;
+
+const REGION: CaptureRegion = CaptureRegion {
+    x: 10,
+    y: 10,
+    width: 100,
+    height: 100,
+};
+
+#[tokio::test]
+async fn test_trigger_auto_ocr_starts_loop_once() {
+    let mut config = Config::default();
+    config.ocr.auto = true;
+    config.ocr.max_concurrent = 1;
+    config.auto_ocr_interval_ms = 60_000;
+    let state = Arc::new(AppState::with_capturer(
+        config,
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let (tx, rx) = kanal::unbounded_async();
+    let ctx = OcrContext::new(
+        state.clone(),
+        tx,
+        Arc::new(JapaneseProcessor::new()),
+        None,
+    );
+
+    // Occupy the worker slot so the loop's first cycle doesn't capture the screen
+    let _held = state.try_acquire_ocr().unwrap();
+
+    assert!(start_auto_ocr_loop(&ctx, REGION));
+    assert!(!start_auto_ocr_loop(&ctx, REGION));
+    assert!(state.auto_ocr_running.load(Ordering::SeqCst));
+
+    // Exactly one loop ran its first cycle
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let mut cycles = 0;
+    while let Ok(Some(AppEvent::OcrStatusUpdate { .. })) = rx.try_recv() {
+        cycles += 1;
+    }
+    assert_eq!(cycles, 1);
+}
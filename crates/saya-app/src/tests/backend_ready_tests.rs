@@ -0,0 +1,59 @@
+//! Tests for sending BackendReady only after initialization finishes
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use saya_config::Config;
+use saya_types::AppEvent;
+use tokio::time::timeout;
+
+use crate::capture::FakeCapturer;
+use crate::io::signal_backend_ready;
+use crate::state::AppState;
+
+#[tokio::test]
+async fn test_backend_ready_waits_for_dictionary() {
+    let state = Arc::new(AppState::with_capturer(
+        Config::default(),
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let (tx, rx) = kanal::unbounded_async();
+
+    tokio::spawn(signal_backend_ready(state.clone(), tx));
+
+    // Simulated slow dictionary load
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert!(rx.try_recv().unwrap().is_none());
+    assert!(!state.status.backend_ready.load(Ordering::SeqCst));
+
+    state.status.mark_dictionary_ready();
+
+    let event = timeout(Duration::from_secs(1), rx.recv())
+        .await
+        .expect("BackendReady not sent after the dictionary loaded")
+        .unwrap();
+    assert!(matches!(event, AppEvent::BackendReady));
+    assert!(state.status.backend_ready.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_backend_ready_immediate_when_already_loaded() {
+    let state = Arc::new(AppState::with_capturer(
+        Config::default(),
+        "main",
+        FakeCapturer::new(""),
+    ));
+    state.status.mark_dictionary_ready();
+    let (tx, rx) = kanal::unbounded_async();
+
+    timeout(Duration::from_secs(1), signal_backend_ready(state, tx))
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        rx.try_recv().unwrap(),
+        Some(AppEvent::BackendReady)
+    ));
+}
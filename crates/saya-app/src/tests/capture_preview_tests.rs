@@ -0,0 +1,47 @@
+//! Tests for previewing a capture without running OCR
+
+use saya_config::Config;
+use saya_ocr::RawImage;
+use saya_types::{AppEvent, CaptureRegion};
+
+use crate::capture::FakeCapturer;
+use crate::events::capture_preview::handle_capture_preview;
+use crate::state::AppState;
+
+const REGION: CaptureRegion = CaptureRegion {
+    x: 100,
+    y: 200,
+    width: 64,
+    height: 32,
+};
+
+#[tokio::test]
+async fn test_preview_returns_png_of_region() {
+    let capturer = FakeCapturer::new("unused");
+    let state = AppState::with_capturer(Config::default(), "main", capturer.clone());
+    let (tx, rx) = kanal::unbounded_async();
+
+    handle_capture_preview(&state, REGION, &tx).await.unwrap();
+
+    let Ok(Some(AppEvent::CapturePreview(png))) = rx.try_recv() else {
+        panic!("expected CapturePreview");
+    };
+    assert!(!png.is_empty());
+    let image = RawImage::from_png(&png).unwrap();
+    assert_eq!((image.width, image.height), (64, 32));
+    assert_eq!(*capturer.regions.lock().unwrap(), [REGION]);
+}
+
+#[tokio::test]
+async fn test_empty_region_reports_failure() {
+    let state = AppState::with_capturer(Config::default(), "main", FakeCapturer::new(""));
+    let (tx, rx) = kanal::unbounded_async();
+    let empty = CaptureRegion { width: 0, ..REGION };
+
+    handle_capture_preview(&state, empty, &tx).await.unwrap();
+
+    assert!(matches!(
+        rx.try_recv(),
+        Ok(Some(AppEvent::OcrStatusUpdate { .. }))
+    ));
+}
@@ -0,0 +1,210 @@
+//! Tests for clamping and persisting the selected capture region
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use saya_config::Config;
+use saya_ocr::MonitorInfo;
+use saya_types::CaptureRegion;
+use tokio::sync::RwLock;
+
+use crate::capture::FakeCapturer;
+use crate::events::update_capture_region::{apply_capture_region, handle_capture_region_update};
+use crate::profile::ConfigSaver;
+use crate::state::AppState;
+
+const SCREEN: CaptureRegion = CaptureRegion {
+    x: 0,
+    y: 0,
+    width: 1920,
+    height: 1080,
+};
+
+#[test]
+fn test_region_clamped_to_screen() {
+    let mut config = Config::default();
+    let dragged = CaptureRegion {
+        x: -50,
+        y: 900,
+        width: 400,
+        height: 400,
+    };
+
+    let saved = apply_capture_region(&mut config, dragged, Some(SCREEN)).unwrap();
+
+    assert_eq!(
+        saved,
+        CaptureRegion {
+            x: 0,
+            y: 900,
+            width: 350,
+            height: 180,
+        }
+    );
+    assert_eq!(config.ocr.capture_region, Some(saved));
+}
+
+#[test]
+fn test_region_outside_screen_keeps_minimum_size() {
+    let mut config = Config::default();
+    let dragged = CaptureRegion {
+        x: 3000,
+        y: 2000,
+        width: 100,
+        height: 100,
+    };
+
+    let saved = apply_capture_region(&mut config, dragged, Some(SCREEN)).unwrap();
+
+    assert_eq!(saved.x, 1919);
+    assert_eq!(saved.y, 1079);
+    assert_eq!((saved.width, saved.height), (1, 1));
+}
+
+#[test]
+fn test_empty_region_stays_empty() {
+    let mut config = Config::default();
+    let shrunk = CaptureRegion {
+        x: 100,
+        y: 100,
+        width: 0,
+        height: 400,
+    };
+
+    // Capturing it then fails as too small instead of reading one pixel
+    assert_eq!(
+        apply_capture_region(&mut config, shrunk, Some(SCREEN)),
+        Some(shrunk)
+    );
+}
+
+#[test]
+fn test_unchanged_region_not_persisted_again() {
+    let mut config = Config::default();
+    let region = CaptureRegion {
+        x: 100,
+        y: 100,
+        width: 600,
+        height: 400,
+    };
+
+    assert!(apply_capture_region(&mut config, region, Some(SCREEN)).is_some());
+    assert!(apply_capture_region(&mut config, region, Some(SCREEN)).is_none());
+}
+
+#[test]
+fn test_region_survives_config_round_trip() {
+    let mut config = Config::default();
+    let region = CaptureRegion {
+        x: 10,
+        y: 20,
+        width: 300,
+        height: 200,
+    };
+    apply_capture_region(&mut config, region, None);
+
+    let json = serde_json::to_string(&config).unwrap();
+    let loaded: Config = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(loaded.ocr.capture_region, Some(region));
+}
+
+#[tokio::test]
+async fn test_region_update_is_saved_debounced() {
+    let saved = Arc::new(Mutex::new(Vec::new()));
+    let mut state = AppState::with_capturer(Config::default(), "main", FakeCapturer::new(""));
+    state.config_saver = {
+        let saved = saved.clone();
+        ConfigSaver::with_sink(Duration::from_millis(50), move |config, _| {
+            saved.lock().unwrap().push(config.ocr.capture_region);
+            Ok(())
+        })
+    };
+    let state = Arc::new(state);
+
+    for x in [10, 20, 30] {
+        let region = CaptureRegion {
+            x,
+            y: 10,
+            width: 100,
+            height: 100,
+        };
+        handle_capture_region_update(&state, region).await.unwrap();
+    }
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let saved = saved.lock().unwrap();
+    assert_eq!(saved.len(), 1, "rapid updates should coalesce into one save");
+    assert_eq!(saved[0].map(|r| r.x), Some(30));
+}
+
+#[tokio::test]
+async fn test_off_screen_update_is_clamped_and_saved() {
+    let capturer = FakeCapturer::new("");
+    *capturer.monitors.lock().unwrap() = vec![MonitorInfo {
+        index: 0,
+        name: "Display 0".to_string(),
+        x: 0,
+        y: 0,
+        width: 1920,
+        height: 1080,
+        scale_factor: 1.0,
+        is_primary: true,
+    }];
+    let saved = Arc::new(Mutex::new(Vec::new()));
+    let mut state = AppState::with_capturer(Config::default(), "main", capturer);
+    state.config_saver = {
+        let saved = saved.clone();
+        ConfigSaver::with_sink(Duration::from_millis(50), move |config, _| {
+            saved.lock().unwrap().push(config.ocr.capture_region);
+            Ok(())
+        })
+    };
+    let state = Arc::new(state);
+
+    let dragged = CaptureRegion {
+        x: -50,
+        y: 900,
+        width: 400,
+        height: 400,
+    };
+    handle_capture_region_update(&state, dragged).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let clamped = CaptureRegion {
+        x: 0,
+        y: 900,
+        width: 350,
+        height: 180,
+    };
+    assert_eq!(*saved.lock().unwrap(), [Some(clamped)]);
+    assert_eq!(*state.current_capture_region.read().await, Some(clamped));
+}
+
+#[tokio::test]
+async fn test_flush_writes_pending_save_once() {
+    let saved = Arc::new(Mutex::new(Vec::new()));
+    let saver = {
+        let saved = saved.clone();
+        ConfigSaver::with_sink(Duration::from_millis(50), move |config, _| {
+            saved.lock().unwrap().push(config.ocr.capture_region);
+            Ok(())
+        })
+    };
+    let config = Arc::new(RwLock::new(Config::default()));
+    let region = CaptureRegion {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+    apply_capture_region(&mut *config.write().await, region, None);
+
+    saver.schedule(config.clone(), "main");
+    saver.flush(&config, "main").await;
+    assert_eq!(*saved.lock().unwrap(), [Some(region)]);
+
+    // The delayed save finds nothing left to write
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(saved.lock().unwrap().len(), 1);
+}
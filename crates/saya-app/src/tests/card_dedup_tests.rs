@@ -0,0 +1,148 @@
+//! Tests for skipping Anki cards for words already in the deck
+
+use std::sync::Arc;
+
+use saya_anki::AnkiConnectClient;
+use saya_config::Config;
+use saya_config::anki::DuplicatePolicy;
+use saya_config::ui::ReadingDisplay;
+use saya_core::language::{LanguageProcessor, Token};
+use saya_lang_japanese::JapaneseProcessor;
+use saya_types::DisplayResult;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+
+use crate::capture::FakeCapturer;
+use crate::display::{DisplayOptions, to_display_result};
+use crate::events::create_card::handle_card_creation;
+use crate::state::AppState;
+use crate::tests::mock_http;
+
+/// Fields of the Basic note type
+const BASIC: &[&str] = &["Front", "Back"];
+
+/// AnkiConnect whose note type has `fields`, with a note per term in `deck`
+/// holding it in `term_field`, forwarding the `term_field` of added notes
+async fn mock_anki(
+    fields: &'static [&'static str],
+    term_field: &'static str,
+    deck: &[&str],
+) -> (String, mpsc::UnboundedReceiver<String>) {
+    let notes: Vec<Value> = deck
+        .iter()
+        .enumerate()
+        .map(|(id, term)| {
+            let values: serde_json::Map<String, Value> = fields
+                .iter()
+                .enumerate()
+                .map(|(order, field)| {
+                    let value = if *field == term_field { *term } else { "" };
+                    (field.to_string(), json!({ "value": value, "order": order }))
+                })
+                .collect();
+            json!({ "noteId": id, "fields": values })
+        })
+        .collect();
+    let (terms_tx, terms_rx) = mpsc::unbounded_channel();
+
+    let url = mock_http::mock_anki(move |request| match request["action"].as_str() {
+        Some("modelFieldNames") => json!(fields),
+        Some("findNotes") => json!((0..notes.len()).collect::<Vec<_>>()),
+        Some("notesInfo") => json!(notes),
+        Some("addNote") => {
+            let term = &request["params"]["note"]["fields"][term_field];
+            let _ = terms_tx.send(term.as_str().unwrap_or_default().to_string());
+            json!(1)
+        }
+        _ => Value::Null,
+    })
+    .await;
+
+    (url, terms_rx)
+}
+
+/// Top result for `word` as the overlay would show it
+fn looked_up(processor: &JapaneseProcessor, word: &str, options: &DisplayOptions) -> DisplayResult {
+    let token = Token {
+        surface: word.to_string(),
+        normalized: word.to_string(),
+        position: 0,
+    };
+    to_display_result(&processor.lookup(&token).remove(0), options)
+}
+
+/// Terms of the cards added for 食べた and 食べる to a deck holding `deck`
+async fn added_terms(
+    config: Config,
+    fields: &'static [&'static str],
+    term_field: &'static str,
+    deck: &[&str],
+) -> Vec<String> {
+    let options = DisplayOptions::from_config(&config);
+    let state = Arc::new(AppState::with_capturer(
+        config,
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let (tx, _rx) = kanal::unbounded_async();
+    let (url, mut terms) = mock_anki(fields, term_field, deck).await;
+    let client = AnkiConnectClient::new(url);
+    let processor = JapaneseProcessor::new();
+
+    for word in ["食べた", "食べる"] {
+        let result = looked_up(&processor, word, &options);
+        handle_card_creation(state.clone(), result, Some(&client), &tx)
+            .await
+            .unwrap();
+    }
+
+    let mut added = Vec::new();
+    while let Ok(term) = terms.try_recv() {
+        added.push(term);
+    }
+    added
+}
+
+async fn added_fronts(config: Config, deck: &[&str]) -> Vec<String> {
+    added_terms(config, BASIC, "Front", deck).await
+}
+
+fn skipping(by_base_form: bool) -> Config {
+    let mut config = Config::default();
+    config.anki.duplicate_policy = DuplicatePolicy::Skip;
+    config.anki.dedup_by_base_form = by_base_form;
+    config
+}
+
+#[tokio::test]
+async fn test_conjugations_collapse_to_one_card() {
+    assert_eq!(added_fronts(skipping(true), &[]).await, ["食べる\nたべる"]);
+}
+
+#[tokio::test]
+async fn test_surface_dedup_keeps_each_conjugation() {
+    assert_eq!(added_fronts(skipping(false), &[]).await.len(), 2);
+}
+
+#[tokio::test]
+async fn test_reading_first_finds_card_in_deck() {
+    let mut config = skipping(true);
+    config.ui.reading_display = ReadingDisplay::ReadingFirst;
+
+    // Fronts hold the reading, as cards added with this setting do
+    assert!(added_fronts(config, &["たべる\n食べる"]).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_card_in_non_front_field_is_found() {
+    let mut config = skipping(true);
+    config.anki.model = "Japanese Vocab".to_string();
+
+    // The term is in the second field, a first-field search would miss it
+    let fields = &["Meaning", "Expression"];
+    assert!(
+        added_terms(config, fields, "Expression", &["食べる\nたべる"])
+            .await
+            .is_empty()
+    );
+}
@@ -0,0 +1,90 @@
+//! Tests for filling the fields of custom Anki note types
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use saya_anki::AnkiConnectClient;
+use saya_config::Config;
+use saya_core::language::LookupResult;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+
+use crate::capture::FakeCapturer;
+use crate::display::{DisplayOptions, to_display_result};
+use crate::events::create_card::handle_card_creation;
+use crate::state::AppState;
+use crate::tests::mock_http;
+
+/// AnkiConnect whose note type has `fields`, forwarding the fields of added notes
+async fn mock_anki(fields: &'static [&'static str]) -> (String, mpsc::UnboundedReceiver<Value>) {
+    let (notes_tx, notes_rx) = mpsc::unbounded_channel();
+    let url = mock_http::mock_anki(move |request| match request["action"].as_str() {
+        Some("modelFieldNames") => json!(fields),
+        Some("addNote") => {
+            let _ = notes_tx.send(request["params"]["note"]["fields"].clone());
+            json!(1)
+        }
+        _ => Value::Null,
+    })
+    .await;
+
+    (url, notes_rx)
+}
+
+/// Fields of the note added for 猫 with `config`, on a note type with `fields`
+async fn added_fields(config: Config, fields: &'static [&'static str]) -> Value {
+    let lookup = LookupResult {
+        term: "猫".to_string(),
+        readings: vec!["ねこ".to_string()],
+        definitions: vec!["cat".to_string()],
+        metadata: HashMap::new(),
+    };
+    let result = to_display_result(&lookup, &DisplayOptions::from_config(&config));
+    let state = Arc::new(AppState::with_capturer(
+        config,
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let (tx, _rx) = kanal::unbounded_async();
+    let (url, mut notes) = mock_anki(fields).await;
+    let client = AnkiConnectClient::new(url);
+
+    handle_card_creation(state, result, Some(&client), &tx)
+        .await
+        .unwrap();
+    notes.recv().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_fields_detected_from_note_type() {
+    let fields = added_fields(
+        Config::default(),
+        &["Expression", "Reading", "Meaning", "Notes"],
+    )
+    .await;
+
+    assert_eq!(
+        fields,
+        json!({ "Expression": "猫", "Reading": "ねこ", "Meaning": "cat" })
+    );
+}
+
+#[tokio::test]
+async fn test_front_back_note_type_keeps_templates() {
+    let fields = added_fields(Config::default(), &["Front", "Back"]).await;
+
+    assert_eq!(fields, json!({ "Front": "猫\nねこ", "Back": "cat" }));
+}
+
+#[tokio::test]
+async fn test_configured_field_map_fills_custom_fields() {
+    let mut config = Config::default();
+    config.anki.field_map = Some(HashMap::from([
+        ("Word".to_string(), "{term}".to_string()),
+        ("Gloss".to_string(), "{definition} ({reading})".to_string()),
+    ]));
+
+    let fields = added_fields(config, &["Expression", "Reading"]).await;
+
+    assert_eq!(fields, json!({ "Word": "猫", "Gloss": "cat (ねこ)" }));
+}
@@ -0,0 +1,196 @@
+//! Tests for previewing Anki cards before they are created
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use saya_anki::AnkiConnectClient;
+use saya_config::Config;
+use saya_config::anki::TemplateOverride;
+use saya_config::ui::Badge;
+use saya_core::language::LookupResult;
+use saya_types::{AppEvent, DisplayResult, JlptLevel};
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+
+use crate::capture::FakeCapturer;
+use crate::display::{DisplayOptions, to_display_result};
+use crate::events::create_card::{handle_card_creation, handle_card_preview};
+use crate::state::AppState;
+use crate::tests::mock_http;
+
+/// AnkiConnect whose note type has `model_fields` and that accepts every note,
+/// forwarding the `addNote` requests it gets
+async fn mock_anki(
+    model_fields: &'static [&'static str],
+) -> (String, mpsc::UnboundedReceiver<Value>) {
+    let (notes_tx, notes_rx) = mpsc::unbounded_channel();
+    let url = mock_http::mock_anki(move |request| match request["action"].as_str() {
+        Some("modelFieldNames") => json!(model_fields),
+        Some("addNote") => {
+            let _ = notes_tx.send(request["params"]["note"].clone());
+            json!(1)
+        }
+        _ => json!(1),
+    })
+    .await;
+
+    (url, notes_rx)
+}
+
+fn result() -> DisplayResult {
+    let lookup = LookupResult {
+        term: "大<".to_string(),
+        readings: vec!["だい".to_string(), "おお".to_string()],
+        definitions: vec!["big & large".to_string(), "great".to_string()],
+        metadata: HashMap::new(),
+    };
+    to_display_result(&lookup, &DisplayOptions::from_config(&Config::default()))
+}
+
+/// Front and back previewed for `result()`, and the note then created for it
+async fn preview_and_note(
+    config: Config,
+    model_fields: &'static [&'static str],
+) -> ((String, String), Value) {
+    let state = Arc::new(AppState::with_capturer(
+        config,
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let (tx, rx) = kanal::unbounded_async();
+    let (url, mut notes) = mock_anki(model_fields).await;
+    let client = AnkiConnectClient::new(url);
+
+    handle_card_preview(state.clone(), result(), Some(&client), &tx)
+        .await
+        .unwrap();
+    let preview = match rx.try_recv().unwrap() {
+        Some(AppEvent::CardPreview { front, back }) => (front, back),
+        other => panic!("expected CardPreview, got {:?}", other.map(|e| e.name())),
+    };
+
+    handle_card_creation(state, result(), Some(&client), &tx)
+        .await
+        .unwrap();
+    (preview, notes.recv().await.unwrap())
+}
+
+#[tokio::test]
+async fn test_preview_matches_created_card() {
+    let ((front, back), note) = preview_and_note(Config::default(), &["Front", "Back"]).await;
+    assert_eq!(note["fields"]["Front"], front.as_str());
+    assert_eq!(note["fields"]["Back"], back.as_str());
+    assert_eq!(front, "大&lt;\nだい, おお");
+
+    // Mapped note types preview their term and definition fields
+    let mut config = Config::default();
+    config.anki.model = "Japanese Vocab".to_string();
+    let ((front, back), note) = preview_and_note(config, &["Expression", "Meaning"]).await;
+    assert_eq!(note["fields"]["Expression"], front.as_str());
+    assert_eq!(note["fields"]["Meaning"], back.as_str());
+    assert_eq!(front, "大&lt;");
+    assert_eq!(back, "big &amp; large; great");
+}
+
+#[tokio::test]
+async fn test_preview_does_not_contact_anki() {
+    let mut config = Config::default();
+    // Nothing listens here, and without a client the preview never asks it
+    config.anki.url = "http://127.0.0.1:9".to_string();
+    let state = Arc::new(AppState::with_capturer(
+        config,
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let (tx, rx) = kanal::unbounded_async();
+
+    handle_card_preview(state, result(), None, &tx)
+        .await
+        .unwrap();
+
+    assert!(matches!(rx.try_recv(), Ok(Some(AppEvent::CardPreview { .. }))));
+}
+
+#[tokio::test]
+async fn test_metadata_placeholders_use_shown_badges() {
+    let mut config = Config::default();
+    config.anki.back_template = "{pitch} {jlpt}".to_string();
+    let lookup = LookupResult {
+        term: "学校".to_string(),
+        readings: vec!["がっこう".to_string()],
+        definitions: vec!["school".to_string()],
+        metadata: HashMap::from([
+            ("pitch_accent".to_string(), "◎".to_string()),
+            ("jlpt_level".to_string(), "🟢 N5".to_string()),
+        ]),
+    };
+    let result = to_display_result(&lookup, &DisplayOptions::from_config(&config));
+    let state = Arc::new(AppState::with_capturer(
+        config,
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let (tx, rx) = kanal::unbounded_async();
+
+    handle_card_preview(state, result, None, &tx).await.unwrap();
+
+    match rx.try_recv().unwrap() {
+        Some(AppEvent::CardPreview { back, .. }) => assert_eq!(back, "◎ N5"),
+        other => panic!("expected CardPreview, got {:?}", other.map(|e| e.name())),
+    }
+}
+
+/// Front of the card previewed for a word at JLPT `level`
+async fn front_at_level(config: &Config, level: &str) -> String {
+    let lookup = LookupResult {
+        term: "猫".to_string(),
+        readings: vec!["ねこ".to_string()],
+        definitions: vec!["cat".to_string()],
+        metadata: HashMap::from([("jlpt_level".to_string(), format!("🟢 {}", level))]),
+    };
+    let result = to_display_result(&lookup, &DisplayOptions::from_config(config));
+    let state = Arc::new(AppState::with_capturer(
+        config.clone(),
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let (tx, rx) = kanal::unbounded_async();
+
+    handle_card_preview(state, result, None, &tx).await.unwrap();
+    match rx.try_recv().unwrap() {
+        Some(AppEvent::CardPreview { front, .. }) => front,
+        other => panic!("expected CardPreview, got {:?}", other.map(|e| e.name())),
+    }
+}
+
+#[tokio::test]
+async fn test_template_chosen_by_jlpt_level() {
+    let mut config = Config::default();
+    config.anki.template_overrides.insert(
+        JlptLevel::N5,
+        TemplateOverride {
+            front_template: "{definition}".to_string(),
+            back_template: "{term}\n{reading}".to_string(),
+            model: None,
+        },
+    );
+
+    assert_eq!(front_at_level(&config, "N5").await, "cat");
+    assert_eq!(front_at_level(&config, "N1").await, "猫\nねこ");
+}
+
+#[tokio::test]
+async fn test_template_chosen_with_jlpt_badge_hidden() {
+    let mut config = Config::default();
+    config.ui.show_badges = vec![Badge::Frequency];
+    config.anki.template_overrides.insert(
+        JlptLevel::N5,
+        TemplateOverride {
+            front_template: "{definition}".to_string(),
+            back_template: "{jlpt}".to_string(),
+            model: None,
+        },
+    );
+
+    assert_eq!(front_at_level(&config, "N5").await, "cat");
+}
@@ -0,0 +1,34 @@
+//! Tests for channel occupancy sampling
+
+use crate::controller::{ChannelSet, NEAR_CAPACITY_RATIO, sample_channel};
+
+#[test]
+fn test_empty_channel_is_not_near_capacity() {
+    let (tx, _rx) = kanal::bounded_async::<u32>(10);
+    assert!(!sample_channel("test", &tx));
+}
+
+#[tokio::test]
+async fn test_filling_channel_warns_near_capacity() {
+    let (tx, _rx) = kanal::bounded_async::<u32>(10);
+    let threshold = (10.0 * NEAR_CAPACITY_RATIO) as u32;
+
+    for i in 0..threshold - 1 {
+        tx.send(i).await.unwrap();
+    }
+    assert!(!sample_channel("test", &tx));
+
+    tx.send(threshold).await.unwrap();
+    assert!(sample_channel("test", &tx));
+}
+
+#[tokio::test]
+async fn test_app_to_ui_channel_warns_under_burst() {
+    let channels = ChannelSet::new();
+    let tx = &channels.app_to_ui.0;
+
+    while tx.try_send(saya_types::AppEvent::BackendReady).unwrap() {}
+
+    assert_eq!(tx.len(), tx.capacity());
+    assert!(sample_channel("app_to_ui", tx));
+}
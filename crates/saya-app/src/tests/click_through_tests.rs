@@ -0,0 +1,44 @@
+//! Tests for toggling overlay click-through
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use saya_config::Config;
+use saya_types::AppEvent;
+
+use crate::capture::FakeCapturer;
+use crate::events::toggle_click_through::handle_toggle_click_through;
+use crate::profile::ConfigSaver;
+use crate::state::AppState;
+
+#[tokio::test]
+async fn test_toggle_flips_setting_and_notifies_ui() {
+    let saved = Arc::new(Mutex::new(Vec::new()));
+    let mut state = AppState::with_capturer(Config::default(), "main", FakeCapturer::new(""));
+    state.config_saver = {
+        let saved = saved.clone();
+        ConfigSaver::with_sink(Duration::ZERO, move |config, _| {
+            saved.lock().unwrap().push(config.ui.click_through);
+            Ok(())
+        })
+    };
+    let state = Arc::new(state);
+    let (tx, rx) = kanal::unbounded_async();
+
+    assert!(handle_toggle_click_through(&state, &tx).await.unwrap());
+    assert!(state.config.read().await.ui.click_through);
+    assert!(matches!(
+        rx.try_recv(),
+        Ok(Some(AppEvent::ClickThroughChanged { enabled: true }))
+    ));
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(!handle_toggle_click_through(&state, &tx).await.unwrap());
+    assert!(matches!(
+        rx.try_recv(),
+        Ok(Some(AppEvent::ClickThroughChanged { enabled: false }))
+    ));
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(*saved.lock().unwrap(), [true, false]);
+}
@@ -0,0 +1,32 @@
+//! Tests for skipping clipboard text outside the target language
+
+use saya_config::clipboard::ClipboardConfig;
+
+use crate::io::accepts_clipboard_text;
+
+fn japanese_filter(require_target_script: bool) -> impl Fn(&str) -> bool {
+    let clipboard = ClipboardConfig {
+        require_target_script,
+        ..ClipboardConfig::default()
+    };
+    move |text| accepts_clipboard_text(&clipboard, "ja", text)
+}
+
+#[test]
+fn test_english_only_text_ignored() {
+    let accepts = japanese_filter(true);
+    assert!(!accepts("https://example.com/a?b=c"));
+    assert!(!accepts("let x = vec![1, 2];"));
+}
+
+#[test]
+fn test_japanese_and_mixed_text_pass() {
+    let accepts = japanese_filter(true);
+    assert!(accepts("食べました"));
+    assert!(accepts("Saya で日本語を読む"));
+}
+
+#[test]
+fn test_filter_off_keeps_everything() {
+    assert!(japanese_filter(false)("https://example.com"));
+}
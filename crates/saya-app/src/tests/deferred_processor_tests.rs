@@ -0,0 +1,65 @@
+//! Tests for building the language processor in the background
+
+use std::sync::Arc;
+
+use saya_config::Config;
+use saya_core::dictionary::DictionaryStatus;
+use saya_core::language::{LanguageProcessor, Token};
+use saya_lang_chinese::ChineseProcessor;
+use saya_types::AppEvent;
+
+use crate::languages::{DeferredProcessor, load_processor};
+
+fn token(text: &str) -> Token {
+    Token {
+        surface: text.to_string(),
+        normalized: text.to_string(),
+        position: 0,
+    }
+}
+
+#[test]
+fn test_lookups_before_ready_report_loading() {
+    let processor = DeferredProcessor::new("zh");
+
+    assert_eq!(processor.dictionary_status(), DictionaryStatus::Loading);
+    assert!(processor.lookup(&token("你好")).is_empty());
+    assert!(processor.tokenize("你好").is_empty());
+}
+
+#[test]
+fn test_lookups_after_ready_return_results() {
+    let processor = DeferredProcessor::new("zh");
+    processor.set(Ok(Arc::new(ChineseProcessor::new())));
+
+    assert!(processor.dictionary_status().is_loaded());
+    assert!(!processor.lookup(&token("你好")).is_empty());
+}
+
+#[test]
+fn test_failed_build_reports_reason() {
+    let processor = DeferredProcessor::new("xx");
+    processor.set(Err(anyhow::anyhow!("unsupported language 'xx'")));
+
+    match processor.dictionary_status() {
+        DictionaryStatus::Failed { reason } => assert!(reason.contains("xx")),
+        other => panic!("expected failed status, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_load_processor_announces_loading_and_ready() {
+    let mut config = Config::default();
+    config.ocr.language = "zh".to_string();
+    let processor = Arc::new(DeferredProcessor::new("zh"));
+    let (tx, rx) = kanal::unbounded_async();
+
+    let status = load_processor(processor.clone(), config, &tx).await;
+
+    assert!(status.is_loaded());
+    assert!(matches!(rx.try_recv(), Ok(Some(AppEvent::DictionaryLoading))));
+    match rx.try_recv() {
+        Ok(Some(AppEvent::DictionaryReady { count })) => assert!(count > 0),
+        other => panic!("expected ready event, got {:?}", other),
+    }
+}
@@ -0,0 +1,51 @@
+//! Tests for surfacing a dictionary that failed to load
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use saya_config::Config;
+use saya_core::dictionary::DictionaryStatus;
+use saya_types::AppEvent;
+
+use crate::capture::FakeCapturer;
+use crate::events::dictionary_status::report_dictionary_status;
+use crate::state::AppState;
+
+fn ready_state() -> Arc<AppState> {
+    let state = Arc::new(AppState::with_capturer(
+        Config::default(),
+        "main",
+        FakeCapturer::new(""),
+    ));
+    state.status.backend_ready.store(true, Ordering::SeqCst);
+    state
+}
+
+#[tokio::test]
+async fn test_failed_dictionary_is_reported() {
+    let (tx, rx) = kanal::unbounded_async();
+    let status = DictionaryStatus::Failed {
+        reason: "missing data".to_string(),
+    };
+
+    report_dictionary_status(ready_state(), tx, status);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    match rx.try_recv() {
+        Ok(Some(AppEvent::OcrStatusUpdate { status, .. })) => {
+            assert!(status.contains("missing data"), "{status}")
+        }
+        other => panic!("expected dictionary status, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_loaded_dictionary_is_silent() {
+    let (tx, rx) = kanal::unbounded_async();
+
+    report_dictionary_status(ready_state(), tx.clone(), DictionaryStatus::Loaded { count: 10 });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert!(matches!(rx.try_recv(), Ok(None)));
+}
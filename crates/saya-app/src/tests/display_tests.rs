@@ -0,0 +1,264 @@
+//! Tests for building display results from lookups
+
+use std::collections::HashMap;
+
+use saya_config::ui::{Badge, ReadingDisplay};
+use saya_core::dictionary::DictionaryEntry;
+use saya_core::language::{LanguageProcessor, LookupResult, Token};
+use saya_lang_japanese::{JMdictEntry, JapaneseProcessor};
+
+use crate::display::{DisplayOptions, cap_input, to_display_result};
+
+fn lookup(term: &str, readings: &[&str]) -> LookupResult {
+    LookupResult {
+        term: term.to_string(),
+        readings: readings.iter().map(|r| r.to_string()).collect(),
+        definitions: vec!["to eat".to_string()],
+        metadata: HashMap::new(),
+    }
+}
+
+fn show(mode: ReadingDisplay, term: &str, reading: &str) -> (String, String) {
+    let options = DisplayOptions {
+        reading_display: mode,
+        ..Default::default()
+    };
+    let result = to_display_result(&lookup(term, &[reading]), &options);
+    (result.term, result.readings.join(", "))
+}
+
+#[test]
+fn test_kanji_first_keeps_term() {
+    let (term, reading) = show(ReadingDisplay::KanjiFirst, "食べる", "たべる");
+    assert_eq!(term, "食べる");
+    assert_eq!(reading, "たべる");
+}
+
+#[test]
+fn test_reading_first_swaps_term_and_reading() {
+    let (term, reading) = show(ReadingDisplay::ReadingFirst, "食べる", "たべる");
+    assert_eq!(term, "たべる");
+    assert_eq!(reading, "食べる");
+}
+
+#[test]
+fn test_reading_only_hides_kanji() {
+    let (term, reading) = show(ReadingDisplay::ReadingOnly, "食べる", "たべる");
+    assert_eq!(term, "たべる");
+    assert_eq!(reading, "");
+}
+
+#[test]
+fn test_kana_only_word_unchanged() {
+    for mode in [
+        ReadingDisplay::KanjiFirst,
+        ReadingDisplay::ReadingFirst,
+        ReadingDisplay::ReadingOnly,
+    ] {
+        assert_eq!(show(mode, "これ", "これ"), ("これ".to_string(), "これ".to_string()));
+    }
+}
+
+#[test]
+fn test_cap_input_limits_chars() {
+    let text = "日本語".repeat(10);
+    assert_eq!(cap_input(&text, 4), "日本語日");
+    assert_eq!(cap_input(&text, 100), text);
+}
+
+#[test]
+fn test_structured_fields_are_kept() {
+    let mut entry = lookup("生", &["せい", "なま", "き"]);
+    entry.definitions = ["life", "raw", "pure", "draft beer"].map(String::from).to_vec();
+
+    let result = to_display_result(&entry, &DisplayOptions::default());
+    assert_eq!(result.readings, vec!["せい", "なま", "き"]);
+    assert_eq!(result.definitions.len(), 4);
+    assert!(result.truncated);
+
+    entry.definitions.truncate(2);
+    assert!(!to_display_result(&entry, &DisplayOptions::default()).truncated);
+}
+
+#[test]
+fn test_long_definition_cut_with_full_text_kept() {
+    let mut entry = lookup("生", &["せい"]);
+    let long = "a very long concatenated definition ".repeat(10);
+    entry.definitions = vec![long.clone()];
+    let options = DisplayOptions {
+        definition_max_chars: 40,
+        ..Default::default()
+    };
+
+    let result = to_display_result(&entry, &options);
+    assert!(result.truncated);
+    assert_eq!(result.definition.chars().count(), 40);
+    assert!(result.definition.ends_with('…'));
+    assert_eq!(result.full_definition, long);
+
+    let unlimited = DisplayOptions {
+        definition_max_chars: 0,
+        ..Default::default()
+    };
+    assert_eq!(to_display_result(&entry, &unlimited).definition, long);
+}
+
+#[test]
+fn test_wide_definition_cut_by_columns() {
+    let mut entry = lookup("日本語", &["にほんご"]);
+    entry.definitions = vec!["日本語".repeat(10)];
+    let options = DisplayOptions {
+        definition_max_chars: 10,
+        ..Default::default()
+    };
+
+    // Kanji take 2 columns each, the ellipsis 1
+    let result = to_display_result(&entry, &options);
+    assert!(result.truncated);
+    assert_eq!(result.definition, "日本語日…");
+}
+
+#[test]
+fn test_part_of_speech_reaches_display_result() {
+    let entry = JMdictEntry {
+        id: "1358280".to_string(),
+        kanji: vec!["食べる".to_string()],
+        readings: vec!["たべる".to_string()],
+        meanings: vec!["to eat".to_string(), "to live on".to_string()],
+        meaning_tags: vec![],
+        fields: vec![],
+        pos: vec!["v1".to_string(), "vt".to_string()],
+        jlpt_level: None,
+        frequency_rank: None,
+        common: false,
+        source: "JMdict".to_string(),
+    };
+
+    let result = to_display_result(&entry.to_lookup_result(), &DisplayOptions::default());
+    assert_eq!(result.part_of_speech.as_deref(), Some("v1, vt"));
+    assert_eq!(result.entry_id.as_deref(), Some("JMdict:1358280"));
+    assert_eq!(result.source.as_deref(), Some("JMdict"));
+}
+
+#[test]
+fn test_examples_reach_display_result() {
+    let mut entry = lookup("家族", &["かぞく"]);
+    entry.metadata.insert(
+        "examples".to_string(),
+        "家族と食べる。\tI eat with my family.\n家族が多い。\tI have a big family.".to_string(),
+    );
+
+    let result = to_display_result(&entry, &DisplayOptions::default());
+    assert_eq!(result.examples.len(), 2);
+    assert_eq!(result.examples[1].0, "家族が多い。");
+    assert_eq!(result.examples[1].1, "I have a big family.");
+}
+
+#[test]
+fn test_deconjugated_result_carries_confidence() {
+    let processor = JapaneseProcessor::new();
+    let first = |word: &str| {
+        let token = Token {
+            surface: word.to_string(),
+            normalized: word.to_string(),
+            position: 0,
+        };
+        let lookup = processor.lookup(&token).remove(0);
+        to_display_result(&lookup, &DisplayOptions::default())
+    };
+
+    let deconjugated = first("食べた");
+    assert!(deconjugated.conjugation.is_some());
+    let confidence = deconjugated.conjugation_confidence.unwrap();
+    assert!(confidence > 0.0 && confidence <= 1.0);
+
+    let direct = first("食べる");
+    assert_eq!(direct.conjugation, None);
+    assert_eq!(direct.conjugation_confidence, None);
+}
+
+#[test]
+fn test_disabled_badge_left_empty() {
+    let mut entry = lookup("食べる", &["たべる"]);
+    entry
+        .metadata
+        .insert("jlpt_level".to_string(), "🟢 N5".to_string());
+    entry
+        .metadata
+        .insert("frequency_stars".to_string(), "★★★".to_string());
+
+    let all = to_display_result(&entry, &DisplayOptions::default());
+    assert_eq!(all.jlpt_level.as_deref(), Some("🟢 N5"));
+
+    let options = DisplayOptions {
+        badges: vec![Badge::Frequency],
+        ..Default::default()
+    };
+    let result = to_display_result(&entry, &options);
+    assert_eq!(result.jlpt_level, None);
+    assert_eq!(result.frequency.as_deref(), Some("★★★"));
+}
+
+#[test]
+fn test_field_badge_from_metadata() {
+    let mut entry = lookup("鼠", &["ねずみ"]);
+    entry
+        .metadata
+        .insert("fields".to_string(), "comp".to_string());
+
+    let result = to_display_result(&entry, &DisplayOptions::default());
+    assert_eq!(result.fields.as_deref(), Some("comp"));
+}
+
+#[test]
+fn test_untyped_metadata_reaches_display_result() {
+    let mut entry = lookup("猫", &["ねこ"]);
+    entry
+        .metadata
+        .insert("mnemonic".to_string(), "cats knead".to_string());
+    entry
+        .metadata
+        .insert("pitch_accent".to_string(), "①".to_string());
+    entry
+        .metadata
+        .insert("strokes".to_string(), "11".to_string());
+    let options = DisplayOptions {
+        badges: vec![Badge::Pitch, Badge::Metadata("mnemonic".to_string())],
+        ..Default::default()
+    };
+
+    let result = to_display_result(&entry, &options);
+    assert_eq!(
+        result.metadata.get("mnemonic").map(String::as_str),
+        Some("cats knead")
+    );
+    // Shown through its own field, or not picked as a badge
+    assert!(!result.metadata.contains_key("pitch_accent"));
+    assert!(!result.metadata.contains_key("strokes"));
+    assert_eq!(result.pitch_accent.as_deref(), Some("①"));
+}
+
+#[test]
+fn test_metadata_badges_hidden_by_default() {
+    let mut entry = lookup("猫", &["ねこ"]);
+    entry
+        .metadata
+        .insert("mnemonic".to_string(), "cats knead".to_string());
+
+    let result = to_display_result(&entry, &DisplayOptions::default());
+    assert!(result.metadata.is_empty());
+}
+
+#[test]
+fn test_best_lookup_is_displayed() {
+    let processor = JapaneseProcessor::new();
+    let options = DisplayOptions::default();
+
+    let result = processor
+        .best_lookup("食べました", 0, |r| to_display_result(r, &options))
+        .unwrap();
+
+    assert_eq!(result.term, "食べる");
+    assert!(result.conjugation.is_some());
+    assert!(!result.definition.is_empty());
+}
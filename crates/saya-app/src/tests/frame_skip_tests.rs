@@ -0,0 +1,72 @@
+//! Tests for skipping auto OCR recognition of unchanged frames
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::Result;
+use saya_config::Config;
+use saya_lang_chinese::ChineseProcessor;
+use saya_ocr::RawImage;
+use saya_types::{CaptureMonitor, CaptureRegion};
+
+use crate::capture::Capturer;
+use crate::events::trigger_ocr::handle_ocr_trigger;
+use crate::ocr_context::OcrContext;
+use crate::state::AppState;
+
+const REGION: CaptureRegion = CaptureRegion {
+    x: 10,
+    y: 10,
+    width: 16,
+    height: 16,
+};
+
+/// Sees the same blank frame every time; the first recognition fails
+#[derive(Default)]
+struct FlakyCapturer {
+    recognized: AtomicUsize,
+}
+
+impl Capturer for FlakyCapturer {
+    fn capture_region(
+        &self,
+        region: CaptureRegion,
+        _monitor: &CaptureMonitor,
+        _monitor_index: usize,
+    ) -> Result<Vec<u8>> {
+        RawImage {
+            data: vec![255; region.width as usize * region.height as usize * 4],
+            width: region.width,
+            height: region.height,
+        }
+        .to_png()
+    }
+
+    fn capture_window(&self, _window_id: Option<u32>, _monitor_index: usize) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn recognize(&self, _image: &[u8], _language: &str) -> Result<String> {
+        match self.recognized.fetch_add(1, Ordering::SeqCst) {
+            0 => anyhow::bail!("engine not ready"),
+            _ => Ok("你好".to_string()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_frame_recorded_only_after_recognition_succeeds() {
+    let mut config = Config::default();
+    config.ocr.skip_unchanged_frames = true;
+    let capturer = Arc::new(FlakyCapturer::default());
+    let state = Arc::new(AppState::with_capturer(config, "main", capturer.clone()));
+    let (tx, _rx) = kanal::unbounded_async();
+    let ctx = OcrContext::new(state, tx, Arc::new(ChineseProcessor::new()), None);
+
+    // The failed recognition doesn't count, so the same frame is tried again
+    for _ in 0..3 {
+        handle_ocr_trigger(&ctx, REGION, true).await.unwrap();
+    }
+
+    assert_eq!(capturer.recognized.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,74 @@
+//! Tests for hiding already-known words from results
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use saya_config::Config;
+use saya_core::language::LookupResult;
+use saya_lang_chinese::ChineseProcessor;
+
+use crate::capture::FakeCapturer;
+use crate::display::{DisplayOptions, token_results};
+use crate::known_words::KnownWords;
+use crate::state::AppState;
+
+fn result(term: &str, base_form: Option<&str>) -> LookupResult {
+    let mut metadata = HashMap::new();
+    if let Some(base) = base_form {
+        metadata.insert("base_form".to_string(), base.to_string());
+    }
+    LookupResult {
+        term: term.to_string(),
+        readings: vec![],
+        definitions: vec![],
+        metadata,
+    }
+}
+
+fn options_knowing(words: &[&str]) -> DisplayOptions {
+    let known = KnownWords::new();
+    known.extend(words.iter().copied());
+    DisplayOptions::default().with_known_words(Arc::new(known))
+}
+
+#[test]
+fn test_known_word_is_hidden() {
+    let options = options_knowing(&["食べる"]);
+
+    assert!(options.is_hidden(&result("食べる", None)));
+    // Conjugated forms are matched through their base form
+    assert!(options.is_hidden(&result("食べた", Some("食べる"))));
+}
+
+#[test]
+fn test_unknown_word_passes() {
+    let options = options_knowing(&["食べる"]);
+
+    assert!(!options.is_hidden(&result("飲む", None)));
+}
+
+#[test]
+fn test_show_known_toggle_shows_known_words() {
+    let options = DisplayOptions {
+        show_known: true,
+        ..options_knowing(&["食べる"])
+    };
+
+    assert!(!options.is_hidden(&result("食べる", None)));
+}
+
+#[test]
+fn test_mark_known_filters_results_at_runtime() {
+    let state = AppState::with_capturer(Config::default(), "main", FakeCapturer::new(""));
+    let processor = ChineseProcessor::new();
+    let options = DisplayOptions::default().with_known_words(state.known_words.clone());
+
+    let before: usize = token_results(&processor, "你好", options.clone()).map(|b| b.len()).sum();
+    assert!(before > 0);
+
+    assert!(state.mark_known("你好"));
+    assert!(!state.mark_known("你好"));
+
+    let after: usize = token_results(&processor, "你好", options).map(|b| b.len()).sum();
+    assert_eq!(after, 0);
+}
@@ -0,0 +1,53 @@
+//! Tests for selecting a language processor by code
+
+use saya_config::Config;
+use saya_config::dictionary::DictionaryConfig;
+
+use crate::languages::{create_processor, processor_from_config};
+
+#[test]
+fn test_japanese_processor_is_registered() {
+    let processor = create_processor("ja", &DictionaryConfig::default()).unwrap();
+    assert_eq!(processor.language_code(), "ja");
+
+    let tokens = processor.tokenize(&processor.normalize("食べる"));
+    assert!(!tokens.is_empty());
+}
+
+#[test]
+fn test_chinese_processor_is_registered() {
+    let processor = create_processor("zh", &DictionaryConfig::default()).unwrap();
+    assert_eq!(processor.language_code(), "zh");
+
+    let tokens = processor.tokenize("你好");
+    assert_eq!(tokens.len(), 1);
+    assert!(!processor.lookup(&tokens[0]).is_empty());
+}
+
+#[test]
+fn test_unknown_language_is_rejected() {
+    let err = create_processor("xx", &DictionaryConfig::default()).err().unwrap();
+    let message = err.to_string();
+    assert!(message.contains("unsupported language 'xx'"));
+    assert!(message.contains("ja"));
+}
+
+#[test]
+fn test_processor_follows_ocr_language() {
+    let mut config = Config::default();
+    assert!(processor_from_config(&config).is_ok());
+
+    config.ocr.language = "xx".to_string();
+    assert!(processor_from_config(&config).is_err());
+}
+
+#[test]
+fn test_dictionary_language_overrides_ocr_language() {
+    let mut config = Config::default();
+    config.ocr.language = "ja".to_string();
+    config.dictionary.language = Some("zh".to_string());
+
+    assert_eq!(config.dictionary_language(), "zh");
+    let processor = processor_from_config(&config).unwrap();
+    assert_eq!(processor.language_code(), "zh");
+}
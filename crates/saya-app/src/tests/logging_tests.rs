@@ -0,0 +1,124 @@
+//! Tests for writing logs to rotating files
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use saya_config::logging::LoggingConfig;
+use tracing_subscriber::prelude::*;
+
+use crate::logging::{file_layer, log_panic};
+
+/// Log output collected in memory
+#[derive(Clone, Default)]
+struct Captured(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Fresh directory under the temp dir, not created yet
+fn temp_log_dir(name: &str) -> PathBuf {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+    std::env::temp_dir().join(format!("saya-logs-{}", nanos)).join(name)
+}
+
+fn logging_to(dir: &Path) -> LoggingConfig {
+    LoggingConfig {
+        file_enabled: true,
+        dir: Some(dir.to_string_lossy().to_string()),
+        level: "info".to_string(),
+    }
+}
+
+/// Contents of the single log file in `dir`
+fn log_file(dir: &Path) -> String {
+    let files: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(files.len(), 1);
+    let name = files[0].file_name().unwrap().to_string_lossy().to_string();
+    assert!(name.starts_with("saya") && name.ends_with(".log"), "{}", name);
+
+    std::fs::read_to_string(&files[0]).unwrap()
+}
+
+#[test]
+fn test_log_line_lands_in_created_directory() {
+    let dir = temp_log_dir("nested");
+
+    let (layer, mut log) = file_layer();
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, || {
+        log.apply(&logging_to(&dir));
+        tracing::info!("written to the log file");
+        tracing::debug!("below the file level");
+    });
+    // Flushes the background writer
+    drop(log);
+
+    let contents = log_file(&dir);
+    assert!(contents.contains("written to the log file"));
+    assert!(!contents.contains("below the file level"));
+
+    let _ = std::fs::remove_dir_all(dir.parent().unwrap());
+}
+
+#[test]
+fn test_profile_settings_apply_after_startup() {
+    let startup = temp_log_dir("startup");
+    let profile = startup.with_file_name("profile");
+
+    let (layer, mut log) = file_layer();
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, || {
+        log.apply(&logging_to(&startup));
+        tracing::warn!("while loading the profile");
+
+        log.apply(&LoggingConfig {
+            level: "debug".to_string(),
+            ..logging_to(&profile)
+        });
+        tracing::debug!("after loading the profile");
+    });
+    drop(log);
+
+    let before = log_file(&startup);
+    assert!(before.contains("while loading the profile"));
+    assert!(!before.contains("after loading the profile"));
+    assert!(log_file(&profile).contains("after loading the profile"));
+
+    let _ = std::fs::remove_dir_all(startup.parent().unwrap());
+}
+
+#[tokio::test]
+async fn test_task_panic_is_logged_by_hook() {
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_ansi(false)
+        .finish();
+    // The current-thread runtime polls the task here, under this subscriber
+    let _default = tracing::subscriber::set_default(subscriber);
+
+    // The hook is process-wide; put the previous one back before asserting
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(log_panic));
+    let result = tokio::spawn(async { panic!("dictionary exploded") }).await;
+    std::panic::set_hook(previous);
+
+    assert!(result.unwrap_err().is_panic());
+    let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("panic: dictionary exploded"), "{}", output);
+    assert!(output.contains("logging_tests.rs"), "{}", output);
+}
@@ -0,0 +1,49 @@
+//! Tests for fetching a full dictionary entry by id
+
+use saya_core::language::LanguageProcessor;
+use saya_lang_chinese::ChineseProcessor;
+use saya_types::AppEvent;
+
+use crate::display::DisplayOptions;
+use crate::events::lookup_by_id::handle_lookup_by_id;
+
+#[tokio::test]
+async fn test_lookup_by_id_returns_full_entry() {
+    let processor = ChineseProcessor::new();
+    let (tx, rx) = kanal::unbounded_async();
+
+    // Take the id from a regular lookup, as the UI would
+    let token = processor.tokenize("我").remove(0);
+    let id = processor.lookup(&token)[0].metadata["entry_id"].clone();
+
+    handle_lookup_by_id(id.clone(), &processor, &tx, DisplayOptions::default())
+        .await
+        .unwrap();
+
+    match rx.try_recv() {
+        Ok(Some(AppEvent::ShowResults(results))) => {
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].term, "我");
+            assert_eq!(results[0].readings, vec!["wo3"]);
+            assert_eq!(results[0].definitions, vec!["I", "me", "my"]);
+            assert!(!results[0].truncated);
+            assert_eq!(results[0].entry_id.as_deref(), Some(id.as_str()));
+        }
+        other => panic!("expected ShowResults, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_lookup_unknown_id_reports_not_found() {
+    let processor = ChineseProcessor::new();
+    let (tx, rx) = kanal::unbounded_async();
+
+    handle_lookup_by_id("missing".to_string(), &processor, &tx, DisplayOptions::default())
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        rx.try_recv(),
+        Ok(Some(AppEvent::OcrStatusUpdate { .. }))
+    ));
+}
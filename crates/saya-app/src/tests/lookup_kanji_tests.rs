@@ -0,0 +1,45 @@
+//! Tests for the kanji breakdown lookup
+
+use saya_lang_chinese::ChineseProcessor;
+use saya_lang_japanese::JapaneseProcessor;
+use saya_types::AppEvent;
+
+use crate::display::DisplayOptions;
+use crate::events::lookup_kanji::handle_lookup_kanji;
+
+#[tokio::test]
+async fn test_lookup_kanji_shows_breakdown() {
+    let processor = JapaneseProcessor::new();
+    let (tx, rx) = kanal::unbounded_async();
+
+    handle_lookup_kanji('語', &processor, &tx, DisplayOptions::default())
+        .await
+        .unwrap();
+
+    match rx.try_recv() {
+        Ok(Some(AppEvent::ShowResults(results))) => {
+            assert_eq!(results[0].term, "語");
+            assert_eq!(results[0].readings, vec!["ゴ", "かた.る", "かた.らう"]);
+            assert_eq!(
+                results[0].breakdown.as_deref(),
+                Some("14 strokes · Radicals: 言 五 口 · On: ゴ · Kun: かた.る かた.らう")
+            );
+        }
+        other => panic!("expected ShowResults, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_lookup_kanji_unsupported_language() {
+    let processor = ChineseProcessor::new();
+    let (tx, rx) = kanal::unbounded_async();
+
+    handle_lookup_kanji('语', &processor, &tx, DisplayOptions::default())
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        rx.try_recv(),
+        Ok(Some(AppEvent::OcrStatusUpdate { .. }))
+    ));
+}
@@ -0,0 +1,64 @@
+//! Local stand-in for AnkiConnect, answering JSON requests over HTTP
+
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+fn content_length(head: &str) -> usize {
+    head.lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Read one request, returning its body, or `None` if the connection closed first
+async fn read_body(stream: &mut TcpStream) -> Option<String> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = stream.read(&mut buf).await.ok()?;
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request);
+        if let Some((head, body)) = text.split_once("\r\n\r\n")
+            && body.len() >= content_length(head)
+        {
+            return Some(body.to_string());
+        }
+        if n == 0 {
+            return None;
+        }
+    }
+}
+
+/// Answer each AnkiConnect request on `listener` with the result `respond` gives for it
+pub fn serve_anki(
+    listener: TcpListener,
+    mut respond: impl FnMut(&Value) -> Value + Send + 'static,
+) {
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let Some(body) = read_body(&mut stream).await else {
+                continue;
+            };
+            let request = serde_json::from_str(&body).unwrap_or(Value::Null);
+
+            let body = json!({ "result": respond(&request), "error": null }).to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+}
+
+/// [`serve_anki`] on a free local port, returning its URL
+pub async fn mock_anki(respond: impl FnMut(&Value) -> Value + Send + 'static) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    serve_anki(listener, respond);
+    url
+}
@@ -1,4 +1,41 @@
 //! Unit tests for OCR and event flow
 
+pub mod anki_known_words_tests;
+pub mod anki_status_tests;
+pub mod auto_ocr_region_tests;
+pub mod auto_ocr_start_tests;
+pub mod backend_ready_tests;
+pub mod capture_preview_tests;
+pub mod capture_region_tests;
+pub mod card_dedup_tests;
+pub mod card_fields_tests;
+pub mod card_preview_tests;
+pub mod channel_monitor_tests;
+pub mod click_through_tests;
+pub mod clipboard_filter_tests;
+pub mod deferred_processor_tests;
+pub mod dictionary_status_tests;
+pub mod display_tests;
+pub mod frame_skip_tests;
+pub mod known_words_tests;
+pub mod languages_tests;
+pub mod logging_tests;
+pub mod lookup_by_id_tests;
+pub mod lookup_kanji_tests;
+pub mod mock_http;
 pub mod ocr_blocking_tests;
+pub mod ocr_generation_tests;
+pub mod ocr_permit_tests;
+pub mod pipeline_tests;
+pub mod profile_tests;
+pub mod result_streaming_tests;
+pub mod search_query_tests;
+pub mod selection_lookup_tests;
+pub mod send_latest_tests;
+pub mod setup_tests;
+pub mod status_server_tests;
+pub mod sort_tests;
 pub mod sync_channel_tests;
+pub mod translation_tests;
+pub mod unmatched_tests;
+pub mod watchers_paused_tests;
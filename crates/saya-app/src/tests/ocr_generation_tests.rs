@@ -0,0 +1,178 @@
+//! Tests for dropping stale results from overlapping OCR captures
+
+use std::sync::Arc;
+
+use kanal::AsyncReceiver;
+use saya_config::Config;
+use saya_lang_japanese::JapaneseProcessor;
+use saya_types::{AppEvent, ErrorKind};
+
+use crate::capture::FakeCapturer;
+use crate::events::ocr_result::handle_ocr_result;
+use crate::ocr_context::OcrContext;
+use crate::state::AppState;
+
+fn test_context() -> (OcrContext, AsyncReceiver<AppEvent>) {
+    let state = Arc::new(AppState::with_capturer(
+        Config::default(),
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let (tx, rx) = kanal::unbounded_async();
+    let ctx = OcrContext::new(state, tx, Arc::new(JapaneseProcessor::new()), None);
+    (ctx, rx)
+}
+
+fn drain_raw_texts(rx: &AsyncReceiver<AppEvent>) -> Vec<String> {
+    let mut texts = Vec::new();
+    while let Ok(Some(event)) = rx.try_recv() {
+        if let AppEvent::RawTextInput { text, .. } = event {
+            texts.push(text);
+        }
+    }
+    texts
+}
+
+#[tokio::test]
+async fn test_only_latest_capture_is_delivered() {
+    let (ctx, rx) = test_context();
+
+    // Two overlapping captures where the older one finishes last
+    let first = ctx.state.next_ocr_generation();
+    let second = ctx.state.next_ocr_generation();
+
+    handle_ocr_result(&ctx, Ok(Ok("新しい".to_string())), second, false)
+        .await
+        .unwrap();
+    handle_ocr_result(&ctx, Ok(Ok("古い".to_string())), first, false)
+        .await
+        .unwrap();
+
+    assert_eq!(drain_raw_texts(&rx), vec!["新しい".to_string()]);
+}
+
+#[tokio::test]
+async fn test_generation_counter_advances() {
+    let state = AppState::with_capturer(Config::default(), "main", FakeCapturer::new(""));
+
+    let first = state.next_ocr_generation();
+    assert!(state.is_current_ocr(first));
+
+    let second = state.next_ocr_generation();
+    assert!(!state.is_current_ocr(first));
+    assert!(state.is_current_ocr(second));
+}
+
+#[tokio::test]
+async fn test_ocr_failure_emits_typed_error() {
+    let (ctx, rx) = test_context();
+    let generation = ctx.state.next_ocr_generation();
+
+    handle_ocr_result(
+        &ctx,
+        Ok(Err(anyhow::anyhow!("engine crashed"))),
+        generation,
+        false,
+    )
+    .await
+    .unwrap();
+
+    let mut errors = Vec::new();
+    while let Ok(Some(event)) = rx.try_recv() {
+        if let AppEvent::Error { kind, message } = event {
+            errors.push((kind, message));
+        }
+    }
+    assert_eq!(
+        errors,
+        vec![(ErrorKind::OcrFailed, "engine crashed".to_string())]
+    );
+}
+
+#[tokio::test]
+async fn test_empty_region_reports_region_too_small() {
+    let (ctx, rx) = test_context();
+    let generation = ctx.state.next_ocr_generation();
+
+    handle_ocr_result(
+        &ctx,
+        Ok(Err(saya_ocr::OcrError::EmptyRegion.into())),
+        generation,
+        false,
+    )
+    .await
+    .unwrap();
+
+    let mut statuses = Vec::new();
+    let mut kinds = Vec::new();
+    while let Ok(Some(event)) = rx.try_recv() {
+        match event {
+            AppEvent::OcrStatusUpdate { status, .. } => statuses.push(status),
+            AppEvent::Error { kind, .. } => kinds.push(kind),
+            _ => {}
+        }
+    }
+    assert_eq!(statuses, vec!["Region too small".to_string()]);
+    assert_eq!(kinds, vec![ErrorKind::CaptureFailed]);
+}
+
+#[tokio::test]
+async fn test_short_ocr_result_dropped_as_noise() {
+    let (ctx, rx) = test_context();
+    ctx.state.config.write().await.ocr.min_text_length = 2;
+
+    let generation = ctx.state.next_ocr_generation();
+    handle_ocr_result(&ctx, Ok(Ok(" 一 ".to_string())), generation, false)
+        .await
+        .unwrap();
+
+    let mut statuses = Vec::new();
+    while let Ok(Some(event)) = rx.try_recv() {
+        match event {
+            AppEvent::OcrStatusUpdate { status, .. } => statuses.push(status),
+            AppEvent::RawTextInput { text, .. } => panic!("noise was shown: {:?}", text),
+            _ => {}
+        }
+    }
+    assert_eq!(statuses, vec!["No text found".to_string()]);
+
+    let generation = ctx.state.next_ocr_generation();
+    handle_ocr_result(&ctx, Ok(Ok("一二".to_string())), generation, false)
+        .await
+        .unwrap();
+    assert_eq!(drain_raw_texts(&rx), vec!["一二".to_string()]);
+}
+
+fn count_show_results(rx: &AsyncReceiver<AppEvent>) -> usize {
+    let mut count = 0;
+    while let Ok(Some(event)) = rx.try_recv() {
+        if matches!(event, AppEvent::ShowResults(_)) {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[tokio::test]
+async fn test_unchanged_auto_ocr_text_processed_once() {
+    let (ctx, rx) = test_context();
+
+    for _ in 0..2 {
+        let generation = ctx.state.next_ocr_generation();
+        handle_ocr_result(&ctx, Ok(Ok("食べる".to_string())), generation, true)
+            .await
+            .unwrap();
+    }
+    assert_eq!(count_show_results(&rx), 1);
+
+    // New text is shown, and a manual capture always is
+    let generation = ctx.state.next_ocr_generation();
+    handle_ocr_result(&ctx, Ok(Ok("飲む".to_string())), generation, true)
+        .await
+        .unwrap();
+    let generation = ctx.state.next_ocr_generation();
+    handle_ocr_result(&ctx, Ok(Ok("飲む".to_string())), generation, false)
+        .await
+        .unwrap();
+    assert_eq!(count_show_results(&rx), 2);
+}
@@ -0,0 +1,66 @@
+//! Tests for bounding concurrent OCR captures
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use saya_config::Config;
+use saya_lang_japanese::JapaneseProcessor;
+use saya_types::AppEvent;
+
+use crate::capture::FakeCapturer;
+use crate::events::capture_window::handle_window_capture;
+use crate::ocr_context::OcrContext;
+use crate::state::AppState;
+
+fn state_with_limit(max_concurrent: usize) -> AppState {
+    let mut config = Config::default();
+    config.ocr.max_concurrent = max_concurrent;
+    AppState::with_capturer(config, "main", FakeCapturer::new(""))
+}
+
+#[test]
+fn test_permits_bounded_by_config() {
+    let state = state_with_limit(2);
+
+    let first = state.try_acquire_ocr();
+    let second = state.try_acquire_ocr();
+    assert!(first.is_some());
+    assert!(second.is_some());
+    assert!(state.try_acquire_ocr().is_none());
+
+    drop(first);
+    assert!(state.try_acquire_ocr().is_some());
+}
+
+#[test]
+fn test_zero_limit_still_allows_one_capture() {
+    let state = state_with_limit(0);
+
+    let permit = state.try_acquire_ocr();
+    assert!(permit.is_some());
+    assert!(state.try_acquire_ocr().is_none());
+}
+
+#[tokio::test]
+async fn test_trigger_beyond_limit_reports_busy() {
+    let state = Arc::new(state_with_limit(1));
+    let (tx, rx) = kanal::unbounded_async();
+    let ctx = OcrContext::new(
+        state.clone(),
+        tx,
+        Arc::new(JapaneseProcessor::new()),
+        None,
+    );
+
+    // Occupy the only worker slot
+    let _held = state.try_acquire_ocr().unwrap();
+
+    handle_window_capture(&ctx, None).await.unwrap();
+
+    match rx.try_recv() {
+        Ok(Some(AppEvent::OcrStatusUpdate { status, .. })) => assert_eq!(status, "Busy"),
+        other => panic!("expected busy status, got {:?}", other),
+    }
+    // The running capture must not be invalidated by the dropped trigger
+    assert_eq!(state.ocr_generation.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,120 @@
+//! End-to-end tests of the event loop with a fake capturer
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use saya_config::Config;
+use saya_core::language::LanguageProcessor;
+use saya_lang_chinese::ChineseProcessor;
+use saya_types::{AppEvent, CaptureRegion, TextSource};
+use tokio::time::timeout;
+
+use crate::capture::FakeCapturer;
+use crate::controller::ChannelSet;
+use crate::events::event_loop;
+use crate::languages::processor_from_config;
+use crate::state::AppState;
+
+const REGION: CaptureRegion = CaptureRegion {
+    x: 40,
+    y: 60,
+    width: 320,
+    height: 120,
+};
+
+/// Event loop on real channels, returning the UI side of them
+fn spawn_loop(capturer: Arc<FakeCapturer>) -> ChannelSet {
+    let state = AppState::with_capturer(Config::default(), "main", capturer);
+    spawn_loop_with(state, Arc::new(ChineseProcessor::new()))
+}
+
+fn spawn_loop_with(state: AppState, processor: Arc<dyn LanguageProcessor>) -> ChannelSet {
+    let channels = ChannelSet::new();
+    tokio::spawn(event_loop(
+        Arc::new(state),
+        channels.ui_to_app.1.clone(),
+        channels.app_to_ui.clone(),
+        processor,
+        None,
+    ));
+    channels
+}
+
+/// Wait for the first `ShowResults`, collecting everything sent before it
+async fn wait_for_results(channels: &ChannelSet) -> (Vec<AppEvent>, Vec<String>) {
+    let mut before = Vec::new();
+    loop {
+        let event = timeout(Duration::from_secs(5), channels.app_to_ui.1.recv())
+            .await
+            .expect("no ShowResults from the event loop")
+            .unwrap();
+        match event {
+            AppEvent::ShowResults(results) => {
+                return (before, results.into_iter().map(|r| r.term).collect());
+            }
+            other => before.push(other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_trigger_ocr_shows_results() {
+    let capturer = FakeCapturer::new("你好朋友");
+    let channels = spawn_loop(capturer.clone());
+
+    channels
+        .ui_to_app
+        .0
+        .send(AppEvent::TriggerOcr(REGION))
+        .await
+        .unwrap();
+
+    let (before, terms) = wait_for_results(&channels).await;
+    assert!(terms.contains(&"你好".to_string()));
+    assert!(before.iter().any(|e| matches!(
+        e,
+        AppEvent::RawTextInput { text, source: TextSource::Ocr } if text == "你好朋友"
+    )));
+    assert_eq!(*capturer.regions.lock().unwrap(), vec![REGION]);
+}
+
+#[tokio::test]
+async fn test_window_capture_shows_results() {
+    let channels = spawn_loop(FakeCapturer::new("朋友"));
+
+    channels
+        .ui_to_app
+        .0
+        .send(AppEvent::CaptureWindow { window_id: None })
+        .await
+        .unwrap();
+
+    let (_, terms) = wait_for_results(&channels).await;
+    assert!(terms.contains(&"朋友".to_string()));
+}
+
+#[tokio::test]
+async fn test_ocr_and_dictionary_languages_are_separate() {
+    let capturer = FakeCapturer::new("你好");
+    let state = AppState::with_capturer(Config::default(), "main", capturer.clone());
+
+    // Recognize with the Japanese engine, look up in the Chinese dictionary
+    let processor = {
+        let mut config = state.config.write().await;
+        config.ocr.language = "ja".to_string();
+        config.dictionary.language = Some("zh".to_string());
+        processor_from_config(&config).unwrap()
+    };
+    let channels = spawn_loop_with(state, processor);
+
+    channels
+        .ui_to_app
+        .0
+        .send(AppEvent::TriggerOcr(REGION))
+        .await
+        .unwrap();
+
+    let (_, terms) = wait_for_results(&channels).await;
+    assert!(terms.contains(&"你好".to_string()));
+    assert_eq!(*capturer.languages.lock().unwrap(), vec!["ja".to_string()]);
+}
@@ -0,0 +1,80 @@
+//! Tests for checking a profile's settings on activation
+
+use saya_config::Config;
+use saya_ocr::MonitorInfo;
+use saya_types::CaptureRegion;
+
+use crate::capture::{Capturer, FakeCapturer};
+use crate::profile::{check_capture_region, update_config_field, validate_capture_region};
+
+fn monitor(index: usize, x: i32) -> MonitorInfo {
+    MonitorInfo {
+        index,
+        name: format!("Display {}", index),
+        x,
+        y: 0,
+        width: 1920,
+        height: 1080,
+        scale_factor: 1.0,
+        is_primary: index == 0,
+    }
+}
+
+fn config_with_region(x: i32) -> Config {
+    let mut config = Config::default();
+    config.ocr.capture_region = Some(CaptureRegion {
+        x,
+        y: 100,
+        width: 400,
+        height: 300,
+    });
+    config
+}
+
+#[test]
+fn test_region_on_removed_monitor_is_cleared() {
+    // Saved while a second monitor sat to the right
+    let mut config = config_with_region(2500);
+
+    assert!(validate_capture_region(&mut config, &[monitor(0, 0)]));
+    assert_eq!(config.ocr.capture_region, None);
+}
+
+#[test]
+fn test_region_on_connected_monitor_is_kept() {
+    let mut config = config_with_region(2500);
+    let monitors = [monitor(0, 0), monitor(1, 1920)];
+
+    assert!(!validate_capture_region(&mut config, &monitors));
+    assert!(config.ocr.capture_region.is_some());
+}
+
+#[test]
+fn test_region_kept_without_monitor_list() {
+    let mut config = config_with_region(2500);
+
+    assert!(!validate_capture_region(&mut config, &[]));
+    assert!(config.ocr.capture_region.is_some());
+}
+
+#[test]
+fn test_region_moved_off_screen_at_runtime_is_cleared() {
+    let capturer = FakeCapturer::new("");
+    *capturer.monitors.lock().unwrap() = vec![monitor(0, 0)];
+    let mut config = config_with_region(200);
+
+    update_config_field(&mut config, "ocr.capture_region.x", "2500").unwrap();
+    assert!(check_capture_region(&mut config, capturer.monitors()));
+    assert_eq!(config.ocr.capture_region, None);
+}
+
+#[test]
+fn test_region_kept_when_monitors_cant_be_listed() {
+    let mut config = config_with_region(2500);
+
+    assert!(!check_capture_region(
+        &mut config,
+        Err(anyhow::anyhow!("no display"))
+    ));
+    assert!(config.ocr.capture_region.is_some());
+}
@@ -0,0 +1,103 @@
+//! Tests for streaming lookup results to the UI in batches
+
+use std::time::{Duration, Instant};
+
+use saya_lang_chinese::ChineseProcessor;
+use saya_types::AppEvent;
+use saya_ui::state::OverlayView;
+
+use crate::display::{DisplayOptions, stream_results, token_results};
+use crate::events::text_input::handle_text_input;
+
+#[tokio::test]
+async fn test_results_arrive_in_multiple_events() {
+    let processor = ChineseProcessor::new();
+    let (tx, rx) = kanal::unbounded_async();
+
+    // 8 dictionary words, more than one batch
+    handle_text_input(
+        "我喜欢学习中文你好朋友今天明天".to_string(),
+        &processor,
+        &tx,
+        DisplayOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    let mut events = Vec::new();
+    while let Ok(Some(event)) = rx.try_recv() {
+        events.push(event);
+    }
+
+    assert!(matches!(events.first(), Some(AppEvent::ShowResults(_))));
+    assert!(events[1..].iter().all(|e| matches!(e, AppEvent::AppendResults(_))));
+    assert!(events.len() > 1);
+
+    let mut view = OverlayView::default();
+    let mut sizes = Vec::new();
+    for event in events {
+        assert!(view.apply(&event));
+        sizes.push(view.results.len());
+    }
+
+    assert!(sizes.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(view.results.len(), 8);
+    assert_eq!(view.results[0].term, "我");
+    assert_eq!(view.results[7].term, "明天");
+}
+
+#[tokio::test]
+async fn test_streaming_stops_when_superseded() {
+    let processor = ChineseProcessor::new();
+    let (tx, rx) = kanal::unbounded_async();
+
+    let batches = token_results(&processor, "我喜欢", DisplayOptions::default());
+    let sent = stream_results(batches, &tx, None, || false).await.unwrap();
+
+    assert_eq!(sent, 0);
+    assert!(rx.try_recv().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_huge_input_is_capped() {
+    let processor = ChineseProcessor::new();
+    let (tx, rx) = kanal::unbounded_async();
+    let options = DisplayOptions {
+        max_input_chars: 2,
+        ..Default::default()
+    };
+
+    // Only the first word is inside the cap
+    let text = format!("你好{}", "朋友".repeat(50_000));
+    let start = Instant::now();
+    handle_text_input(text, &processor, &tx, options)
+        .await
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    let mut view = OverlayView::default();
+    while let Ok(Some(event)) = rx.try_recv() {
+        view.apply(&event);
+    }
+    assert!(!view.results.is_empty());
+    assert!(view.results.iter().all(|result| result.term == "你好"));
+}
+
+#[tokio::test]
+async fn test_structured_fields_survive_pipeline() {
+    let processor = ChineseProcessor::new();
+    let (tx, rx) = kanal::unbounded_async();
+
+    handle_text_input("是".to_string(), &processor, &tx, DisplayOptions::default())
+        .await
+        .unwrap();
+
+    match rx.try_recv() {
+        Ok(Some(AppEvent::ShowResults(results))) => {
+            assert_eq!(results[0].readings, vec!["shi4"]);
+            assert_eq!(results[0].definitions, vec!["is", "are", "am", "yes", "to be"]);
+            assert!(results[0].truncated);
+        }
+        other => panic!("expected ShowResults, got {:?}", other),
+    }
+}
@@ -0,0 +1,129 @@
+//! Tests for cancelling stale search-as-you-type queries
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use saya_config::Config;
+use saya_core::dictionary::DictionaryStatus;
+use saya_core::language::{LanguageProcessor, LookupResult, Token};
+use saya_types::AppEvent;
+
+use crate::capture::FakeCapturer;
+use crate::display::DisplayOptions;
+use crate::events::search_query::handle_search_query;
+use crate::state::AppState;
+
+/// Finds one word per query, the term echoing the prefix, and records what it searched
+#[derive(Default)]
+struct EchoPrefix {
+    searched: Mutex<Vec<String>>,
+    /// Started as a newer query arrives, mid-search
+    overtaken_by: Option<Arc<AppState>>,
+}
+
+impl LanguageProcessor for EchoPrefix {
+    fn language_code(&self) -> &str {
+        "ja"
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    fn tokenize(&self, _text: &str) -> Vec<Token> {
+        vec![]
+    }
+
+    fn lookup(&self, _token: &Token) -> Vec<LookupResult> {
+        vec![]
+    }
+
+    fn search_prefix(&self, prefix: &str, _limit: usize) -> Vec<LookupResult> {
+        self.searched.lock().unwrap().push(prefix.to_string());
+        if let Some(state) = &self.overtaken_by {
+            state.next_search_generation();
+        }
+        vec![LookupResult {
+            term: prefix.to_string(),
+            readings: vec![],
+            definitions: vec!["match".to_string()],
+            metadata: HashMap::new(),
+        }]
+    }
+
+    fn dictionary_status(&self) -> DictionaryStatus {
+        DictionaryStatus::Loaded { count: 1 }
+    }
+}
+
+fn shown_terms(rx: &kanal::AsyncReceiver<AppEvent>) -> Vec<Vec<String>> {
+    let mut shown = Vec::new();
+    while let Ok(Some(event)) = rx.try_recv() {
+        if let AppEvent::ShowResults(results) = event {
+            shown.push(results.into_iter().map(|r| r.term).collect());
+        }
+    }
+    shown
+}
+
+#[tokio::test]
+async fn test_rapid_queries_deliver_only_latest() {
+    let state = Arc::new(AppState::with_capturer(
+        Config::default(),
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let processor = Arc::new(EchoPrefix::default());
+    let (tx, rx) = kanal::unbounded_async();
+
+    // Two keystrokes arrive before either search runs
+    let first = state.next_search_generation();
+    let second = state.next_search_generation();
+    for (generation, query) in [(first, "た"), (second, "たべ")] {
+        handle_search_query(
+            state.clone(),
+            generation,
+            query.to_string(),
+            processor.clone(),
+            tx.clone(),
+            DisplayOptions::default(),
+        )
+        .await
+        .unwrap();
+    }
+
+    assert_eq!(shown_terms(&rx), vec![vec!["たべ".to_string()]]);
+    assert_eq!(
+        *processor.searched.lock().unwrap(),
+        vec!["たべ".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_query_overtaken_mid_search_is_dropped() {
+    let state = Arc::new(AppState::with_capturer(
+        Config::default(),
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let processor = Arc::new(EchoPrefix {
+        overtaken_by: Some(state.clone()),
+        ..Default::default()
+    });
+    let (tx, rx) = kanal::unbounded_async();
+
+    let generation = state.next_search_generation();
+    handle_search_query(
+        state.clone(),
+        generation,
+        "た".to_string(),
+        processor.clone(),
+        tx,
+        DisplayOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    assert!(shown_terms(&rx).is_empty());
+    assert_eq!(*processor.searched.lock().unwrap(), vec!["た".to_string()]);
+}
@@ -0,0 +1,67 @@
+//! Tests for looking up a span selected in the overlay's raw text
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use saya_config::Config;
+use saya_lang_japanese::JapaneseProcessor;
+use saya_types::{AppEvent, DisplayResult};
+use tokio::time::timeout;
+
+use crate::capture::FakeCapturer;
+use crate::controller::ChannelSet;
+use crate::events::event_loop;
+use crate::state::AppState;
+
+/// Results the event loop shows for `text`, sent as the overlay sends a selection
+async fn looked_up(text: String) -> Vec<DisplayResult> {
+    let channels = ChannelSet::new();
+    tokio::spawn(event_loop(
+        Arc::new(AppState::with_capturer(
+            Config::default(),
+            "main",
+            FakeCapturer::new(""),
+        )),
+        channels.ui_to_app.1.clone(),
+        channels.app_to_ui.clone(),
+        Arc::new(JapaneseProcessor::new()),
+        None,
+    ));
+    channels
+        .ui_to_app
+        .0
+        .send(AppEvent::TextInput(text))
+        .await
+        .unwrap();
+
+    let mut results = Vec::new();
+    // The first batch can take a while, the rest follow right after it
+    let mut wait = Duration::from_secs(5);
+    while let Ok(Ok(event)) = timeout(wait, channels.app_to_ui.1.recv()).await {
+        match event {
+            AppEvent::ShowResults(batch) => results = batch,
+            AppEvent::AppendResults(batch) => results.extend(batch),
+            _ => continue,
+        }
+        wait = Duration::from_millis(200);
+    }
+    results
+}
+
+#[tokio::test]
+async fn test_selection_lookup_produces_results() {
+    // "食べた" is bytes 15..24, selected right to left
+    let raw = "昨日ご飯を食べた";
+    let selection = saya_ui::selected_text(raw, 24, 15).unwrap();
+    assert_eq!(selection, "食べた");
+
+    let results = looked_up(selection).await;
+
+    assert!(!results.is_empty());
+    assert!(
+        results
+            .iter()
+            .any(|r| r.term == "食べる" || r.base_form.as_deref() == Some("食べる"))
+    );
+    assert!(results.iter().all(|r| r.term != "昨日"));
+}
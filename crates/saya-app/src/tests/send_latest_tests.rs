@@ -0,0 +1,93 @@
+//! Tests for non-blocking result sends on a full UI channel
+
+use std::time::Duration;
+
+use saya_lang_chinese::ChineseProcessor;
+use saya_types::{AppEvent, ErrorKind, TextSource};
+use tokio::time::timeout;
+
+use crate::display::{DisplayOptions, send_latest, stream_results, token_results};
+
+fn raw(text: &str) -> AppEvent {
+    AppEvent::RawTextInput {
+        text: text.to_string(),
+        source: TextSource::Ocr,
+    }
+}
+
+fn queued_texts(rx: &kanal::AsyncReceiver<AppEvent>) -> Vec<String> {
+    let mut texts = Vec::new();
+    while let Ok(Some(event)) = rx.try_recv() {
+        match event {
+            AppEvent::RawTextInput { text, .. } => texts.push(text),
+            other => texts.push(other.name().to_string()),
+        }
+    }
+    texts
+}
+
+#[tokio::test]
+async fn test_newest_event_wins_on_full_channel() {
+    let (tx, rx) = kanal::bounded_async(2);
+    tx.send(AppEvent::ShowResults(vec![])).await.unwrap();
+    tx.send(AppEvent::AppendResults(vec![])).await.unwrap();
+
+    timeout(Duration::from_secs(1), send_latest(&tx, &rx, raw("new")))
+        .await
+        .expect("send_latest blocked on a full channel")
+        .unwrap();
+
+    assert_eq!(queued_texts(&rx), vec!["new"]);
+}
+
+#[tokio::test]
+async fn test_other_events_survive_dropped_results() {
+    let (tx, rx) = kanal::bounded_async(3);
+    tx.send(AppEvent::ShowResults(vec![])).await.unwrap();
+    tx.send(AppEvent::Error {
+        kind: ErrorKind::TranslationFailed,
+        message: "quota".to_string(),
+    })
+    .await
+    .unwrap();
+    tx.send(AppEvent::AppendResults(vec![])).await.unwrap();
+
+    timeout(
+        Duration::from_secs(1),
+        send_latest(&tx, &rx, AppEvent::ShowResults(vec![])),
+    )
+    .await
+    .expect("send_latest blocked on a full channel")
+    .unwrap();
+
+    assert_eq!(queued_texts(&rx), vec!["Error", "ShowResults"]);
+}
+
+#[tokio::test]
+async fn test_nothing_dropped_with_room() {
+    let (tx, rx) = kanal::bounded_async(4);
+    tx.send(raw("first")).await.unwrap();
+
+    send_latest(&tx, &rx, raw("second")).await.unwrap();
+
+    assert_eq!(queued_texts(&rx), vec!["first", "second"]);
+}
+
+#[tokio::test]
+async fn test_streamed_results_replace_stale_events() {
+    let processor = ChineseProcessor::new();
+    let (tx, rx) = kanal::bounded_async(1);
+    tx.send(AppEvent::ShowResults(vec![])).await.unwrap();
+
+    let batches = token_results(&processor, "你好", DisplayOptions::default());
+    let sent = timeout(
+        Duration::from_secs(1),
+        stream_results(batches, &tx, Some(&rx), || true),
+    )
+    .await
+    .expect("streaming blocked on a full channel")
+    .unwrap();
+
+    assert!(sent > 0);
+    assert_eq!(queued_texts(&rx), vec!["ShowResults"]);
+}
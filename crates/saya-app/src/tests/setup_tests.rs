@@ -0,0 +1,80 @@
+//! Tests for the first-run setup wizard
+
+use saya_config::Config;
+use saya_types::{AppEvent, SetupStatus, SetupStep};
+
+use crate::setup::{SetupWizard, check_step, run_wizard};
+
+#[test]
+fn test_wizard_visits_steps_in_order() {
+    let mut wizard = SetupWizard::new();
+    let mut visited = Vec::new();
+
+    while !wizard.is_complete() {
+        visited.push(wizard.current());
+        wizard.advance(SetupStatus::Passed);
+    }
+
+    assert_eq!(
+        visited,
+        vec![
+            SetupStep::OcrLanguage,
+            SetupStep::Clipboard,
+            SetupStep::TranslatorKey,
+            SetupStep::AnkiUrl,
+        ]
+    );
+}
+
+#[test]
+fn test_failed_step_still_advances() {
+    let mut wizard = SetupWizard::new();
+
+    let event = wizard.advance(SetupStatus::Failed("not installed".into()));
+
+    assert!(matches!(
+        event,
+        AppEvent::SetupStep {
+            step: SetupStep::OcrLanguage,
+            status: SetupStatus::Failed(_),
+        }
+    ));
+    assert_eq!(wizard.current(), SetupStep::Clipboard);
+}
+
+#[test]
+fn test_optional_steps_follow_config() {
+    let mut config = Config::default();
+    config.translator.enabled = true;
+    config.translator.api_key.clear();
+    config.anki.enabled = false;
+
+    assert!(matches!(
+        check_step(SetupStep::TranslatorKey, &config),
+        SetupStatus::NeedsInput(_)
+    ));
+    assert_eq!(check_step(SetupStep::AnkiUrl, &config), SetupStatus::Skipped);
+
+    config.translator.api_key = "key".into();
+    assert_eq!(check_step(SetupStep::TranslatorKey, &config), SetupStatus::Passed);
+}
+
+#[tokio::test]
+async fn test_run_wizard_marks_setup_complete() {
+    let mut config = Config::default();
+    config.setup_complete = false;
+    let (tx, rx) = kanal::unbounded_async();
+
+    run_wizard(&mut config, &tx, |_, _| SetupStatus::Passed)
+        .await
+        .unwrap();
+
+    assert!(config.setup_complete);
+
+    let mut steps = Vec::new();
+    while let Ok(Some(AppEvent::SetupStep { step, .. })) = rx.try_recv() {
+        steps.push(step);
+    }
+    assert_eq!(steps.len(), 5);
+    assert_eq!(steps.last(), Some(&SetupStep::Complete));
+}
@@ -0,0 +1,88 @@
+//! Tests for the configurable result sort order
+
+use std::collections::HashMap;
+
+use saya_config::dictionary::SortBy;
+use saya_core::language::LookupResult;
+
+use crate::display::{DisplayOptions, sort_results};
+
+fn result(term: &str, rank: Option<u32>, jlpt: Option<&str>) -> LookupResult {
+    let mut metadata = HashMap::new();
+    if let Some(rank) = rank {
+        metadata.insert("frequency_rank".to_string(), rank.to_string());
+    }
+    if let Some(badge) = jlpt {
+        metadata.insert("jlpt_level".to_string(), badge.to_string());
+    }
+    LookupResult {
+        term: term.to_string(),
+        readings: vec![],
+        definitions: vec![],
+        metadata,
+    }
+}
+
+/// Mixed set in tokenizer order: long rare match first, unranked last-but-one
+fn mixed() -> Vec<LookupResult> {
+    vec![
+        result("日本語学校", None, None),
+        result("学校", Some(300), Some("🟢 N5")),
+        result("語学", Some(5000), Some("🟠 N3")),
+        result("日本", Some(10), Some("🟢 N5")),
+        result("校", None, Some("🟣 N1")),
+    ]
+}
+
+fn sorted(sort_by: SortBy, jlpt_harder_first: bool) -> Vec<String> {
+    let options = DisplayOptions {
+        sort_by,
+        jlpt_harder_first,
+        ..Default::default()
+    };
+    let mut results = mixed();
+    sort_results(&mut results, &options);
+    results.into_iter().map(|r| r.term).collect()
+}
+
+#[test]
+fn test_sort_none_keeps_tokenizer_order() {
+    assert_eq!(
+        sorted(SortBy::None, false),
+        ["日本語学校", "学校", "語学", "日本", "校"]
+    );
+}
+
+#[test]
+fn test_sort_by_frequency_puts_common_first() {
+    // Unranked results keep their relative order at the end
+    assert_eq!(
+        sorted(SortBy::Frequency, false),
+        ["日本", "学校", "語学", "日本語学校", "校"]
+    );
+}
+
+#[test]
+fn test_sort_by_jlpt_easier_first() {
+    // Ties (two N5 words) keep tokenizer order
+    assert_eq!(
+        sorted(SortBy::Jlpt, false),
+        ["学校", "日本", "語学", "校", "日本語学校"]
+    );
+}
+
+#[test]
+fn test_sort_by_jlpt_harder_first() {
+    assert_eq!(
+        sorted(SortBy::Jlpt, true),
+        ["校", "語学", "学校", "日本", "日本語学校"]
+    );
+}
+
+#[test]
+fn test_sort_by_length_puts_longer_first() {
+    assert_eq!(
+        sorted(SortBy::Length, false),
+        ["日本語学校", "学校", "語学", "日本", "校"]
+    );
+}
@@ -0,0 +1,80 @@
+//! Tests for the optional HTTP status endpoint
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use saya_config::Config;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+
+use crate::capture::FakeCapturer;
+use crate::state::AppState;
+use crate::status::{StatusSnapshot, serve_status};
+
+#[tokio::test]
+async fn test_status_endpoint_returns_json() {
+    let state = Arc::new(AppState::with_capturer(
+        Config::default(),
+        "main",
+        FakeCapturer::new(""),
+    ));
+    state.status.backend_ready.store(true, Ordering::SeqCst);
+    state.status.ocr.write().await.record_capture();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let cancel = CancellationToken::new();
+    let server = tokio::spawn(serve_status(listener, state.clone(), cancel.clone()));
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(b"GET /status HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+
+    let mut response = String::new();
+    timeout(Duration::from_secs(2), stream.read_to_string(&mut response))
+        .await
+        .expect("status server did not respond")
+        .unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+    let body = response.split("\r\n\r\n").nth(1).unwrap();
+    let snapshot: StatusSnapshot = serde_json::from_str(body).unwrap();
+
+    assert!(snapshot.backend_ready);
+    assert_eq!(snapshot.profile, "main");
+    assert_eq!(snapshot.ocr_language, "ja");
+    assert_eq!(snapshot.capture_count, 1);
+    assert!(snapshot.last_capture_ms.is_some());
+
+    cancel.cancel();
+    server.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_status_endpoint_unknown_path() {
+    let state = Arc::new(AppState::with_capturer(
+        Config::default(),
+        "main",
+        FakeCapturer::new(""),
+    ));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let cancel = CancellationToken::new();
+    let server = tokio::spawn(serve_status(listener, state, cancel.clone()));
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(b"GET /other HTTP/1.1\r\n\r\n").await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+
+    cancel.cancel();
+    server.await.unwrap().unwrap();
+}
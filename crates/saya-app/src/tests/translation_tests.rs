@@ -0,0 +1,270 @@
+//! Tests for the rate-limited translation dispatcher and translation scopes
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use kanal::AsyncReceiver;
+use saya_config::Config;
+use saya_config::translator::TranslationScope;
+use saya_lang_japanese::JapaneseProcessor;
+use saya_translator::{LanguageCode, ProviderMetadata, TranslateError, Translation, Translator};
+use saya_types::AppEvent;
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+
+use crate::capture::FakeCapturer;
+use crate::events::ocr_result::handle_ocr_result;
+use crate::events::translate_text::handle_translate_text;
+use crate::ocr_context::OcrContext;
+use crate::state::AppState;
+use crate::translation::{STALE_REQUEST, TranslationDispatcher};
+
+/// Records when each text reached the provider
+#[derive(Default)]
+struct RecordingTranslator {
+    calls: Mutex<Vec<(String, Instant)>>,
+    detections: Mutex<Vec<String>>,
+    /// Returned with every translation
+    alternatives: Vec<String>,
+}
+
+#[async_trait::async_trait]
+impl Translator for RecordingTranslator {
+    async fn translate(
+        &self,
+        text: &str,
+        from: LanguageCode,
+        to: LanguageCode,
+    ) -> Result<Translation, TranslateError> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push((text.to_string(), Instant::now()));
+        Ok(Translation {
+            text: text.to_uppercase(),
+            from,
+            to,
+            provider: "test".to_string(),
+            confidence: None,
+            alternatives: self.alternatives.clone(),
+        })
+    }
+
+    async fn detect_language(&self, text: &str) -> Result<LanguageCode, TranslateError> {
+        self.detections.lock().unwrap().push(text.to_string());
+        // ASCII text is "English", anything else Japanese
+        if text.is_ascii() {
+            Ok("en".to_string())
+        } else {
+            Ok("ja".to_string())
+        }
+    }
+
+    fn supported_languages(&self) -> Vec<(LanguageCode, LanguageCode)> {
+        vec![]
+    }
+
+    fn metadata(&self) -> ProviderMetadata {
+        ProviderMetadata {
+            name: "test".to_string(),
+            requires_api_key: false,
+            free_tier_available: true,
+        }
+    }
+}
+
+fn dispatcher(min_interval_ms: u64) -> (TranslationDispatcher, Arc<RecordingTranslator>) {
+    let mut config = Config::default();
+    config.translator.min_interval_ms = min_interval_ms;
+    spawn_with(config)
+}
+
+fn spawn_with(config: Config) -> (TranslationDispatcher, Arc<RecordingTranslator>) {
+    let translator = Arc::new(RecordingTranslator::default());
+    let dispatcher =
+        TranslationDispatcher::spawn(translator.clone(), Arc::new(RwLock::new(config)));
+    (dispatcher, translator)
+}
+
+#[tokio::test]
+async fn test_rapid_requests_are_spaced_and_coalesced() {
+    let (dispatcher, translator) = dispatcher(50);
+
+    let (a1, a2, b, a3, c) = tokio::join!(
+        dispatcher.translate("a", "ja", "en"),
+        dispatcher.translate("a", "ja", "en"),
+        dispatcher.translate("b", "ja", "en"),
+        dispatcher.translate("a", "ja", "en"),
+        dispatcher.translate("c", "ja", "en"),
+    );
+
+    // Every caller gets its answer, duplicates share one provider call
+    for result in [&a1, &a2, &a3] {
+        assert_eq!(result.as_ref().unwrap().as_ref().unwrap().text, "A");
+    }
+    assert_eq!(b.unwrap().unwrap().text, "B");
+    assert_eq!(c.unwrap().unwrap().text, "C");
+
+    let calls = translator.calls.lock().unwrap();
+    let texts: Vec<&str> = calls.iter().map(|(text, _)| text.as_str()).collect();
+    assert_eq!(texts, ["a", "b", "c"]);
+
+    for pair in calls.windows(2) {
+        let gap = pair[1].1 - pair[0].1;
+        assert!(gap >= Duration::from_millis(50), "calls only {:?} apart", gap);
+    }
+}
+
+#[tokio::test]
+async fn test_language_pair_is_part_of_the_request() {
+    let (dispatcher, translator) = dispatcher(0);
+
+    let (en, de) = tokio::join!(
+        dispatcher.translate("a", "ja", "en"),
+        dispatcher.translate("a", "ja", "de"),
+    );
+
+    assert_eq!(en.unwrap().unwrap().to, "en");
+    assert_eq!(de.unwrap().unwrap().to, "de");
+    assert_eq!(translator.calls.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_stale_request_is_not_sent() {
+    let (dispatcher, translator) = dispatcher(0);
+
+    let (stale, current) = tokio::join!(
+        dispatcher.translate_if_current("a", "ja", "en", || false),
+        dispatcher.translate("b", "ja", "en"),
+    );
+
+    assert_eq!(stale.unwrap_err(), STALE_REQUEST);
+    assert_eq!(current.unwrap().unwrap().text, "B");
+    let calls = translator.calls.lock().unwrap();
+    let texts: Vec<&str> = calls.iter().map(|(text, _)| text.as_str()).collect();
+    assert_eq!(texts, ["b"]);
+}
+
+fn auto_detecting() -> (TranslationDispatcher, Arc<RecordingTranslator>) {
+    let mut config = Config::default();
+    config.translator.min_interval_ms = 0;
+    config.translator.auto_detect_source = true;
+    spawn_with(config)
+}
+
+#[tokio::test]
+async fn test_text_already_in_target_language_is_not_translated() {
+    let (dispatcher, translator) = auto_detecting();
+
+    let result = dispatcher.translate("hello", "ja", "en").await;
+
+    assert!(result.unwrap().is_none());
+    assert_eq!(*translator.detections.lock().unwrap(), ["hello"]);
+    assert!(translator.calls.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_detected_language_is_used_and_cached() {
+    let (dispatcher, translator) = auto_detecting();
+
+    let first = dispatcher.translate("日本", "zh", "en").await.unwrap().unwrap();
+    let second = dispatcher.translate("日本", "zh", "en").await.unwrap().unwrap();
+
+    assert_eq!(first.from, "ja");
+    assert_eq!(second.from, "ja");
+    assert_eq!(translator.detections.lock().unwrap().len(), 1);
+    assert_eq!(translator.calls.lock().unwrap().len(), 2);
+}
+
+const CAPTURED: &str = "メニュー\n今日はとても良い天気ですね。散歩しよう！";
+
+fn context_with(
+    scope: TranslationScope,
+    translator: Arc<RecordingTranslator>,
+) -> (OcrContext, AsyncReceiver<AppEvent>) {
+    let mut config = Config::default();
+    config.translator.min_interval_ms = 0;
+    config.translator.scope = scope;
+    let state = Arc::new(AppState::with_capturer(
+        config,
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let (tx, rx) = kanal::unbounded_async();
+    let ctx = OcrContext::new(
+        state,
+        tx,
+        Arc::new(JapaneseProcessor::new()),
+        Some(translator),
+    );
+    (ctx, rx)
+}
+
+fn scoped_context(scope: TranslationScope) -> (OcrContext, Arc<RecordingTranslator>) {
+    let translator = Arc::new(RecordingTranslator::default());
+    let (ctx, _rx) = context_with(scope, translator.clone());
+    (ctx, translator)
+}
+
+async fn translated_texts(scope: TranslationScope) -> Vec<String> {
+    let (ctx, translator) = scoped_context(scope);
+    let generation = ctx.state.next_ocr_generation();
+    handle_ocr_result(&ctx, Ok(Ok(CAPTURED.to_string())), generation, false)
+        .await
+        .unwrap();
+
+    let calls = translator.calls.lock().unwrap();
+    calls.iter().map(|(text, _)| text.clone()).collect()
+}
+
+#[tokio::test]
+async fn test_full_scope_sends_whole_capture() {
+    assert_eq!(translated_texts(TranslationScope::Full).await, [CAPTURED]);
+}
+
+#[tokio::test]
+async fn test_sentence_scope_sends_longest_sentence() {
+    assert_eq!(
+        translated_texts(TranslationScope::Sentence).await,
+        ["今日はとても良い天気ですね。"]
+    );
+}
+
+#[tokio::test]
+async fn test_off_scope_sends_nothing() {
+    assert!(translated_texts(TranslationScope::Off).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_requested_translation_ignores_scope() {
+    let (ctx, translator) = scoped_context(TranslationScope::Off);
+
+    handle_translate_text(&ctx, "天気".to_string())
+        .await
+        .unwrap();
+
+    let calls = translator.calls.lock().unwrap();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].0, "天気");
+}
+
+#[tokio::test]
+async fn test_provider_alternatives_reach_the_ui() {
+    let translator = Arc::new(RecordingTranslator {
+        alternatives: vec!["Nice weather".to_string()],
+        ..Default::default()
+    });
+    let (ctx, rx) = context_with(TranslationScope::Full, translator);
+
+    handle_translate_text(&ctx, "天気".to_string())
+        .await
+        .unwrap();
+
+    let mut shown = Vec::new();
+    while let Ok(Some(event)) = rx.try_recv() {
+        if let AppEvent::ShowTranslation { alternatives, .. } = event {
+            shown.push(alternatives);
+        }
+    }
+    assert_eq!(shown, [vec!["Nice weather".to_string()]]);
+}
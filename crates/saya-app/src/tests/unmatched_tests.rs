@@ -0,0 +1,112 @@
+//! Tests for the placeholder results shown when nothing matched
+
+use saya_core::dictionary::DictionaryStatus;
+use saya_core::language::{LanguageProcessor, LookupResult, Token};
+use saya_types::AppEvent;
+
+use crate::display::{DisplayOptions, UNMATCHED_DEFINITION, segment_unmatched};
+use crate::events::text_input::{NO_JAPANESE_STATUS, NO_MATCHES_STATUS, handle_text_input};
+
+/// Loaded processor whose dictionary knows no words
+struct NoMatches;
+
+impl LanguageProcessor for NoMatches {
+    fn language_code(&self) -> &str {
+        "ja"
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        text.chars()
+            .enumerate()
+            .map(|(position, ch)| Token {
+                surface: ch.to_string(),
+                normalized: ch.to_string(),
+                position,
+            })
+            .collect()
+    }
+
+    fn lookup(&self, _token: &Token) -> Vec<LookupResult> {
+        vec![]
+    }
+
+    fn dictionary_status(&self) -> DictionaryStatus {
+        DictionaryStatus::Loaded { count: 1 }
+    }
+}
+
+async fn events_for(text: &str, show_unmatched: bool) -> Vec<AppEvent> {
+    let (tx, rx) = kanal::unbounded_async();
+    let options = DisplayOptions {
+        show_unmatched,
+        ..Default::default()
+    };
+    handle_text_input(text.to_string(), &NoMatches, &tx, options)
+        .await
+        .unwrap();
+
+    let mut events = Vec::new();
+    while let Ok(Some(event)) = rx.try_recv() {
+        events.push(event);
+    }
+    events
+}
+
+#[test]
+fn test_segments_split_on_script_changes() {
+    assert_eq!(
+        segment_unmatched("田中さんはトウキョウへ、Saya2!"),
+        ["田中", "さんは", "トウキョウ", "へ", "Saya2"]
+    );
+}
+
+#[tokio::test]
+async fn test_unmatched_input_shows_segments_when_enabled() {
+    let events = events_for("佐藤さんとリンゴ", true).await;
+
+    let Some(AppEvent::ShowResults(results)) = events.first() else {
+        panic!("expected ShowResults, got {:?}", events);
+    };
+    let terms: Vec<&str> = results.iter().map(|r| r.term.as_str()).collect();
+    assert_eq!(terms, ["佐藤", "さんと", "リンゴ"]);
+    assert!(
+        results
+            .iter()
+            .all(|r| r.definitions == [UNMATCHED_DEFINITION])
+    );
+}
+
+#[tokio::test]
+async fn test_unmatched_input_shows_nothing_by_default() {
+    let events = events_for("佐藤さんとリンゴ", false).await;
+
+    assert!(!events.iter().any(|e| matches!(e, AppEvent::ShowResults(_))));
+}
+
+fn statuses(events: &[AppEvent]) -> Vec<&str> {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            AppEvent::OcrStatusUpdate { status, .. } => Some(status.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_english_input_reports_no_japanese() {
+    let events = events_for("Press START to continue", false).await;
+
+    assert_eq!(statuses(&events), [NO_JAPANESE_STATUS]);
+}
+
+#[tokio::test]
+async fn test_unknown_japanese_reports_no_matches() {
+    let events = events_for("佐藤さんとリンゴ", false).await;
+
+    assert_eq!(statuses(&events), [NO_MATCHES_STATUS]);
+}
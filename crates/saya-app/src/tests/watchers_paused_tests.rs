@@ -0,0 +1,153 @@
+//! Tests for pausing hotkeys, auto OCR, clipboard and websocket input
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use saya_config::Config;
+use saya_lang_japanese::JapaneseProcessor;
+use saya_types::{AppEvent, CaptureRegion, TextSource};
+
+use crate::capture::FakeCapturer;
+use crate::events::set_watchers_paused::handle_set_watchers_paused;
+use crate::events::trigger_auto_ocr::start_auto_ocr_loop;
+use crate::io::{HotkeyIds, forward_text};
+use crate::ocr_context::OcrContext;
+use crate::state::AppState;
+
+const HOTKEYS: HotkeyIds = HotkeyIds {
+    ocr: Some(1),
+    click_through: Some(2),
+    pause: Some(3),
+};
+
+async fn set_paused(state: &AppState, paused: bool) {
+    let (tx, _rx) = kanal::unbounded_async();
+    handle_set_watchers_paused(state, paused, &tx)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_pause_notifies_ui() {
+    let state = AppState::with_capturer(Config::default(), "main", FakeCapturer::new(""));
+    let (tx, rx) = kanal::unbounded_async();
+
+    handle_set_watchers_paused(&state, true, &tx).await.unwrap();
+    assert!(state.watchers_paused());
+    assert!(matches!(
+        rx.try_recv(),
+        Ok(Some(AppEvent::WatchersPausedChanged { paused: true }))
+    ));
+
+    handle_set_watchers_paused(&state, false, &tx)
+        .await
+        .unwrap();
+    assert!(!state.watchers_paused());
+}
+
+#[test]
+fn test_ocr_hotkey_ignored_while_paused() {
+    assert!(HOTKEYS.event_for(1, true).is_none());
+    assert!(matches!(
+        HOTKEYS.event_for(2, true),
+        Some(AppEvent::ToggleClickThrough)
+    ));
+    // The pause hotkey still works, so it can resume
+    assert!(matches!(
+        HOTKEYS.event_for(3, true),
+        Some(AppEvent::SetWatchersPaused(false))
+    ));
+
+    assert!(matches!(
+        HOTKEYS.event_for(1, false),
+        Some(AppEvent::HotkeyOcrTriggered)
+    ));
+    assert!(matches!(
+        HOTKEYS.event_for(3, false),
+        Some(AppEvent::SetWatchersPaused(true))
+    ));
+}
+
+#[test]
+fn test_pause_hotkey_works_without_ocr() {
+    let hotkeys = HotkeyIds {
+        ocr: None,
+        ..HOTKEYS
+    };
+
+    assert!(hotkeys.event_for(1, false).is_none());
+    assert!(matches!(
+        hotkeys.event_for(3, false),
+        Some(AppEvent::SetWatchersPaused(true))
+    ));
+}
+
+#[tokio::test]
+async fn test_watched_text_dropped_while_paused() {
+    let state = AppState::with_capturer(Config::default(), "main", FakeCapturer::new(""));
+    let (tx, rx) = kanal::unbounded_async();
+
+    for source in [TextSource::Clipboard, TextSource::Websocket] {
+        set_paused(&state, true).await;
+        assert!(!forward_text(
+            &state,
+            &tx,
+            "食べる".to_string(),
+            source.clone()
+        ));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(matches!(rx.try_recv(), Ok(None)));
+
+        set_paused(&state, false).await;
+        assert!(forward_text(&state, &tx, "食べる".to_string(), source));
+        let raw = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await;
+        assert!(matches!(raw, Ok(Ok(AppEvent::RawTextInput { .. }))));
+        let text = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await;
+        assert!(matches!(text, Ok(Ok(AppEvent::TextInput(t))) if t == "食べる"));
+    }
+}
+
+fn count_cycles(rx: &kanal::AsyncReceiver<AppEvent>) -> usize {
+    let mut cycles = 0;
+    while let Ok(Some(event)) = rx.try_recv() {
+        if matches!(event, AppEvent::OcrStatusUpdate { .. }) {
+            cycles += 1;
+        }
+    }
+    cycles
+}
+
+#[tokio::test]
+async fn test_auto_ocr_skips_cycles_while_paused() {
+    let mut config = Config::default();
+    config.ocr.auto = true;
+    config.ocr.max_concurrent = 1;
+    config.auto_ocr_interval_ms = 10;
+    let state = Arc::new(AppState::with_capturer(
+        config,
+        "main",
+        FakeCapturer::new(""),
+    ));
+    let (tx, rx) = kanal::unbounded_async();
+    let ctx = OcrContext::new(state.clone(), tx, Arc::new(JapaneseProcessor::new()), None);
+
+    // Occupy the worker slot so cycles report busy instead of capturing
+    let _held = state.try_acquire_ocr().unwrap();
+    set_paused(&state, true).await;
+
+    let region = CaptureRegion {
+        x: 10,
+        y: 10,
+        width: 100,
+        height: 100,
+    };
+    assert!(start_auto_ocr_loop(&ctx, region));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(count_cycles(&rx), 0);
+
+    set_paused(&state, false).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(count_cycles(&rx) > 0);
+
+    state.config.write().await.ocr.auto = false;
+}
@@ -0,0 +1,261 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use kanal::{AsyncReceiver, AsyncSender};
+use saya_config::Config;
+use saya_config::translator::{Formality, TranslationScope};
+use saya_lang_japanese::JapaneseTranslator;
+use saya_lang_japanese::sentence::split_sentences;
+use saya_translator::{Glossary, GlossaryTranslator, Translation, Translator};
+use tokio::sync::{RwLock, oneshot};
+use tokio::time::Instant;
+
+/// Build the configured translator, `None` when disabled or missing an API key
+pub fn translator_from_config(config: &Config) -> Option<Arc<dyn Translator>> {
+    let settings = &config.translator;
+    if !settings.enabled || settings.api_key.is_empty() {
+        return None;
+    }
+
+    let mut deepl = JapaneseTranslator::new(settings.api_key.clone(), settings.api_url.clone())
+        .with_timeout(config.request_timeout())
+        .with_formality_alternatives(settings.formality_alternatives);
+    if let Some(glossary_id) = &settings.glossary_id {
+        deepl = deepl.with_glossary_id(glossary_id.clone());
+    }
+    if settings.formality != Formality::Default {
+        deepl = deepl.with_formality(settings.formality.as_str());
+    }
+
+    let glossary = settings.glossary_path.as_deref().and_then(|path| {
+        Glossary::load_from_file(path)
+            .inspect_err(|e| tracing::warn!("Failed to load glossary from {}: {}", path, e))
+            .ok()
+    });
+    match glossary {
+        Some(glossary) => {
+            tracing::info!("Using translation glossary with {} terms", glossary.len());
+            Some(Arc::new(GlossaryTranslator::new(deepl, glossary)))
+        }
+        None => Some(Arc::new(deepl)),
+    }
+}
+
+/// The part of captured `text` that `scope` sends for translation, if any
+///
+/// [`TranslationScope::Sentence`] picks the longest sentence, the first one on a tie.
+pub fn text_to_translate(scope: TranslationScope, text: &str) -> Option<String> {
+    match scope {
+        TranslationScope::Full => Some(text.to_string()),
+        TranslationScope::Sentence => split_sentences(text)
+            .into_iter()
+            .rev()
+            .max_by_key(|sentence| sentence.chars().count()),
+        TranslationScope::Off => None,
+    }
+}
+
+/// Outcome shared by every caller of a coalesced request
+///
+/// `Ok(None)` means translation was skipped because the text is already in the target language.
+pub type TranslationResult = Result<Option<Translation>, String>;
+
+/// Detected languages remembered before the cache is reset
+const MAX_DETECTIONS: usize = 256;
+
+/// Error answered to a request that went stale before reaching the provider
+pub const STALE_REQUEST: &str = "request went stale before it was sent";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Request {
+    text: String,
+    from: String,
+    to: String,
+}
+
+struct Waiter {
+    reply: oneshot::Sender<TranslationResult>,
+    /// Whether the caller still wants the translation
+    is_current: Box<dyn Fn() -> bool + Send + Sync>,
+}
+
+struct Job {
+    request: Request,
+    waiter: Waiter,
+}
+
+/// Single background worker in front of the translation provider
+///
+/// Requests are spaced at least `translator.min_interval_ms` apart, and identical
+/// requests still waiting in the queue are sent once and answered together. With
+/// `translator.auto_detect_source` the source language is detected (and cached per
+/// text) first. Requests whose callers no longer want them by their turn are never
+/// sent.
+#[derive(Clone)]
+pub struct TranslationDispatcher {
+    tx: AsyncSender<Job>,
+}
+
+impl TranslationDispatcher {
+    pub fn spawn(translator: Arc<dyn Translator>, config: Arc<RwLock<Config>>) -> Self {
+        let (tx, rx) = kanal::unbounded_async();
+        tokio::spawn(run_worker(translator, config, rx));
+        Self { tx }
+    }
+
+    pub async fn translate(&self, text: &str, from: &str, to: &str) -> TranslationResult {
+        self.translate_if_current(text, from, to, || true).await
+    }
+
+    /// Like [`Self::translate`], skipping the provider if `is_current` is false by
+    /// the request's turn
+    pub async fn translate_if_current(
+        &self,
+        text: &str,
+        from: &str,
+        to: &str,
+        is_current: impl Fn() -> bool + Send + Sync + 'static,
+    ) -> TranslationResult {
+        let (reply, response) = oneshot::channel();
+        let request = Request {
+            text: text.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+        let waiter = Waiter {
+            reply,
+            is_current: Box::new(is_current),
+        };
+
+        self.tx
+            .send(Job { request, waiter })
+            .await
+            .map_err(|_| "translation worker stopped".to_string())?;
+        response
+            .await
+            .map_err(|_| "translation worker dropped the request".to_string())?
+    }
+}
+
+type Pending = VecDeque<(Request, Vec<Waiter>)>;
+
+fn enqueue(pending: &mut Pending, job: Job) {
+    match pending.iter_mut().find(|(request, _)| *request == job.request) {
+        Some((_, waiters)) => waiters.push(job.waiter),
+        None => pending.push_back((job.request, vec![job.waiter])),
+    }
+}
+
+/// Answer the waiters that went stale, keeping the rest
+fn drop_stale(waiters: Vec<Waiter>) -> Vec<Waiter> {
+    let (current, stale): (Vec<_>, Vec<_>) = waiters
+        .into_iter()
+        .partition(|waiter| (waiter.is_current)());
+    for waiter in stale {
+        let _ = waiter.reply.send(Err(STALE_REQUEST.to_string()));
+    }
+    current
+}
+
+async fn run_worker(
+    translator: Arc<dyn Translator>,
+    config: Arc<RwLock<Config>>,
+    rx: AsyncReceiver<Job>,
+) {
+    let mut pending = Pending::new();
+    let mut last_call: Option<Instant> = None;
+    let mut detections: HashMap<String, String> = HashMap::new();
+
+    loop {
+        if pending.is_empty() {
+            match rx.recv().await {
+                Ok(job) => enqueue(&mut pending, job),
+                Err(_) => return,
+            }
+        }
+
+        let (min_interval, auto_detect) = {
+            let config = config.read().await;
+            (
+                Duration::from_millis(config.translator.min_interval_ms),
+                config.translator.auto_detect_source,
+            )
+        };
+
+        // Respect the provider's quota, collecting requests that arrive meanwhile
+        let previous_call = last_call;
+        throttle(&mut last_call, min_interval).await;
+        while let Ok(Some(job)) = rx.try_recv() {
+            enqueue(&mut pending, job);
+        }
+
+        let Some((request, waiters)) = pending.pop_front() else {
+            continue;
+        };
+        let waiters = drop_stale(waiters);
+        if waiters.is_empty() {
+            tracing::debug!("Dropping stale translation request");
+            // Nothing was sent, so the slot is still free
+            last_call = previous_call;
+            continue;
+        }
+
+        let mut detected_now = false;
+        let from = if !auto_detect {
+            request.from.clone()
+        } else if let Some(detected) = detections.get(&request.text) {
+            detected.clone()
+        } else {
+            detected_now = true;
+            match translator.detect_language(&request.text).await {
+                Ok(detected) => {
+                    if detections.len() >= MAX_DETECTIONS {
+                        detections.clear();
+                    }
+                    detections.insert(request.text.clone(), detected.clone());
+                    detected
+                }
+                Err(e) => {
+                    tracing::debug!("Language detection failed, assuming {}: {}", request.from, e);
+                    request.from.clone()
+                }
+            }
+        };
+
+        let result = if same_language(&from, &request.to) {
+            tracing::debug!("Source already in {}, skipping translation", request.to);
+            Ok(None)
+        } else {
+            // Detection already used this slot
+            if detected_now {
+                throttle(&mut last_call, min_interval).await;
+            }
+            translator
+                .translate(&request.text, from, request.to.clone())
+                .await
+                .map(Some)
+                .map_err(|e| e.to_string())
+        };
+        if waiters.len() > 1 {
+            tracing::debug!("Coalesced {} identical translation requests", waiters.len());
+        }
+        for waiter in waiters {
+            let _ = waiter.reply.send(result.clone());
+        }
+    }
+}
+
+/// Wait until `min_interval` has passed since the last provider call, then claim the slot
+async fn throttle(last_call: &mut Option<Instant>, min_interval: Duration) {
+    if let Some(last) = *last_call {
+        tokio::time::sleep_until(last + min_interval).await;
+    }
+    *last_call = Some(Instant::now());
+}
+
+/// Compare primary language subtags, so "en" matches "EN-US"
+fn same_language(a: &str, b: &str) -> bool {
+    let primary = |code: &str| code.split('-').next().unwrap_or_default().to_ascii_lowercase();
+    primary(a) == primary(b)
+}
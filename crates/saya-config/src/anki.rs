@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use saya_types::types::JlptLevel;
 use serde::{Deserialize, Serialize};
 
 fn default_enabled() -> bool {
@@ -16,6 +19,51 @@ fn default_model() -> String {
     "Basic".to_string()
 }
 
+fn default_front_template() -> String {
+    "{term}\n{reading}".to_string()
+}
+
+fn default_back_template() -> String {
+    "{definition}".to_string()
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+fn default_cooldown_ms() -> u64 {
+    30000
+}
+
+fn default_dedup_by_base_form() -> bool {
+    true
+}
+
+fn default_health_check_interval_ms() -> u64 {
+    10000
+}
+
+/// What to do when a card for the word is already in the deck
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePolicy {
+    /// Send every card, leaving duplicate checks to AnkiConnect
+    #[default]
+    Allow,
+    /// Don't add a card for a word the deck already has
+    Skip,
+}
+
+/// Front and back used instead of the defaults for words of one JLPT level
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TemplateOverride {
+    pub front_template: String,
+    pub back_template: String,
+    /// Note type, `anki.model` when unset
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct AnkiConfig {
@@ -27,6 +75,39 @@ pub struct AnkiConfig {
     pub deck: String,
     #[serde(default = "default_model")]
     pub model: String,
+    /// Placeholders: {term}, {reading}, {definition}, {example},
+    /// {frequency}, {pitch}, {jlpt}, {conjugation}
+    #[serde(default = "default_front_template")]
+    pub front_template: String,
+    #[serde(default = "default_back_template")]
+    pub back_template: String,
+    /// Template for each field of the note type, for note types without Front
+    /// and Back fields. Detected from the field names when unset.
+    #[serde(default)]
+    pub field_map: Option<HashMap<String, String>>,
+    /// Templates by JLPT level, e.g. production cards for easy words
+    #[serde(default)]
+    pub template_overrides: HashMap<JlptLevel, TemplateOverride>,
+    /// Deck whose notes are added to the known words at startup
+    #[serde(default)]
+    pub known_words_deck: Option<String>,
+    /// Extra tags besides "saya". Placeholders: {jlpt}, {date}, {profile}
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Connection failures in a row before Anki is treated as closed
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// How long to skip requests to a closed Anki before trying again
+    #[serde(default = "default_cooldown_ms")]
+    pub cooldown_ms: u64,
+    #[serde(default)]
+    pub duplicate_policy: DuplicatePolicy,
+    /// Treat conjugations of one word as duplicates, keying on the dictionary form
+    #[serde(default = "default_dedup_by_base_form")]
+    pub dedup_by_base_form: bool,
+    /// How often to check whether Anki is running, for the status indicator; 0 never checks
+    #[serde(default = "default_health_check_interval_ms")]
+    pub health_check_interval_ms: u64,
 }
 
 impl Default for AnkiConfig {
@@ -36,6 +117,17 @@ impl Default for AnkiConfig {
             url: default_url(),
             deck: default_deck(),
             model: default_model(),
+            front_template: default_front_template(),
+            back_template: default_back_template(),
+            field_map: None,
+            template_overrides: HashMap::new(),
+            known_words_deck: None,
+            tags: Vec::new(),
+            failure_threshold: default_failure_threshold(),
+            cooldown_ms: default_cooldown_ms(),
+            duplicate_policy: DuplicatePolicy::default(),
+            dedup_by_base_form: default_dedup_by_base_form(),
+            health_check_interval_ms: default_health_check_interval_ms(),
         }
     }
 }
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+fn default_poll_interval_ms() -> u64 {
+    500
+}
+
+/// How the clipboard watcher notices new text
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipboardMode {
+    /// Change notifications where the OS has them (Windows), polling elsewhere
+    #[default]
+    Events,
+    /// Read the clipboard every `clipboard.poll_interval_ms`
+    Poll,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ClipboardConfig {
+    #[serde(default)]
+    pub mode: ClipboardMode,
+    /// How often the clipboard is read when it's polled
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Ignore clipboard text with no character in the OCR language's script
+    #[serde(default)]
+    pub require_target_script: bool,
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self {
+            mode: ClipboardMode::default(),
+            poll_interval_ms: default_poll_interval_ms(),
+            require_target_script: false,
+        }
+    }
+}
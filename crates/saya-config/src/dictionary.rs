@@ -4,20 +4,118 @@ fn default_enabled() -> bool {
     true
 }
 
+fn default_max_input_chars() -> usize {
+    2000
+}
+
+fn default_gloss_lang() -> String {
+    "eng".to_string()
+}
+
+fn default_cache() -> bool {
+    true
+}
+
+fn default_min_deconj_confidence() -> f32 {
+    0.7
+}
+
+/// Order results are shown in
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+    /// More common words first
+    Frequency,
+    /// By JLPT level, direction set by `jlpt_harder_first`
+    Jlpt,
+    /// Longer terms first
+    Length,
+    /// Tokenizer order
+    #[default]
+    None,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct DictionaryConfig {
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    /// Language of the processor and dictionary, `ocr.language` when unset
+    ///
+    /// Read at startup, so a change takes a restart.
+    #[serde(default)]
+    pub language: Option<String>,
     #[serde(default)]
     pub additional_paths: Vec<String>,
+    #[serde(default = "default_max_input_chars")]
+    pub max_input_chars: usize,
+    /// JMdict gloss language (ISO 639-2), English is used where it is missing
+    #[serde(default = "default_gloss_lang")]
+    pub gloss_lang: String,
+    /// Gloss languages in order of preference, used instead of `gloss_lang` when set
+    #[serde(default)]
+    pub gloss_langs: Vec<String>,
+    /// Tatoeba/Tanaka `examples.utf` file for example sentences
+    #[serde(default)]
+    pub examples_path: Option<String>,
+    /// KANJIDIC-style TSV replacing the built-in kanji data
+    #[serde(default)]
+    pub kanji_path: Option<String>,
+    #[serde(default)]
+    pub sort_by: SortBy,
+    /// With `sort_by = "jlpt"`, put N1 first instead of N5
+    #[serde(default)]
+    pub jlpt_harder_first: bool,
+    /// Newline-delimited words to hide from results
+    #[serde(default)]
+    pub known_words_path: Option<String>,
+    /// Show known words anyway
+    #[serde(default)]
+    pub show_known: bool,
+    /// When nothing matches, show the text split into words anyway
+    #[serde(default)]
+    pub show_unmatched: bool,
+    /// Deconjugation guesses below this are only used when nothing better matched
+    #[serde(default = "default_min_deconj_confidence")]
+    pub min_deconj_confidence: f32,
+    /// Keep built dictionaries in a binary cache to skip parsing JSON at launch
+    #[serde(default = "default_cache")]
+    pub cache: bool,
+    /// Leave archaic, obsolete and rare senses out of definitions
+    #[serde(default)]
+    pub hide_rare_senses: bool,
+}
+
+impl DictionaryConfig {
+    /// Gloss languages to try for each sense, most preferred first
+    pub fn gloss_chain(&self) -> Vec<&str> {
+        if self.gloss_langs.is_empty() {
+            vec![self.gloss_lang.as_str()]
+        } else {
+            self.gloss_langs.iter().map(String::as_str).collect()
+        }
+    }
 }
 
 impl Default for DictionaryConfig {
     fn default() -> Self {
         Self {
             enabled: default_enabled(),
+            language: None,
             additional_paths: vec![],
+            max_input_chars: default_max_input_chars(),
+            gloss_lang: default_gloss_lang(),
+            gloss_langs: vec![],
+            examples_path: None,
+            kanji_path: None,
+            sort_by: SortBy::default(),
+            jlpt_harder_first: false,
+            known_words_path: None,
+            show_known: false,
+            show_unmatched: false,
+            min_deconj_confidence: default_min_deconj_confidence(),
+            cache: default_cache(),
+            hide_rare_senses: false,
         }
     }
 }
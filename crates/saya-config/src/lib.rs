@@ -1,15 +1,23 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 use translator::TranslatorConfig;
 
 pub mod anki;
+pub mod clipboard;
 pub mod dictionary;
+pub mod logging;
 pub mod ocr;
+pub mod status;
 pub mod translator;
 pub mod ui;
 
 use self::anki::AnkiConfig;
+use self::clipboard::ClipboardConfig;
 use self::dictionary::DictionaryConfig;
+use self::logging::LoggingConfig;
 use self::ocr::OcrConfig;
+use self::status::StatusConfig;
 use self::ui::UiConfig;
 
 fn default_watchdog_timeout_ms() -> u64 {
@@ -36,6 +44,10 @@ fn default_ws_url() -> String {
     "ws://localhost:8080".to_string()
 }
 
+fn default_setup_complete() -> bool {
+    true
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct Config {
@@ -44,6 +56,9 @@ pub struct Config {
     pub anki: AnkiConfig,
     pub dictionary: DictionaryConfig,
     pub translator: TranslatorConfig,
+    pub status: StatusConfig,
+    pub logging: LoggingConfig,
+    pub clipboard: ClipboardConfig,
 
     #[serde(default = "default_watchdog_timeout_ms")]
     pub watchdog_timeout_ms: u64,
@@ -61,6 +76,9 @@ pub struct Config {
     pub ws_url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub delta_time: Option<u64>,
+    /// Cleared for a freshly created profile so the setup wizard runs once
+    #[serde(default = "default_setup_complete")]
+    pub setup_complete: bool,
 }
 
 impl Default for Config {
@@ -71,6 +89,9 @@ impl Default for Config {
             anki: AnkiConfig::default(),
             dictionary: DictionaryConfig::default(),
             translator: TranslatorConfig::default(),
+            status: StatusConfig::default(),
+            logging: LoggingConfig::default(),
+            clipboard: ClipboardConfig::default(),
             watchdog_timeout_ms: default_watchdog_timeout_ms(),
             hotkey_poll_interval_ms: default_hotkey_poll_interval_ms(),
             auto_ocr_interval_ms: default_auto_ocr_interval_ms(),
@@ -79,6 +100,22 @@ impl Default for Config {
             listen_to_ws: false,
             ws_url: default_ws_url(),
             delta_time: None,
+            setup_complete: default_setup_complete(),
         }
     }
 }
+
+impl Config {
+    /// Timeout for HTTP requests to AnkiConnect and translation providers
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_seconds.max(1) as u64)
+    }
+
+    /// Language lookups run in, `dictionary.language` falling back to `ocr.language`
+    pub fn dictionary_language(&self) -> &str {
+        self.dictionary
+            .language
+            .as_deref()
+            .unwrap_or(&self.ocr.language)
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+fn default_file_enabled() -> bool {
+    true
+}
+
+fn default_level() -> String {
+    "info".to_string()
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Also write logs to daily rotated files
+    #[serde(default = "default_file_enabled")]
+    pub file_enabled: bool,
+    /// Log directory, `logs` in the Saya roaming folder when unset
+    #[serde(default)]
+    pub dir: Option<String>,
+    /// Level written to the file: error, warn, info, debug or trace
+    #[serde(default = "default_level")]
+    pub level: String,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            file_enabled: default_file_enabled(),
+            dir: None,
+            level: default_level(),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use saya_types::types::CaptureRegion;
+use saya_types::types::{CaptureMonitor, CaptureRegion};
 use serde::{Deserialize, Serialize};
 
 fn default_enabled() -> bool {
@@ -13,6 +13,30 @@ fn default_language() -> String {
     "ja".to_string()
 }
 
+fn default_backend() -> String {
+    "windows".to_string()
+}
+
+fn default_tesseract_path() -> String {
+    "tesseract".to_string()
+}
+
+fn default_max_concurrent() -> usize {
+    2
+}
+
+fn default_min_text_length() -> usize {
+    1
+}
+
+fn default_skip_unchanged_frames() -> bool {
+    false
+}
+
+fn default_frame_change_threshold() -> u32 {
+    4
+}
+
 fn default_border_ready_color() -> String {
     "#00ff88".to_string()
 }
@@ -36,12 +60,36 @@ pub struct OcrConfig {
     pub language: String,
     pub capture_region: Option<CaptureRegion>,
     pub target_window: Option<String>,
+    /// Recognition engine, "windows" or "tesseract"
+    #[serde(default = "default_backend")]
+    pub backend: String,
+    /// Tesseract executable, for the "tesseract" backend
+    #[serde(default = "default_tesseract_path")]
+    pub tesseract_path: String,
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+    /// Recognized text shorter than this many characters (trimmed) counts as noise
+    #[serde(default = "default_min_text_length")]
+    pub min_text_length: usize,
+    /// Auto OCR skips recognition when the captured image barely changed.
+    /// Off by default: a single changed line of text can stay under the threshold.
+    #[serde(default = "default_skip_unchanged_frames")]
+    pub skip_unchanged_frames: bool,
+    /// Bits of the 64-bit image hash that may differ for a frame to count as unchanged
+    #[serde(default = "default_frame_change_threshold")]
+    pub frame_change_threshold: u32,
     #[serde(default = "default_border_ready_color")]
     pub border_ready_color: String,
     #[serde(default = "default_border_capturing_color")]
     pub border_capturing_color: String,
     #[serde(default = "default_border_preparing_color")]
     pub border_preparing_color: String,
+    /// Monitor for full-screen capture and regions spanning monitors
+    #[serde(default)]
+    pub monitor_index: usize,
+    /// "auto", or a monitor index or name that region capture always uses
+    #[serde(default)]
+    pub capture_monitor: CaptureMonitor,
 }
 
 impl Default for OcrConfig {
@@ -52,9 +100,42 @@ impl Default for OcrConfig {
             language: default_language(),
             capture_region: None,
             target_window: None,
+            backend: default_backend(),
+            tesseract_path: default_tesseract_path(),
+            max_concurrent: default_max_concurrent(),
+            min_text_length: default_min_text_length(),
+            skip_unchanged_frames: default_skip_unchanged_frames(),
+            frame_change_threshold: default_frame_change_threshold(),
             border_ready_color: default_border_ready_color(),
             border_capturing_color: default_border_capturing_color(),
             border_preparing_color: default_border_preparing_color(),
+            monitor_index: 0,
+            capture_monitor: CaptureMonitor::Auto,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_monitor_forms() {
+        let parse = |json: &str| {
+            serde_json::from_str::<OcrConfig>(json)
+                .unwrap()
+                .capture_monitor
+        };
+
+        assert_eq!(parse("{}"), CaptureMonitor::Auto);
+        assert_eq!(
+            parse(r#"{"capture_monitor": "auto"}"#),
+            CaptureMonitor::Auto
+        );
+        assert_eq!(parse(r#"{"capture_monitor": 1}"#), CaptureMonitor::Index(1));
+        assert_eq!(
+            parse(r#"{"capture_monitor": "DELL U2720Q"}"#),
+            CaptureMonitor::Name("DELL U2720Q".to_string())
+        );
+    }
+}
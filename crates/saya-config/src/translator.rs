@@ -20,6 +20,48 @@ fn default_api_url() -> String {
     "https://api-free.deepl.com/v2/translate".to_string()
 }
 
+fn default_min_interval_ms() -> u64 {
+    1000
+}
+
+/// How much of each OCR capture is sent for translation
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslationScope {
+    /// The whole captured text
+    #[default]
+    Full,
+    /// Only the longest sentence, skipping short UI labels around it
+    Sentence,
+    /// Nothing, results can still be translated on request
+    Off,
+}
+
+/// DeepL's `formality`, only sent for target languages that support it
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Formality {
+    /// Leave it to the provider
+    #[default]
+    Default,
+    More,
+    Less,
+    PreferMore,
+    PreferLess,
+}
+
+impl Formality {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Formality::Default => "default",
+            Formality::More => "more",
+            Formality::Less => "less",
+            Formality::PreferMore => "prefer_more",
+            Formality::PreferLess => "prefer_less",
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct TranslatorConfig {
@@ -35,6 +77,25 @@ pub struct TranslatorConfig {
     pub api_key: String,
     #[serde(default = "default_api_url")]
     pub api_url: String,
+    /// Minimum time between requests to the provider
+    #[serde(default = "default_min_interval_ms")]
+    pub min_interval_ms: u64,
+    /// `source\ttarget` lines forcing how terms such as names are translated
+    #[serde(default)]
+    pub glossary_path: Option<String>,
+    /// DeepL glossary to use server-side instead of, or as well as, the local one
+    #[serde(default)]
+    pub glossary_id: Option<String>,
+    /// Detect the source language instead of assuming `from_lang`
+    #[serde(default)]
+    pub auto_detect_source: bool,
+    #[serde(default)]
+    pub scope: TranslationScope,
+    /// Also fetch a formal rendering to show as an alternative, costing a second request
+    #[serde(default)]
+    pub formality_alternatives: bool,
+    #[serde(default)]
+    pub formality: Formality,
 }
 
 impl Default for TranslatorConfig {
@@ -46,6 +107,13 @@ impl Default for TranslatorConfig {
             to_lang: default_to_lang(),
             api_key: String::new(),
             api_url: default_api_url(),
+            min_interval_ms: default_min_interval_ms(),
+            glossary_path: None,
+            glossary_id: None,
+            auto_detect_source: false,
+            scope: TranslationScope::default(),
+            formality_alternatives: false,
+            formality: Formality::default(),
         }
     }
 }
@@ -4,17 +4,158 @@ fn default_max_text_lines() -> u32 {
     3
 }
 
+fn default_opacity() -> f32 {
+    0.94
+}
+
+fn default_font_size() -> f32 {
+    12.0
+}
+
+fn default_show_badges() -> Vec<Badge> {
+    Badge::ALL.to_vec()
+}
+
+fn default_definition_max_chars() -> usize {
+    200
+}
+
+/// Font sizes the overlay layout still works with
+const FONT_SIZE_RANGE: (f32, f32) = (6.0, 36.0);
+
+/// How headwords and readings are arranged in results
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingDisplay {
+    /// Kanji headword with the reading below
+    #[default]
+    KanjiFirst,
+    /// Reading as the headword with the kanji below
+    ReadingFirst,
+    /// Reading only, kanji hidden
+    ReadingOnly,
+}
+
+/// Metadata shown next to a result's headword
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Badge {
+    Frequency,
+    Pitch,
+    Jlpt,
+    Conjugation,
+    Pos,
+    /// Domain of the meanings, e.g. "med" or "comp"
+    Field,
+    /// Any other lookup metadata key, shown as a generic "key: value" badge
+    #[serde(untagged)]
+    Metadata(String),
+}
+
+impl Badge {
+    pub const ALL: [Badge; 6] = [
+        Badge::Frequency,
+        Badge::Pitch,
+        Badge::Jlpt,
+        Badge::Conjugation,
+        Badge::Pos,
+        Badge::Field,
+    ];
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct UiConfig {
     #[serde(default = "default_max_text_lines")]
     pub max_text_lines: u32,
+    #[serde(default)]
+    pub reading_display: ReadingDisplay,
+    /// Hide the overlay after this long without new text, 0 keeps it shown
+    #[serde(default)]
+    pub auto_hide_ms: u64,
+    /// Overlay background opacity, 0.0-1.0
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    /// Definition text size in pt; other text scales with it
+    #[serde(default = "default_font_size")]
+    pub font_size: f32,
+    /// Let clicks pass through the overlay to the game
+    #[serde(default)]
+    pub click_through: bool,
+    /// Badges shown on results, empty hides them all
+    #[serde(default = "default_show_badges")]
+    pub show_badges: Vec<Badge>,
+    /// Collapsed definitions are cut to this many columns (CJK counts as 2), 0 never cuts them
+    #[serde(default = "default_definition_max_chars")]
+    pub definition_max_chars: usize,
+    /// Keep the recognized text pinned above the results, for checking OCR against lookups
+    #[serde(default)]
+    pub show_raw_text: bool,
 }
 
 impl Default for UiConfig {
     fn default() -> Self {
         Self {
             max_text_lines: default_max_text_lines(),
+            reading_display: ReadingDisplay::default(),
+            auto_hide_ms: 0,
+            opacity: default_opacity(),
+            font_size: default_font_size(),
+            click_through: false,
+            show_badges: default_show_badges(),
+            definition_max_chars: default_definition_max_chars(),
+            show_raw_text: false,
         }
     }
 }
+
+impl UiConfig {
+    /// `opacity` clamped to 0.0-1.0
+    pub fn clamped_opacity(&self) -> f32 {
+        if self.opacity.is_nan() {
+            return default_opacity();
+        }
+        self.opacity.clamp(0.0, 1.0)
+    }
+
+    /// `font_size` clamped to a size the overlay can lay out
+    pub fn clamped_font_size(&self) -> f32 {
+        if self.font_size.is_nan() {
+            return default_font_size();
+        }
+        self.font_size.clamp(FONT_SIZE_RANGE.0, FONT_SIZE_RANGE.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_range_values_are_clamped() {
+        let ui: UiConfig = serde_json::from_str(r#"{"opacity": 1.5, "font_size": 200}"#).unwrap();
+        assert_eq!(ui.clamped_opacity(), 1.0);
+        assert_eq!(ui.clamped_font_size(), 36.0);
+
+        let ui: UiConfig = serde_json::from_str(r#"{"opacity": -0.2, "font_size": 2}"#).unwrap();
+        assert_eq!(ui.clamped_opacity(), 0.0);
+        assert_eq!(ui.clamped_font_size(), 6.0);
+    }
+
+    #[test]
+    fn test_unknown_badge_names_a_metadata_key() {
+        let ui: UiConfig =
+            serde_json::from_str(r#"{"show_badges": ["pitch", "mnemonic"]}"#).unwrap();
+        assert_eq!(
+            ui.show_badges,
+            [Badge::Pitch, Badge::Metadata("mnemonic".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_missing_values_use_defaults() {
+        let ui: UiConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(ui.clamped_opacity(), default_opacity());
+        assert_eq!(ui.clamped_font_size(), default_font_size());
+    }
+}
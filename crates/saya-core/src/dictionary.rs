@@ -11,7 +11,7 @@ pub trait Dictionary: Send + Sync {
     /// Search dictionary with options
     fn search(&self, query: &str, options: SearchOptions) -> Vec<Box<dyn DictionaryEntry>>;
 
-    /// Get entry by unique ID if supported
+    /// Get entry by its [`DictionaryEntry::qualified_id`] if supported
     fn get_by_id(&self, id: &str) -> Option<Box<dyn DictionaryEntry>>;
 
     /// Get dictionary metadata
@@ -32,16 +32,46 @@ pub trait DictionaryEntry: Send + Sync {
     /// All definitions
     fn definitions(&self) -> Vec<Definition>;
 
+    /// Name of the dictionary the entry came from, if tracked
+    fn source(&self) -> Option<String> {
+        None
+    }
+
+    /// ID unique across merged dictionaries, "source:id" when the source is tracked
+    fn qualified_id(&self) -> String {
+        match self.source() {
+            Some(source) => format!("{}:{}", source, self.id()),
+            None => self.id(),
+        }
+    }
+
     /// Language-specific data as JSON
     fn metadata(&self) -> serde_json::Value;
 
     /// Convert to generic lookup result
     fn to_lookup_result(&self) -> LookupResult {
+        let definitions = self.definitions();
+
+        let mut part_of_speech: Vec<String> = Vec::new();
+        for pos in definitions.iter().flat_map(|d| &d.part_of_speech) {
+            if !part_of_speech.contains(pos) {
+                part_of_speech.push(pos.clone());
+            }
+        }
+
+        let mut metadata = HashMap::from([("entry_id".to_string(), self.qualified_id())]);
+        if !part_of_speech.is_empty() {
+            metadata.insert("part_of_speech".to_string(), part_of_speech.join(", "));
+        }
+        if let Some(source) = self.source() {
+            metadata.insert("source".to_string(), source);
+        }
+
         LookupResult {
             term: self.headword(),
             readings: self.readings(),
-            definitions: self.definitions().iter().map(|d| d.text.clone()).collect(),
-            metadata: HashMap::new(),
+            definitions: definitions.into_iter().map(|d| d.text).collect(),
+            metadata,
         }
     }
 }
@@ -88,6 +118,40 @@ pub struct DictionaryMetadata {
     pub entry_count: usize,
 }
 
+/// Outcome of loading a processor's dictionary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DictionaryStatus {
+    /// Still being built in the background
+    Loading,
+    Loaded { count: usize },
+    Failed { reason: String },
+    Empty,
+}
+
+impl DictionaryStatus {
+    pub fn from_count(count: usize) -> Self {
+        if count == 0 {
+            DictionaryStatus::Empty
+        } else {
+            DictionaryStatus::Loaded { count }
+        }
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        matches!(self, DictionaryStatus::Loaded { .. })
+    }
+
+    /// Short user-facing description
+    pub fn message(&self) -> String {
+        match self {
+            DictionaryStatus::Loading => "Dictionary loading...".to_string(),
+            DictionaryStatus::Loaded { count } => format!("Dictionary loaded ({} entries)", count),
+            DictionaryStatus::Failed { reason } => format!("Dictionary failed to load: {}", reason),
+            DictionaryStatus::Empty => "Dictionary is empty".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Definition {
     pub text: String,
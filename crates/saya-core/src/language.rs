@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::dictionary::DictionaryStatus;
+
 /// Text processing and lookup interface for language implementations
 pub trait LanguageProcessor: Send + Sync {
     /// Language identifier (ISO 639-1 code: "ja", "zh", "ko", etc.)
@@ -13,6 +15,24 @@ pub trait LanguageProcessor: Send + Sync {
 
     /// Look up a token in the dictionary
     fn lookup(&self, token: &Token) -> Vec<LookupResult>;
+
+    /// Fetch a single dictionary entry by its ID, with every reading and meaning
+    fn lookup_by_id(&self, _id: &str) -> Option<LookupResult> {
+        None
+    }
+
+    /// Up to `limit` entries whose headword or reading starts with `prefix`
+    fn search_prefix(&self, _prefix: &str, _limit: usize) -> Vec<LookupResult> {
+        Vec::new()
+    }
+
+    /// Breakdown of a single character (kanji/hanzi), if the language has one
+    fn character_info(&self, _ch: char) -> Option<LookupResult> {
+        None
+    }
+
+    /// Whether the dictionary loaded, so an empty one isn't mistaken for no matches
+    fn dictionary_status(&self) -> DictionaryStatus;
 }
 
 /// Optional trait for languages with conjugation/declension
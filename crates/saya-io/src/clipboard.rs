@@ -1,25 +1,375 @@
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use arboard::Clipboard;
 use tokio::time;
 
-pub async fn watch_clipboard<F>(mut on_text: F) -> Result<(), anyhow::Error>
+/// Clipboard text access, so the copy/watch logic can run without a display
+pub trait ClipboardAccess {
+    fn read_text(&mut self) -> anyhow::Result<String>;
+    fn write_text(&mut self, text: &str) -> anyhow::Result<()>;
+}
+
+impl ClipboardAccess for Clipboard {
+    fn read_text(&mut self) -> anyhow::Result<String> {
+        Ok(self.get_text()?)
+    }
+
+    fn write_text(&mut self, text: &str) -> anyhow::Result<()> {
+        Ok(self.set_text(text)?)
+    }
+}
+
+/// Text saya wrote itself, skipped once by the watcher
+#[derive(Debug, Default)]
+pub struct SelfWrites {
+    pending: Mutex<Option<String>>,
+}
+
+impl SelfWrites {
+    pub fn mark(&self, text: &str) {
+        *self.pending.lock().unwrap() = Some(text.to_string());
+    }
+
+    /// Consume the mark, returning whether `text` is what we wrote
+    pub fn take_if_matches(&self, text: &str) -> bool {
+        self.pending.lock().unwrap().take().as_deref() == Some(text)
+    }
+}
+
+/// Change detection for polled clipboard contents
+pub struct ClipboardChanges {
+    last_text: String,
+    self_writes: Arc<SelfWrites>,
+}
+
+impl ClipboardChanges {
+    pub fn new(self_writes: Arc<SelfWrites>) -> Self {
+        Self {
+            last_text: String::new(),
+            self_writes,
+        }
+    }
+
+    /// Returns the text if it is new and was not written by us
+    pub fn observe(&mut self, text: String) -> Option<String> {
+        if text.is_empty() || text == self.last_text {
+            return None;
+        }
+        self.last_text = text.clone();
+
+        if self.self_writes.take_if_matches(&text) {
+            return None;
+        }
+        Some(text)
+    }
+}
+
+/// Write `text` to the clipboard without the watcher reporting it back
+pub fn copy_text(
+    clipboard: &mut impl ClipboardAccess,
+    text: &str,
+    self_writes: &SelfWrites,
+) -> anyhow::Result<()> {
+    self_writes.mark(text);
+    clipboard.write_text(text)
+}
+
+/// [`copy_text`] against the system clipboard
+pub fn copy_to_system(text: &str, self_writes: &SelfWrites) -> anyhow::Result<()> {
+    copy_text(&mut Clipboard::new()?, text, self_writes)
+}
+
+/// Check that the system clipboard can be opened
+pub fn check_access() -> anyhow::Result<()> {
+    Clipboard::new()?;
+    Ok(())
+}
+
+/// How [`watch_clipboard`] notices clipboard changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchMode {
+    /// Read the clipboard every poll interval
+    Poll,
+    /// Wait for change notifications, polling where the platform has none
+    Events,
+}
+
+/// Report clipboard text not written by saya to `on_text`
+///
+/// [`WatchMode::Events`] is only event-driven on Windows; elsewhere, or if the
+/// listener can't be set up, the clipboard is polled every `poll_interval`.
+pub async fn watch_clipboard<F>(
+    self_writes: Arc<SelfWrites>,
+    mode: WatchMode,
+    poll_interval: Duration,
+    mut on_text: F,
+) -> Result<(), anyhow::Error>
 where
     F: FnMut(String) + Send + 'static,
 {
     let mut clipboard = Clipboard::new()?;
-    let mut last_text = String::new();
+    let mut changes = ClipboardChanges::new(self_writes);
 
-    let mut interval = time::interval(Duration::from_millis(500));
+    if mode == WatchMode::Events {
+        #[cfg(windows)]
+        match listener::spawn() {
+            Ok(updates) => {
+                return watch_updates(&mut clipboard, &mut changes, updates, on_text).await;
+            }
+            Err(e) => tracing::warn!("Clipboard listener unavailable, polling instead: {}", e),
+        }
+        #[cfg(not(windows))]
+        tracing::debug!("No clipboard change events on this platform, polling instead");
+    }
+
+    poll_clipboard(&mut clipboard, &mut changes, poll_interval, &mut on_text).await;
+    Ok(())
+}
+
+/// Read `clipboard` every `interval`, reporting changes to `on_text`
+///
+/// Never returns; stop it by dropping the future.
+pub async fn poll_clipboard(
+    clipboard: &mut impl ClipboardAccess,
+    changes: &mut ClipboardChanges,
+    interval: Duration,
+    on_text: &mut impl FnMut(String),
+) {
+    let mut interval = time::interval(interval.max(Duration::from_millis(1)));
 
     loop {
         interval.tick().await;
-        if let Ok(text) = clipboard.get_text()
-            && !text.is_empty()
-            && text != last_text
+        if let Ok(text) = clipboard.read_text()
+            && let Some(text) = changes.observe(text)
         {
-            last_text = text.clone();
             on_text(text);
         }
     }
 }
+
+/// Read `clipboard` once per change notification, and once up front
+#[cfg(windows)]
+async fn watch_updates(
+    clipboard: &mut impl ClipboardAccess,
+    changes: &mut ClipboardChanges,
+    mut updates: tokio::sync::mpsc::UnboundedReceiver<()>,
+    mut on_text: impl FnMut(String),
+) -> anyhow::Result<()> {
+    loop {
+        if let Ok(text) = clipboard.read_text()
+            && let Some(text) = changes.observe(text)
+        {
+            on_text(text);
+        }
+        if updates.recv().await.is_none() {
+            anyhow::bail!("Clipboard listener stopped");
+        }
+    }
+}
+
+/// `WM_CLIPBOARDUPDATE` notifications from `AddClipboardFormatListener`
+///
+/// The listener is a message-only window on its own thread, since it needs a
+/// message loop. It shuts down once the receiver is dropped and the next
+/// notification arrives.
+#[cfg(windows)]
+mod listener {
+    use std::cell::RefCell;
+
+    use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+    use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::System::DataExchange::{
+        AddClipboardFormatListener, RemoveClipboardFormatListener,
+    };
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetMessageW,
+        HWND_MESSAGE, MSG, PostQuitMessage, RegisterClassW, WINDOW_EX_STYLE, WINDOW_STYLE,
+        WM_CLIPBOARDUPDATE, WNDCLASSW,
+    };
+    use windows::core::w;
+
+    thread_local! {
+        static UPDATES: RefCell<Option<UnboundedSender<()>>> = const { RefCell::new(None) };
+    }
+
+    unsafe extern "system" fn wndproc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        if msg == WM_CLIPBOARDUPDATE {
+            let sent = UPDATES.with_borrow(|tx| tx.as_ref().is_some_and(|tx| tx.send(()).is_ok()));
+            if !sent {
+                unsafe { PostQuitMessage(0) };
+            }
+            return LRESULT(0);
+        }
+        unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) }
+    }
+
+    /// Create the listener window, returning once it is subscribed
+    pub fn spawn() -> anyhow::Result<UnboundedReceiver<()>> {
+        let (tx, rx) = unbounded_channel();
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            let hwnd = match unsafe { create_window() } {
+                Ok(hwnd) => hwnd,
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+            UPDATES.with_borrow_mut(|updates| *updates = Some(tx));
+            let _ = ready_tx.send(Ok(()));
+
+            let mut msg = MSG::default();
+            unsafe {
+                while GetMessageW(&mut msg, None, 0, 0).0 > 0 {
+                    DispatchMessageW(&msg);
+                }
+                let _ = RemoveClipboardFormatListener(hwnd);
+                let _ = DestroyWindow(hwnd);
+            }
+        });
+
+        ready_rx.recv()??;
+        Ok(rx)
+    }
+
+    unsafe fn create_window() -> anyhow::Result<HWND> {
+        unsafe {
+            let instance = GetModuleHandleW(None)?.into();
+            let class = WNDCLASSW {
+                lpfnWndProc: Some(wndproc),
+                hInstance: instance,
+                lpszClassName: w!("SayaClipboardListener"),
+                ..Default::default()
+            };
+            // Fails harmlessly if an earlier listener already registered it
+            RegisterClassW(&class);
+
+            let hwnd = CreateWindowExW(
+                WINDOW_EX_STYLE(0),
+                w!("SayaClipboardListener"),
+                w!("saya clipboard listener"),
+                WINDOW_STYLE(0),
+                0,
+                0,
+                0,
+                0,
+                Some(HWND_MESSAGE),
+                None,
+                Some(instance),
+                None,
+            )?;
+            if let Err(e) = AddClipboardFormatListener(hwnd) {
+                let _ = DestroyWindow(hwnd);
+                return Err(e.into());
+            }
+            Ok(hwnd)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct MemoryClipboard {
+        text: String,
+    }
+
+    impl ClipboardAccess for MemoryClipboard {
+        fn read_text(&mut self) -> anyhow::Result<String> {
+            Ok(self.text.clone())
+        }
+
+        fn write_text(&mut self, text: &str) -> anyhow::Result<()> {
+            self.text = text.to_string();
+            Ok(())
+        }
+    }
+
+    /// Clipboard shared between the watcher and the test
+    #[derive(Clone, Default)]
+    struct SharedClipboard(Arc<Mutex<String>>);
+
+    impl ClipboardAccess for SharedClipboard {
+        fn read_text(&mut self) -> anyhow::Result<String> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        fn write_text(&mut self, text: &str) -> anyhow::Result<()> {
+            *self.0.lock().unwrap() = text.to_string();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_polling_reports_clipboard_change() {
+        let mut user = SharedClipboard::default();
+        let mut watched = user.clone();
+        let mut changes = ClipboardChanges::new(Arc::new(SelfWrites::default()));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut on_text = move |text| {
+            let _ = tx.send(text);
+        };
+
+        let poll = poll_clipboard(
+            &mut watched,
+            &mut changes,
+            Duration::from_millis(5),
+            &mut on_text,
+        );
+        let copies = async {
+            let mut seen = Vec::new();
+            for text in ["食べる", "飲む"] {
+                user.write_text(text).unwrap();
+                seen.push(rx.recv().await.unwrap());
+            }
+            seen
+        };
+
+        tokio::select! {
+            _ = poll => unreachable!("polling never stops"),
+            seen = time::timeout(Duration::from_secs(5), copies) => {
+                assert_eq!(seen.unwrap(), vec!["食べる", "飲む"]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_copy_writes_text_and_suppresses_echo() {
+        let self_writes = Arc::new(SelfWrites::default());
+        let mut changes = ClipboardChanges::new(self_writes.clone());
+        let mut clipboard = MemoryClipboard::default();
+
+        copy_text(&mut clipboard, "食べる", &self_writes).unwrap();
+        assert_eq!(clipboard.read_text().unwrap(), "食べる");
+
+        // The watcher's next poll sees our own write and ignores it
+        assert_eq!(changes.observe(clipboard.read_text().unwrap()), None);
+        assert_eq!(changes.observe(clipboard.read_text().unwrap()), None);
+    }
+
+    #[test]
+    fn test_user_copies_still_reported() {
+        let self_writes = Arc::new(SelfWrites::default());
+        let mut changes = ClipboardChanges::new(self_writes.clone());
+        let mut clipboard = MemoryClipboard::default();
+
+        copy_text(&mut clipboard, "食べる", &self_writes).unwrap();
+        assert_eq!(changes.observe(clipboard.read_text().unwrap()), None);
+
+        // Suppression is one-shot: copying the same text again later is user input
+        clipboard.write_text("飲む").unwrap();
+        assert_eq!(changes.observe(clipboard.read_text().unwrap()), Some("飲む".to_string()));
+        clipboard.write_text("食べる").unwrap();
+        assert_eq!(changes.observe(clipboard.read_text().unwrap()), Some("食べる".to_string()));
+    }
+}
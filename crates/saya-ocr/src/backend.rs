@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use crate::capture::RawImage;
+use crate::tesseract::TesseractOcr;
+
+/// Values accepted by `ocr.backend`
+pub const BACKENDS: &[&str] = &["windows", "tesseract"];
+
+/// Text recognition engine
+pub trait OcrBackend: Send + Sync {
+    /// Name as written in `ocr.backend`
+    fn name(&self) -> &'static str;
+
+    /// Recognize the text in `image`, `language` is a BCP-47 code like "ja"
+    fn recognize(&self, image: &RawImage, language: &str) -> Result<String>;
+}
+
+/// Build the engine named by `ocr.backend`
+///
+/// `tesseract_path` is the tesseract executable, only used by that backend.
+pub fn create_backend(
+    name: &str,
+    language: &str,
+    tesseract_path: &str,
+) -> Result<Arc<dyn OcrBackend>> {
+    match name {
+        "windows" => windows_backend(language),
+        "tesseract" => Ok(Arc::new(TesseractOcr::new(tesseract_path))),
+        other => anyhow::bail!(
+            "unknown OCR backend '{}' (supported: {})",
+            other,
+            BACKENDS.join(", ")
+        ),
+    }
+}
+
+#[cfg(windows)]
+fn windows_backend(language: &str) -> Result<Arc<dyn OcrBackend>> {
+    let engine = crate::ocr::init_ocr_engine(language)?;
+    Ok(Arc::new(crate::ocr::WindowsOcr::new(engine)))
+}
+
+#[cfg(not(windows))]
+fn windows_backend(_language: &str) -> Result<Arc<dyn OcrBackend>> {
+    anyhow::bail!(crate::unsupported::UNSUPPORTED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_backend_is_selected() {
+        let backend = create_backend("tesseract", "ja", "tesseract").unwrap();
+        assert_eq!(backend.name(), "tesseract");
+    }
+
+    #[test]
+    fn test_unknown_backend_errors() {
+        let err = create_backend("paddle", "ja", "tesseract").err().unwrap();
+        let message = err.to_string();
+        assert!(message.contains("'paddle'"));
+        assert!(message.contains("windows, tesseract"));
+    }
+}
@@ -16,7 +16,7 @@ fn main() -> Result<()> {
     // 2. Capture primary screen
     tracing::debug!("\n2. Capturing primary screen...");
     let start = std::time::Instant::now();
-    let png_data = saya_ocr::capture_primary_screen()?;
+    let png_data = saya_ocr::capture_full_screen(0)?;
     tracing::debug!("   {} bytes in {:?}", png_data.len(), start.elapsed());
 
     // 3. Save for inspection
@@ -1,14 +1,45 @@
 use anyhow::{Context, Result};
-use saya_types::CaptureRegion;
+use saya_types::{CaptureMonitor, CaptureRegion};
 use xcap::{Monitor, Window};
 
-#[allow(dead_code)]
+use crate::frame_hash::average_hash;
+use crate::monitor::{MonitorInfo, capture_crop, list_monitors, monitors_bounds, select_monitor};
+use crate::window::{SystemWindows, capture_window_in};
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum OcrError {
+    /// The region has no width or height, e.g. a window shrunk to its header
+    #[error("Region too small")]
+    EmptyRegion,
+}
+
+/// RGBA pixels
 pub struct RawImage {
     pub data: Vec<u8>,
     pub width: u32,
     pub height: u32,
 }
 
+impl RawImage {
+    /// Decode a PNG as returned by the capture functions
+    pub fn from_png(bytes: &[u8]) -> Result<Self> {
+        let image = xcap::image::load_from_memory_with_format(bytes, xcap::image::ImageFormat::Png)
+            .context("Failed to decode PNG")?
+            .to_rgba8();
+        Ok(Self {
+            width: image.width(),
+            height: image.height(),
+            data: image.into_raw(),
+        })
+    }
+
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let image = xcap::image::RgbaImage::from_raw(self.width, self.height, self.data.clone())
+            .context("Pixel data doesn't match the image size")?;
+        encode_png(&image)
+    }
+}
+
 /// List all available windows with their titles
 pub fn list_windows() -> Result<Vec<(u32, String)>> {
     let windows = Window::all().context("Failed to enumerate windows")?;
@@ -20,15 +51,11 @@ pub fn list_windows() -> Result<Vec<(u32, String)>> {
 }
 
 /// Capture a specific window by ID
+///
+/// Fails with a [`WindowCaptureError`](crate::WindowCaptureError) when the window is
+/// gone or stays minimized.
 pub fn capture_window(window_id: u32) -> Result<Vec<u8>> {
-    let windows = Window::all().context("Failed to enumerate windows")?;
-    let window = windows
-        .into_iter()
-        .find(|w| w.id() == window_id)
-        .context("Window not found")?;
-
-    let image = window.capture_image().context("Failed to capture window")?;
-    encode_png(&image)
+    Ok(capture_window_in(&SystemWindows, window_id)?)
 }
 
 /// Capture window by title (partial match)
@@ -43,10 +70,11 @@ pub fn capture_window_by_title(title: &str) -> Result<Vec<u8>> {
     encode_png(&image)
 }
 
-/// Capture the entire primary monitor
-pub fn capture_primary_screen() -> Result<Vec<u8>> {
+/// Capture the entire monitor selected by `ocr.monitor_index`
+pub fn capture_full_screen(monitor_index: usize) -> Result<Vec<u8>> {
     let monitors = Monitor::all().context("Failed to get monitors")?;
-    let monitor = monitors.first().context("No monitor found")?;
+    anyhow::ensure!(!monitors.is_empty(), "No monitor found");
+    let monitor = &monitors[select_monitor(monitors.len(), monitor_index)];
 
     let image = monitor
         .capture_image()
@@ -70,39 +98,82 @@ pub fn capture_primary_screen_raw() -> Result<RawImage> {
     })
 }
 
+/// Bounding box of all monitors, in screen coordinates
+pub fn screen_bounds() -> Result<CaptureRegion> {
+    monitors_bounds(&list_monitors()?).context("No monitor found")
+}
+
 /// Capture a region of the screen
-pub fn capture_screen_region(region: CaptureRegion) -> Result<Vec<u8>> {
+///
+/// The region is cropped from `monitor`, see [`capture_crop`]; with
+/// [`CaptureMonitor::Auto`] regions not on a single monitor are cropped
+/// from `default_monitor`. Fails with [`OcrError::EmptyRegion`] when
+/// there's nothing to crop.
+pub fn capture_screen_region(
+    region: CaptureRegion,
+    monitor: &CaptureMonitor,
+    default_monitor: usize,
+) -> Result<Vec<u8>> {
+    encode_png(&capture_region_image(region, monitor, default_monitor)?)
+}
+
+/// [`capture_screen_region`] as raw pixels, with their [`average_hash`]
+///
+/// Comparing the hash with the previous frame's tells whether OCR is worth running.
+pub fn capture_and_hash(
+    region: CaptureRegion,
+    monitor: &CaptureMonitor,
+    default_monitor: usize,
+) -> Result<(RawImage, u64)> {
+    let image = capture_region_image(region, monitor, default_monitor)?;
+    let image = RawImage {
+        width: image.width(),
+        height: image.height(),
+        data: image.into_raw(),
+    };
+    let hash = average_hash(&image);
+    Ok((image, hash))
+}
+
+fn capture_region_image(
+    region: CaptureRegion,
+    monitor: &CaptureMonitor,
+    default_monitor: usize,
+) -> Result<xcap::image::RgbaImage> {
+    if region.width == 0 || region.height == 0 {
+        return Err(OcrError::EmptyRegion.into());
+    }
+
     let monitors = Monitor::all().context("Failed to get monitors")?;
+    anyhow::ensure!(!monitors.is_empty(), "No monitor found");
 
-    let monitor = monitors
+    let infos: Vec<MonitorInfo> = monitors
         .iter()
-        .find(|m| {
-            region.x >= m.x()
-                && region.y >= m.y()
-                && region.x + region.width as i32 <= m.x() + m.width() as i32
-                && region.y + region.height as i32 <= m.y() + m.height() as i32
-        })
-        .or(monitors.first())
-        .context("No monitor found")?;
+        .enumerate()
+        .map(|(index, monitor)| MonitorInfo::from_xcap(index, monitor))
+        .collect();
+    let (index, (x, y, width, height)) = capture_crop(&infos, region, monitor, default_monitor);
 
-    let image = monitor
+    let image = monitors[index]
         .capture_image()
         .context("Failed to capture screen")?;
-
-    // Crop to region using xcap's image (0.25)
-    let cropped = xcap::image::imageops::crop_imm(
-        &image,
-        (region.x - monitor.x()) as u32,
-        (region.y - monitor.y()) as u32,
-        region.width,
-        region.height,
-    )
-    .to_image();
-
-    encode_png(&cropped)
+    tracing::debug!(
+        "Cropping {}x{} at ({}, {}) from monitor {} (scale {})",
+        width,
+        height,
+        x,
+        y,
+        index,
+        infos[index].scale_factor
+    );
+    // Off the monitor's edge
+    if width == 0 || height == 0 {
+        return Err(OcrError::EmptyRegion.into());
+    }
+    Ok(xcap::image::imageops::crop_imm(&image, x, y, width, height).to_image())
 }
 
-fn encode_png(image: &xcap::image::RgbaImage) -> Result<Vec<u8>> {
+pub(crate) fn encode_png(image: &xcap::image::RgbaImage) -> Result<Vec<u8>> {
     use xcap::image::ImageEncoder;
     let mut buffer = Vec::new();
     xcap::image::codecs::png::PngEncoder::new(&mut buffer)
@@ -115,3 +186,22 @@ fn encode_png(image: &xcap::image::RgbaImage) -> Result<Vec<u8>> {
         .context("Failed to encode PNG")?;
     Ok(buffer)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_size_region_is_rejected() {
+        for (width, height) in [(200, 0), (0, 100), (0, 0)] {
+            let region = CaptureRegion {
+                x: 10,
+                y: 10,
+                width,
+                height,
+            };
+            let err = capture_screen_region(region, &CaptureMonitor::Auto, 0).unwrap_err();
+            assert_eq!(err.downcast_ref::<OcrError>(), Some(&OcrError::EmptyRegion));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::capture::RawImage;
+
+/// Side of the grayscale thumbnail hashed by [`average_hash`]
+const HASH_SIDE: u64 = 8;
+
+/// 64-bit average hash of `image`
+///
+/// The image is shrunk to an 8x8 grayscale thumbnail and each cell brighter
+/// than the thumbnail's mean sets one bit. Near-identical frames hash to
+/// nearby values, compare them with [`hamming_distance`].
+pub fn average_hash(image: &RawImage) -> u64 {
+    let (width, height) = (image.width as u64, image.height as u64);
+    let mut sums = [0u64; 64];
+    let mut counts = [0u64; 64];
+
+    for (i, pixel) in image.data.chunks_exact(4).enumerate() {
+        let (x, y) = (i as u64 % width.max(1), i as u64 / width.max(1));
+        if y >= height {
+            break;
+        }
+        let cell = (y * HASH_SIDE / height * HASH_SIDE + x * HASH_SIDE / width) as usize;
+        let [r, g, b, _] = [pixel[0], pixel[1], pixel[2], pixel[3]].map(u64::from);
+        sums[cell] += (299 * r + 587 * g + 114 * b) / 1000;
+        counts[cell] += 1;
+    }
+
+    let cells: Vec<u64> = sums
+        .iter()
+        .zip(&counts)
+        .map(|(sum, count)| sum.checked_div(*count).unwrap_or(0))
+        .collect();
+    let mean = cells.iter().sum::<u64>() / cells.len() as u64;
+
+    cells
+        .iter()
+        .enumerate()
+        .filter(|(_, luma)| **luma > mean)
+        .fold(0, |hash, (bit, _)| hash | 1 << bit)
+}
+
+/// Number of bits two hashes differ in
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// White image with a black box over `dark` columns
+    fn image(dark: std::ops::Range<u32>) -> RawImage {
+        let (width, height) = (64, 32);
+        let mut data = Vec::new();
+        for _ in 0..height {
+            for x in 0..width {
+                let value = if dark.contains(&x) { 0 } else { 255 };
+                data.extend([value, value, value, 255]);
+            }
+        }
+        RawImage {
+            data,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_identical_images_hash_identically() {
+        assert_eq!(average_hash(&image(0..16)), average_hash(&image(0..16)));
+    }
+
+    #[test]
+    fn test_changed_image_hashes_differently() {
+        let before = average_hash(&image(0..16));
+        let after = average_hash(&image(40..64));
+        assert_ne!(before, after);
+        assert!(hamming_distance(before, after) > 8);
+    }
+
+    #[test]
+    fn test_empty_image() {
+        let empty = RawImage {
+            data: Vec::new(),
+            width: 0,
+            height: 0,
+        };
+        assert_eq!(average_hash(&empty), 0);
+    }
+}
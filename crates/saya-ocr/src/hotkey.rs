@@ -7,6 +7,7 @@ use global_hotkey::{
 pub struct HotkeyManager {
     manager: GlobalHotKeyManager,
     hotkey: HotKey,
+    extra: Vec<HotKey>,
 }
 
 impl HotkeyManager {
@@ -20,7 +21,11 @@ impl HotkeyManager {
             .register(hotkey)
             .context("Failed to register hotkey")?;
 
-        Ok(Self { manager, hotkey })
+        Ok(Self {
+            manager,
+            hotkey,
+            extra: Vec::new(),
+        })
     }
 
     /// Create with F9 hotkey
@@ -33,7 +38,11 @@ impl HotkeyManager {
             .register(hotkey)
             .context("Failed to register hotkey")?;
 
-        Ok(Self { manager, hotkey })
+        Ok(Self {
+            manager,
+            hotkey,
+            extra: Vec::new(),
+        })
     }
 
     /// Create with custom hotkey
@@ -46,7 +55,33 @@ impl HotkeyManager {
             .register(hotkey)
             .context("Failed to register hotkey")?;
 
-        Ok(Self { manager, hotkey })
+        Ok(Self {
+            manager,
+            hotkey,
+            extra: Vec::new(),
+        })
+    }
+
+    /// Register another hotkey, reported by [`HotkeyManager::poll_id`]
+    pub fn add_hotkey(&mut self, modifiers: Modifiers, code: Code) -> Result<u32> {
+        let hotkey = HotKey::new(Some(modifiers), code);
+
+        self.manager
+            .register(hotkey)
+            .context("Failed to register hotkey")?;
+        self.extra.push(hotkey);
+
+        Ok(hotkey.id())
+    }
+
+    /// ID of a registered hotkey pressed since the last poll (non-blocking)
+    ///
+    /// Use this instead of [`HotkeyManager::poll`] once extra hotkeys are
+    /// registered, since polling consumes events for every hotkey.
+    pub fn poll_id(&self) -> Option<u32> {
+        let event = GlobalHotKeyEvent::receiver().try_recv().ok()?;
+        let known = event.id == self.hotkey.id() || self.extra.iter().any(|h| h.id() == event.id);
+        known.then_some(event.id)
     }
 
     /// Check if hotkey was pressed (non-blocking)
@@ -85,5 +120,8 @@ impl HotkeyManager {
 impl Drop for HotkeyManager {
     fn drop(&mut self) {
         let _ = self.manager.unregister(self.hotkey);
+        for hotkey in &self.extra {
+            let _ = self.manager.unregister(*hotkey);
+        }
     }
 }
@@ -1,12 +1,33 @@
+mod backend;
 mod capture;
+#[cfg(windows)]
 mod com;
+mod frame_hash;
 mod hotkey;
+mod monitor;
+#[cfg(windows)]
 mod ocr;
+mod tesseract;
+#[cfg(not(windows))]
+mod unsupported;
+mod window;
 
+pub use backend::{BACKENDS, OcrBackend, create_backend};
 pub use capture::{
-    capture_primary_screen, capture_screen_region, capture_window, capture_window_by_title,
-    list_windows,
+    OcrError, RawImage, capture_and_hash, capture_full_screen, capture_screen_region,
+    capture_window, capture_window_by_title, list_windows, screen_bounds,
 };
+#[cfg(windows)]
 pub use com::ComGuard;
+pub use frame_hash::{average_hash, hamming_distance};
+pub use global_hotkey::hotkey::{Code, Modifiers};
 pub use hotkey::HotkeyManager;
-pub use ocr::{init_ocr_engine, recognize_sync};
+pub use monitor::{
+    MonitorInfo, capture_crop, list_monitors, monitor_for_region, monitors_bounds, select_monitor,
+};
+#[cfg(windows)]
+pub use ocr::{OcrEngine, WindowsOcr, init_ocr_engine, recognize_sync};
+pub use tesseract::{TesseractOcr, tesseract_language};
+#[cfg(not(windows))]
+pub use unsupported::{ComGuard, OcrEngine, UNSUPPORTED, init_ocr_engine, recognize_sync};
+pub use window::{SystemWindows, WindowCaptureError, WindowInfo, WindowSet, capture_window_in};
@@ -0,0 +1,312 @@
+use anyhow::{Context, Result};
+use saya_types::{CaptureMonitor, CaptureRegion};
+use xcap::Monitor;
+
+/// A connected monitor, as offered in the monitor picker
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    /// Position in [`list_monitors`], the value for `ocr.monitor_index`
+    pub index: usize,
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    /// Physical pixels per logical pixel (1.5 at 150% scaling)
+    ///
+    /// Only reported: regions and monitor bounds are both in physical pixels.
+    pub scale_factor: f32,
+    pub is_primary: bool,
+}
+
+impl MonitorInfo {
+    pub(crate) fn from_xcap(index: usize, monitor: &Monitor) -> Self {
+        Self {
+            index,
+            name: monitor.name().to_string(),
+            x: monitor.x(),
+            y: monitor.y(),
+            width: monitor.width(),
+            height: monitor.height(),
+            scale_factor: monitor.scale_factor(),
+            is_primary: monitor.is_primary(),
+        }
+    }
+
+    /// Whether `region` lies entirely on this monitor
+    ///
+    /// Regions are in physical pixels, like the monitor bounds, whatever the scale factor.
+    pub fn contains(&self, region: CaptureRegion) -> bool {
+        let (left, top, right, bottom) = edges(region);
+
+        left >= self.x as i64
+            && top >= self.y as i64
+            && right <= self.x as i64 + self.width as i64
+            && bottom <= self.y as i64 + self.height as i64
+    }
+
+    /// Whether any of `region` is on this monitor
+    pub fn overlaps(&self, region: CaptureRegion) -> bool {
+        let (left, top, right, bottom) = edges(region);
+
+        left < self.x as i64 + self.width as i64
+            && right > self.x as i64
+            && top < self.y as i64 + self.height as i64
+            && bottom > self.y as i64
+    }
+
+    /// Crop rectangle `(x, y, width, height)` in the captured image's pixels
+    pub fn crop_rect(&self, region: CaptureRegion) -> (u32, u32, u32, u32) {
+        self.crop_from(region, self.x as i64, self.y as i64)
+    }
+
+    /// [`MonitorInfo::crop_rect`] for a region relative to this monitor's top-left corner
+    pub fn relative_crop_rect(&self, region: CaptureRegion) -> (u32, u32, u32, u32) {
+        self.crop_from(region, 0, 0)
+    }
+
+    /// Crop of `region` with `(origin_x, origin_y)` subtracted, clamped to the monitor
+    fn crop_from(
+        &self,
+        region: CaptureRegion,
+        origin_x: i64,
+        origin_y: i64,
+    ) -> (u32, u32, u32, u32) {
+        let clamp = |value: i64, max: u32| value.clamp(0, max as i64) as u32;
+
+        let x = clamp(region.x as i64 - origin_x, self.width);
+        let y = clamp(region.y as i64 - origin_y, self.height);
+        let width = clamp(region.width as i64, self.width - x);
+        let height = clamp(region.height as i64, self.height - y);
+
+        (x, y, width, height)
+    }
+}
+
+/// `(left, top, right, bottom)` of `region`, without overflow
+fn edges(region: CaptureRegion) -> (i64, i64, i64, i64) {
+    let left = region.x as i64;
+    let top = region.y as i64;
+    (
+        left,
+        top,
+        left + region.width as i64,
+        top + region.height as i64,
+    )
+}
+
+/// All connected monitors
+pub fn list_monitors() -> Result<Vec<MonitorInfo>> {
+    let monitors = Monitor::all().context("Failed to get monitors")?;
+    Ok(monitors
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| MonitorInfo::from_xcap(index, monitor))
+        .collect())
+}
+
+/// Smallest region covering all of `monitors`, `None` without any
+pub fn monitors_bounds(monitors: &[MonitorInfo]) -> Option<CaptureRegion> {
+    let left = monitors.iter().map(|m| m.x).min()?;
+    let top = monitors.iter().map(|m| m.y).min()?;
+    let right = monitors.iter().map(|m| m.x + m.width as i32).max()?;
+    let bottom = monitors.iter().map(|m| m.y + m.height as i32).max()?;
+
+    Some(CaptureRegion {
+        x: left,
+        y: top,
+        width: (right - left) as u32,
+        height: (bottom - top) as u32,
+    })
+}
+
+/// `requested` if there are that many monitors, otherwise the first
+pub fn select_monitor(count: usize, requested: usize) -> usize {
+    if requested < count {
+        return requested;
+    }
+    tracing::warn!(
+        "Monitor {} not found ({} connected), using monitor 0",
+        requested,
+        count
+    );
+    0
+}
+
+/// Monitor holding all of `region`, or `default_index` when none does
+pub fn monitor_for_region(
+    monitors: &[MonitorInfo],
+    region: CaptureRegion,
+    default_index: usize,
+) -> usize {
+    monitors
+        .iter()
+        .position(|m| m.contains(region))
+        .unwrap_or_else(|| select_monitor(monitors.len(), default_index))
+}
+
+/// Monitor to capture `region` from and the crop rectangle on it
+///
+/// A forced `choice` that isn't connected is reported and treated as
+/// [`CaptureMonitor::Auto`].
+pub fn capture_crop(
+    monitors: &[MonitorInfo],
+    region: CaptureRegion,
+    choice: &CaptureMonitor,
+    default_index: usize,
+) -> (usize, (u32, u32, u32, u32)) {
+    let forced = match choice {
+        CaptureMonitor::Auto => None,
+        CaptureMonitor::Index(index) => (*index < monitors.len()).then_some(*index),
+        CaptureMonitor::Name(name) => monitors
+            .iter()
+            .position(|m| m.name.eq_ignore_ascii_case(name)),
+    };
+
+    match forced {
+        Some(index) => (index, monitors[index].relative_crop_rect(region)),
+        None => {
+            if *choice != CaptureMonitor::Auto {
+                tracing::warn!("Capture monitor {:?} not connected, picking one", choice);
+            }
+            let index = monitor_for_region(monitors, region, default_index);
+            (index, monitors[index].crop_rect(region))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(index: usize, x: i32) -> MonitorInfo {
+        MonitorInfo {
+            index,
+            name: format!("Display {}", index),
+            x,
+            y: 0,
+            width: 1920,
+            height: 1080,
+            scale_factor: 1.0,
+            is_primary: index == 0,
+        }
+    }
+
+    fn region(x: i32) -> CaptureRegion {
+        CaptureRegion {
+            x,
+            y: 100,
+            width: 400,
+            height: 300,
+        }
+    }
+
+    #[test]
+    fn test_monitors_bounds_spans_all_monitors() {
+        assert_eq!(monitors_bounds(&[]), None);
+        assert_eq!(
+            monitors_bounds(&[monitor(0, 0), monitor(1, 1920)]),
+            Some(CaptureRegion {
+                x: 0,
+                y: 0,
+                width: 3840,
+                height: 1080,
+            })
+        );
+    }
+
+    #[test]
+    fn test_select_monitor_in_range() {
+        assert_eq!(select_monitor(3, 2), 2);
+        assert_eq!(select_monitor(3, 0), 0);
+    }
+
+    #[test]
+    fn test_select_monitor_out_of_range_falls_back() {
+        assert_eq!(select_monitor(2, 5), 0);
+        assert_eq!(select_monitor(0, 1), 0);
+    }
+
+    #[test]
+    fn test_region_maps_to_its_monitor() {
+        let monitors = [monitor(0, 0), monitor(1, 1920)];
+        assert_eq!(monitor_for_region(&monitors, region(2000), 0), 1);
+        assert_eq!(monitor_for_region(&monitors, region(100), 1), 0);
+    }
+
+    #[test]
+    fn test_region_spanning_monitors_uses_default() {
+        let monitors = [monitor(0, 0), monitor(1, 1920)];
+        assert_eq!(monitor_for_region(&monitors, region(1800), 1), 1);
+        assert_eq!(monitor_for_region(&monitors, region(1800), 7), 0);
+    }
+
+    #[test]
+    fn test_overlaps_partially_visible_region() {
+        let monitor = monitor(0, 0);
+        assert!(monitor.overlaps(region(1800)));
+        assert!(!monitor.overlaps(region(1920)));
+        assert!(!monitor.overlaps(region(-400)));
+    }
+
+    #[test]
+    fn test_crop_rect_unscaled() {
+        assert_eq!(monitor(0, 0).crop_rect(region(200)), (200, 100, 400, 300));
+        assert_eq!(monitor(1, 1920).crop_rect(region(2000)), (80, 100, 400, 300));
+    }
+
+    #[test]
+    fn test_forced_monitor_crops_relative_to_its_origin() {
+        let monitors = [monitor(0, 0), monitor(1, 1920)];
+
+        let auto = capture_crop(&monitors, region(200), &CaptureMonitor::Auto, 0);
+        assert_eq!(auto, (0, (200, 100, 400, 300)));
+
+        let by_index = capture_crop(&monitors, region(200), &CaptureMonitor::Index(1), 0);
+        assert_eq!(by_index, (1, (200, 100, 400, 300)));
+        let by_name = CaptureMonitor::Name("display 1".to_string());
+        assert_eq!(capture_crop(&monitors, region(200), &by_name, 0), by_index);
+
+        // Forced coordinates start at the monitor's corner, auto ones at the screen's
+        let auto = capture_crop(&monitors, region(2000), &CaptureMonitor::Auto, 0);
+        let forced = capture_crop(&monitors, region(80), &CaptureMonitor::Index(1), 0);
+        assert_eq!(auto, (1, (80, 100, 400, 300)));
+        assert_eq!(forced, auto);
+    }
+
+    #[test]
+    fn test_missing_forced_monitor_falls_back_to_auto() {
+        let monitors = [monitor(0, 0), monitor(1, 1920)];
+        let missing = CaptureMonitor::Name("Projector".to_string());
+        assert_eq!(
+            capture_crop(&monitors, region(2000), &missing, 0),
+            capture_crop(&monitors, region(2000), &CaptureMonitor::Auto, 0)
+        );
+        assert_eq!(
+            capture_crop(&monitors, region(2000), &CaptureMonitor::Index(4), 0).0,
+            1
+        );
+    }
+
+    #[test]
+    fn test_physical_region_not_rescaled_on_hidpi() {
+        let hidpi = MonitorInfo {
+            scale_factor: 1.5,
+            ..monitor(0, 0)
+        };
+        assert_eq!(hidpi.crop_rect(region(200)), (200, 100, 400, 300));
+        assert!(hidpi.contains(region(1500)));
+        assert!(!hidpi.contains(region(1600)));
+    }
+
+    #[test]
+    fn test_crop_rect_clamps_to_monitor() {
+        let edge = CaptureRegion {
+            x: 1800,
+            y: -50,
+            width: 400,
+            height: 300,
+        };
+        assert_eq!(monitor(0, 0).crop_rect(edge), (1800, 0, 120, 300));
+    }
+}
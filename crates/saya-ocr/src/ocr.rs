@@ -1,12 +1,18 @@
 use anyhow::Context;
 use windows::{
     Globalization::Language,
-    Graphics::Imaging::{BitmapAlphaMode, BitmapDecoder, BitmapPixelFormat},
+    Graphics::Imaging::{BitmapAlphaMode, BitmapDecoder, BitmapPixelFormat, SoftwareBitmap},
     Media::Ocr::OcrEngine as WinOcrEngine,
     Storage::Streams::{DataWriter, InMemoryRandomAccessStream},
     core::HSTRING,
 };
 
+use crate::backend::OcrBackend;
+use crate::capture::RawImage;
+
+/// Windows.Media.Ocr engine
+pub type OcrEngine = WinOcrEngine;
+
 pub fn init_ocr_engine(language_code: &str) -> anyhow::Result<WinOcrEngine> {
     // Create OCR engine
     let language = Language::CreateLanguage(&HSTRING::from(language_code))
@@ -83,3 +89,54 @@ pub async fn recognize_async(
 
     Ok(text)
 }
+
+/// Windows.Media.Ocr as an [`OcrBackend`]
+///
+/// The engine is created for one language, `language` passed to
+/// `recognize` is only logged.
+pub struct WindowsOcr {
+    engine: WinOcrEngine,
+}
+
+impl WindowsOcr {
+    pub fn new(engine: WinOcrEngine) -> Self {
+        Self { engine }
+    }
+}
+
+impl OcrBackend for WindowsOcr {
+    fn name(&self) -> &'static str {
+        "windows"
+    }
+
+    fn recognize(&self, image: &RawImage, language: &str) -> anyhow::Result<String> {
+        tracing::debug!(
+            ">>> [OCR] Windows OCR: {}x{}, lang={}",
+            image.width,
+            image.height,
+            language
+        );
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(recognize_raw_async(&self.engine, image))
+        })
+    }
+}
+
+/// Perform OCR on RGBA pixels without a PNG round trip
+async fn recognize_raw_async(engine: &WinOcrEngine, image: &RawImage) -> anyhow::Result<String> {
+    let writer = DataWriter::new()?;
+    writer.WriteBytes(&image.data)?;
+    let buffer = writer.DetachBuffer()?;
+
+    let bitmap = SoftwareBitmap::CreateCopyFromBuffer(
+        &buffer,
+        BitmapPixelFormat::Rgba8,
+        image.width as i32,
+        image.height as i32,
+    )?;
+    // OCR only takes Bgra8 or Gray8
+    let bitmap = SoftwareBitmap::Convert(&bitmap, BitmapPixelFormat::Bgra8)?;
+
+    let result = engine.RecognizeAsync(&bitmap)?.await?;
+    Ok(result.Text()?.to_string())
+}
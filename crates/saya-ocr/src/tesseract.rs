@@ -0,0 +1,126 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+use crate::backend::OcrBackend;
+use crate::capture::RawImage;
+
+/// OCR through the tesseract command line tool
+pub struct TesseractOcr {
+    command: String,
+}
+
+impl TesseractOcr {
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+        }
+    }
+}
+
+impl OcrBackend for TesseractOcr {
+    fn name(&self) -> &'static str {
+        "tesseract"
+    }
+
+    fn recognize(&self, image: &RawImage, language: &str) -> Result<String> {
+        let png = image.to_png()?;
+        let lang = tesseract_language(language);
+
+        let mut child = Command::new(&self.command)
+            .args(["stdin", "stdout", "-l", lang])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| {
+                format!("Failed to run '{}', is tesseract installed?", self.command)
+            })?;
+
+        child
+            .stdin
+            .take()
+            .context("tesseract stdin unavailable")?
+            .write_all(&png)
+            .context("Failed to send image to tesseract")?;
+
+        let output = child
+            .wait_with_output()
+            .context("tesseract didn't finish")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "tesseract failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let text = String::from_utf8_lossy(&output.stdout);
+        tracing::debug!(">>> [OCR] tesseract ({}): {} chars", lang, text.len());
+        Ok(normalize_output(&text, lang))
+    }
+}
+
+/// Tesseract traineddata name for a BCP-47 language code
+pub fn tesseract_language(code: &str) -> &str {
+    match code {
+        "ja" => "jpn",
+        "zh" | "zh-CN" | "zh-Hans" => "chi_sim",
+        "zh-TW" | "zh-Hant" => "chi_tra",
+        "ko" => "kor",
+        "en" | "en-US" => "eng",
+        other => other,
+    }
+}
+
+/// Drop the spaces tesseract puts between CJK characters and trailing blank lines
+fn normalize_output(text: &str, lang: &str) -> String {
+    let unspaced = matches!(lang, "jpn" | "chi_sim" | "chi_tra");
+    text.lines()
+        .map(|line| {
+            if unspaced {
+                line.split_whitespace().collect::<String>()
+            } else {
+                line.trim_end().to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tesseract_language() {
+        assert_eq!(tesseract_language("ja"), "jpn");
+        assert_eq!(tesseract_language("zh-TW"), "chi_tra");
+        assert_eq!(tesseract_language("jpn_vert"), "jpn_vert");
+    }
+
+    #[test]
+    fn test_normalize_output_joins_cjk() {
+        assert_eq!(
+            normalize_output("日 本 語\n東 京\n\n", "jpn"),
+            "日本語\n東京"
+        );
+        assert_eq!(normalize_output("hello world  \n\n", "eng"), "hello world");
+    }
+
+    #[test]
+    fn test_missing_executable_errors() {
+        let image = RawImage {
+            data: vec![255; 4],
+            width: 1,
+            height: 1,
+        };
+        let err = TesseractOcr::new("saya-no-such-tesseract")
+            .recognize(&image, "ja")
+            .unwrap_err();
+        assert!(err.to_string().contains("is tesseract installed"));
+    }
+}
@@ -0,0 +1,54 @@
+//! Stand-ins for the Windows OCR engine on other platforms
+//!
+//! Capture still works everywhere, recognition reports [`UNSUPPORTED`].
+
+use anyhow::Result;
+
+/// Error message for recognition off Windows
+pub const UNSUPPORTED: &str = "OCR not supported on this platform";
+
+/// Placeholder engine, never constructed
+#[derive(Debug, Clone)]
+pub struct OcrEngine {
+    _private: (),
+}
+
+/// COM is Windows-only, nothing to initialize
+pub struct ComGuard;
+
+impl ComGuard {
+    pub fn initialize() -> Result<Self> {
+        Ok(ComGuard)
+    }
+}
+
+pub fn init_ocr_engine(language_code: &str) -> Result<OcrEngine> {
+    tracing::warn!("No OCR engine for '{}': {}", language_code, UNSUPPORTED);
+    anyhow::bail!(UNSUPPORTED)
+}
+
+pub fn recognize_sync(
+    _engine: &OcrEngine,
+    _image_bytes: &[u8],
+    _language_code: &str,
+) -> Result<String> {
+    anyhow::bail!(UNSUPPORTED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_init_reports_unsupported() {
+        let err = init_ocr_engine("ja").unwrap_err();
+        assert_eq!(err.to_string(), UNSUPPORTED);
+    }
+
+    #[test]
+    fn test_recognize_reports_unsupported() {
+        let engine = OcrEngine { _private: () };
+        let err = recognize_sync(&engine, &[], "ja").unwrap_err();
+        assert_eq!(err.to_string(), UNSUPPORTED);
+    }
+}
@@ -0,0 +1,258 @@
+use anyhow::{Context, Result};
+use saya_types::{CaptureMonitor, CaptureRegion};
+use xcap::Window;
+
+use crate::capture::{capture_screen_region, encode_png};
+
+/// Time a restored window gets to repaint before it's captured
+const RESTORE_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// A top-level window, as last reported by the window system
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowInfo {
+    pub id: u32,
+    pub title: String,
+    pub minimized: bool,
+    /// Where the window is on screen
+    pub bounds: CaptureRegion,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WindowCaptureError {
+    #[error("Window not found")]
+    NotFound,
+    #[error("Window minimized")]
+    Minimized,
+    #[error("Window can't be captured: {0:#}")]
+    Failed(anyhow::Error),
+}
+
+/// The windows a capture can target
+///
+/// Implemented over xcap by [`SystemWindows`]; tests can supply their own.
+pub trait WindowSet {
+    fn windows(&self) -> Result<Vec<WindowInfo>>;
+    /// Un-minimize the window, returning whether it was restored
+    fn restore(&self, id: u32) -> bool;
+    fn capture(&self, id: u32) -> Result<Vec<u8>>;
+    fn capture_region(&self, region: CaptureRegion) -> Result<Vec<u8>>;
+}
+
+/// Capture window `id` from `set`
+///
+/// A minimized window is restored first. When the window itself can't be
+/// captured (occluded, or its surface is gone) the screen area it last
+/// occupied is captured instead.
+pub fn capture_window_in(set: &impl WindowSet, id: u32) -> Result<Vec<u8>, WindowCaptureError> {
+    let find = || -> Result<WindowInfo, WindowCaptureError> {
+        set.windows()
+            .map_err(WindowCaptureError::Failed)?
+            .into_iter()
+            .find(|w| w.id == id)
+            .ok_or(WindowCaptureError::NotFound)
+    };
+
+    let mut window = find()?;
+    if window.minimized {
+        tracing::debug!(">>> [OCR] Window {} is minimized, restoring", id);
+        if !set.restore(id) {
+            return Err(WindowCaptureError::Minimized);
+        }
+        window = find().map_err(|_| WindowCaptureError::Minimized)?;
+        if window.minimized {
+            return Err(WindowCaptureError::Minimized);
+        }
+    }
+
+    match set.capture(id) {
+        Ok(image) => Ok(image),
+        Err(e) if window.bounds.width > 0 && window.bounds.height > 0 => {
+            tracing::warn!(
+                ">>> [OCR] Window {} capture failed ({:#}), capturing its bounds",
+                id,
+                e
+            );
+            set.capture_region(window.bounds)
+                .map_err(WindowCaptureError::Failed)
+        }
+        Err(e) => Err(WindowCaptureError::Failed(e)),
+    }
+}
+
+/// The desktop's windows, through xcap
+pub struct SystemWindows;
+
+impl SystemWindows {
+    fn find(id: u32) -> Result<Window> {
+        Window::all()
+            .context("Failed to enumerate windows")?
+            .into_iter()
+            .find(|w| w.id() == id)
+            .context("Window not found")
+    }
+}
+
+impl WindowSet for SystemWindows {
+    fn windows(&self) -> Result<Vec<WindowInfo>> {
+        let windows = Window::all().context("Failed to enumerate windows")?;
+        Ok(windows
+            .iter()
+            .map(|w| WindowInfo {
+                id: w.id(),
+                title: w.title().to_string(),
+                minimized: w.is_minimized(),
+                bounds: CaptureRegion {
+                    x: w.x(),
+                    y: w.y(),
+                    width: w.width(),
+                    height: w.height(),
+                },
+            })
+            .collect())
+    }
+
+    #[cfg(windows)]
+    fn restore(&self, id: u32) -> bool {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::UI::WindowsAndMessaging::{IsIconic, SW_RESTORE, ShowWindow};
+
+        // xcap window IDs are HWNDs on Windows
+        let hwnd = HWND(id as usize as *mut std::ffi::c_void);
+        let restored = unsafe {
+            let _ = ShowWindow(hwnd, SW_RESTORE);
+            !IsIconic(hwnd).as_bool()
+        };
+        if restored {
+            std::thread::sleep(RESTORE_DELAY);
+        }
+        restored
+    }
+
+    #[cfg(not(windows))]
+    fn restore(&self, _id: u32) -> bool {
+        false
+    }
+
+    fn capture(&self, id: u32) -> Result<Vec<u8>> {
+        let image = Self::find(id)?
+            .capture_image()
+            .context("Failed to capture window")?;
+        encode_png(&image)
+    }
+
+    fn capture_region(&self, region: CaptureRegion) -> Result<Vec<u8>> {
+        // Window rectangles are in screen coordinates, whatever monitor is configured
+        capture_screen_region(region, &CaptureMonitor::Auto, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    struct MockWindows {
+        windows: Vec<WindowInfo>,
+        can_restore: bool,
+        capturable: bool,
+        restored: Cell<bool>,
+        region: Cell<Option<CaptureRegion>>,
+    }
+
+    impl MockWindows {
+        fn new(minimized: bool) -> Self {
+            Self {
+                windows: vec![WindowInfo {
+                    id: 7,
+                    title: "Game".to_string(),
+                    minimized,
+                    bounds: CaptureRegion {
+                        x: 10,
+                        y: 20,
+                        width: 640,
+                        height: 480,
+                    },
+                }],
+                can_restore: false,
+                capturable: true,
+                restored: Cell::new(false),
+                region: Cell::new(None),
+            }
+        }
+    }
+
+    impl WindowSet for MockWindows {
+        fn windows(&self) -> Result<Vec<WindowInfo>> {
+            let restored = self.restored.get();
+            Ok(self
+                .windows
+                .iter()
+                .cloned()
+                .map(|w| WindowInfo {
+                    minimized: w.minimized && !restored,
+                    ..w
+                })
+                .collect())
+        }
+
+        fn restore(&self, _id: u32) -> bool {
+            self.restored.set(self.can_restore);
+            self.can_restore
+        }
+
+        fn capture(&self, _id: u32) -> Result<Vec<u8>> {
+            anyhow::ensure!(self.capturable, "surface gone");
+            Ok(b"window".to_vec())
+        }
+
+        fn capture_region(&self, region: CaptureRegion) -> Result<Vec<u8>> {
+            self.region.set(Some(region));
+            Ok(b"region".to_vec())
+        }
+    }
+
+    #[test]
+    fn test_missing_window_not_found() {
+        let set = MockWindows::new(false);
+        assert!(matches!(
+            capture_window_in(&set, 99),
+            Err(WindowCaptureError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn test_visible_window_captured_directly() {
+        let set = MockWindows::new(false);
+        assert_eq!(capture_window_in(&set, 7).unwrap(), b"window");
+        assert!(!set.restored.get());
+    }
+
+    #[test]
+    fn test_minimized_window_is_restored() {
+        let set = MockWindows {
+            can_restore: true,
+            ..MockWindows::new(true)
+        };
+        assert_eq!(capture_window_in(&set, 7).unwrap(), b"window");
+        assert!(set.restored.get());
+    }
+
+    #[test]
+    fn test_unrestorable_window_reports_minimized() {
+        let set = MockWindows::new(true);
+        let err = capture_window_in(&set, 7).unwrap_err();
+        assert!(matches!(err, WindowCaptureError::Minimized));
+        assert_eq!(err.to_string(), "Window minimized");
+    }
+
+    #[test]
+    fn test_failed_capture_falls_back_to_bounds() {
+        let set = MockWindows {
+            capturable: false,
+            ..MockWindows::new(false)
+        };
+        assert_eq!(capture_window_in(&set, 7).unwrap(), b"region");
+        assert_eq!(set.region.get(), Some(set.windows[0].bounds));
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{LanguageCode, ProviderMetadata, TranslateError, Translation, Translator};
+
+/// User-chosen translations for terms the provider gets wrong, e.g. character names
+#[derive(Debug, Clone, Default)]
+pub struct Glossary {
+    /// (source, target), longest source first so overlapping terms match whole
+    entries: Vec<(String, String)>,
+}
+
+impl Glossary {
+    pub fn new(entries: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut entries: Vec<(String, String)> = entries
+            .into_iter()
+            .filter(|(source, _)| !source.is_empty())
+            .collect();
+        entries.sort_by_key(|(source, _)| std::cmp::Reverse(source.chars().count()));
+        Self { entries }
+    }
+
+    /// Parse `source\ttarget` lines, skipping blank lines and `#` comments
+    pub fn parse(text: &str) -> Self {
+        Self::new(
+            text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .filter_map(|line| line.split_once('\t'))
+                .map(|(source, target)| (source.trim().to_string(), target.trim().to_string())),
+        )
+    }
+
+    pub fn load_from_file(path: &str) -> std::io::Result<Self> {
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Swap glossary terms for placeholders the provider leaves alone
+    ///
+    /// Returns the protected text and what each placeholder stands for, in order.
+    /// Text that already looks like a placeholder is protected as itself, so it
+    /// can't be mistaken for one on the way back.
+    fn protect(&self, text: &str) -> (String, Vec<String>) {
+        let mut protected = String::with_capacity(text.len());
+        let mut used: Vec<String> = Vec::new();
+        let mut rest = text;
+
+        while let Some(c) = rest.chars().next() {
+            let matched = self
+                .entries
+                .iter()
+                .find(|(source, _)| rest.starts_with(source.as_str()))
+                .map(|(source, target)| (source.len(), target.as_str()))
+                .or_else(|| placeholder_at(rest).map(|(len, _)| (len, &rest[..len])));
+
+            let Some((len, replacement)) = matched else {
+                protected.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            };
+            let n = match used.iter().position(|u| u == replacement) {
+                Some(n) => n,
+                None => {
+                    used.push(replacement.to_string());
+                    used.len() - 1
+                }
+            };
+            protected.push_str(&placeholder(n));
+            rest = &rest[len..];
+        }
+
+        (protected, used)
+    }
+
+    /// Put the protected text back in place of the placeholders, in one pass
+    fn restore(&self, text: &str, used: &[String]) -> String {
+        let mut restored = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(c) = rest.chars().next() {
+            match placeholder_at(rest).filter(|&(_, n)| n < used.len()) {
+                Some((len, n)) => {
+                    restored.push_str(&used[n]);
+                    rest = &rest[len..];
+                }
+                None => {
+                    restored.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+
+        restored
+    }
+}
+
+fn placeholder(n: usize) -> String {
+    format!("[G{}]", n)
+}
+
+/// Byte length and number of a placeholder at the start of `text`
+fn placeholder_at(text: &str) -> Option<(usize, usize)> {
+    let digits = text.strip_prefix("[G")?;
+    let end = digits.find(|c: char| !c.is_ascii_digit())?;
+    if end == 0 || !digits[end..].starts_with(']') {
+        return None;
+    }
+
+    let n = digits[..end].parse().ok()?;
+    Some(("[G".len() + end + 1, n))
+}
+
+/// Translator wrapper applying a [`Glossary`] around any provider
+pub struct GlossaryTranslator<T> {
+    inner: T,
+    glossary: Glossary,
+}
+
+impl<T: Translator> GlossaryTranslator<T> {
+    pub fn new(inner: T, glossary: Glossary) -> Self {
+        Self { inner, glossary }
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: Translator> Translator for GlossaryTranslator<T> {
+    async fn translate(
+        &self,
+        text: &str,
+        from: LanguageCode,
+        to: LanguageCode,
+    ) -> Result<Translation, TranslateError> {
+        let (protected, used) = self.glossary.protect(text);
+        if used.is_empty() {
+            return self.inner.translate(text, from, to).await;
+        }
+
+        let mut translation = self.inner.translate(&protected, from, to).await?;
+        translation.text = self.glossary.restore(&translation.text, &used);
+        for alternative in &mut translation.alternatives {
+            *alternative = self.glossary.restore(alternative, &used);
+        }
+        Ok(translation)
+    }
+
+    async fn detect_language(&self, text: &str) -> Result<LanguageCode, TranslateError> {
+        self.inner.detect_language(text).await
+    }
+
+    fn supported_languages(&self) -> Vec<(LanguageCode, LanguageCode)> {
+        self.inner.supported_languages()
+    }
+
+    fn metadata(&self) -> ProviderMetadata {
+        self.inner.metadata()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mistranslates the name unless it arrives as a placeholder
+    struct NameMangler;
+
+    #[async_trait::async_trait]
+    impl Translator for NameMangler {
+        async fn translate(
+            &self,
+            text: &str,
+            from: LanguageCode,
+            to: LanguageCode,
+        ) -> Result<Translation, TranslateError> {
+            let text = text
+                .replace("アリス", "Alice")
+                .replace("は強い", " is strong");
+            Ok(Translation {
+                text,
+                from,
+                to,
+                provider: "test".to_string(),
+                confidence: None,
+                alternatives: vec![],
+            })
+        }
+
+        async fn detect_language(&self, _text: &str) -> Result<LanguageCode, TranslateError> {
+            Ok("ja".to_string())
+        }
+
+        fn supported_languages(&self) -> Vec<(LanguageCode, LanguageCode)> {
+            vec![]
+        }
+
+        fn metadata(&self) -> ProviderMetadata {
+            ProviderMetadata {
+                name: "test".to_string(),
+                requires_api_key: false,
+                free_tier_available: true,
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_skips_comments_and_sorts_longest_first() {
+        let glossary = Glossary::parse("# names\nアリス\tArisu\n\nアリスちゃん\tLittle Arisu\n");
+
+        assert_eq!(glossary.len(), 2);
+        let (protected, used) = glossary.protect("アリスちゃんとアリス");
+        assert_eq!(protected, "[G0]と[G1]");
+        assert_eq!(glossary.restore(&protected, &used), "Little ArisuとArisu");
+    }
+
+    #[tokio::test]
+    async fn test_glossed_name_uses_chosen_translation() {
+        let glossary = Glossary::parse("アリス\tArisu");
+        let translator = GlossaryTranslator::new(NameMangler, glossary);
+
+        let translation = translator
+            .translate("アリスは強い", "ja".to_string(), "en".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(translation.text, "Arisu is strong");
+    }
+
+    #[tokio::test]
+    async fn test_text_without_glossary_terms_passes_through() {
+        let translator = GlossaryTranslator::new(NameMangler, Glossary::parse("ボブ\tBobu"));
+
+        let translation = translator
+            .translate("アリスは強い", "ja".to_string(), "en".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(translation.text, "Alice is strong");
+    }
+
+    #[tokio::test]
+    async fn test_placeholder_lookalikes_are_left_alone() {
+        let glossary = Glossary::parse("アリス\t[G1]\nボブ\tBobu");
+        let translator = GlossaryTranslator::new(NameMangler, glossary);
+
+        let translation = translator
+            .translate("アリスは強い[G0]", "ja".to_string(), "en".to_string())
+            .await
+            .unwrap();
+        assert_eq!(translation.text, "[G1] is strong[G0]");
+
+        let translation = translator
+            .translate("アリスとボブ", "ja".to_string(), "en".to_string())
+            .await
+            .unwrap();
+        assert_eq!(translation.text, "[G1]とBobu");
+    }
+}
@@ -1,3 +1,7 @@
+mod glossary;
+
+pub use glossary::{Glossary, GlossaryTranslator};
+
 pub type LanguageCode = String;
 
 /// Translation provider interface
@@ -44,7 +48,7 @@ pub enum TranslateError {
     ApiError(String),
 
     #[error("Network error: {0}")]
-    NetworkError(#[from] reqwest::Error),
+    NetworkError(#[source] reqwest::Error),
 
     #[error("Unsupported language pair: {from} -> {to}")]
     UnsupportedLanguagePair { from: String, to: String },
@@ -55,3 +59,13 @@ pub enum TranslateError {
     #[error("Authentication error")]
     AuthenticationError,
 }
+
+impl From<reqwest::Error> for TranslateError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            TranslateError::ApiError("timeout".to_string())
+        } else {
+            TranslateError::NetworkError(e)
+        }
+    }
+}
@@ -1,3 +1,8 @@
+pub mod text;
 pub mod types;
 
+pub use text::{
+    contains_target_script, is_chinese_char, is_hiragana, is_japanese_char, is_kanji, is_katakana,
+    truncate_display,
+};
 pub use types::*;
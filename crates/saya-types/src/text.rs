@@ -0,0 +1,137 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Appended to text cut by [`truncate_display`]
+pub const ELLIPSIS: char = '…';
+
+/// Columns a grapheme takes up, 2 for CJK, fullwidth forms and emoji
+fn grapheme_width(grapheme: &str) -> usize {
+    match grapheme.chars().next() {
+        Some(
+            '\u{1100}'..='\u{115F}'
+            | '\u{2E80}'..='\u{A4CF}'
+            | '\u{AC00}'..='\u{D7A3}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{FE30}'..='\u{FE4F}'
+            | '\u{FF00}'..='\u{FF60}'
+            | '\u{FFE0}'..='\u{FFE6}'
+            | '\u{1F1E6}'..='\u{1F1FF}'
+            | '\u{1F300}'..='\u{1FAFF}'
+            | '\u{20000}'..='\u{3FFFD}',
+        ) => 2,
+        _ => 1,
+    }
+}
+
+/// Cut `s` to at most `max` columns, ending in [`ELLIPSIS`] if anything was dropped
+///
+/// Cuts only fall between graphemes, so accents and emoji sequences stay whole.
+pub fn truncate_display(s: &str, max: usize) -> String {
+    let total: usize = s.graphemes(true).map(grapheme_width).sum();
+    if total <= max {
+        return s.to_string();
+    }
+
+    // Leave a column for the ellipsis
+    let budget = max.saturating_sub(1);
+    let mut width = 0;
+    let mut out = String::new();
+    for grapheme in s.graphemes(true) {
+        width += grapheme_width(grapheme);
+        if width > budget {
+            break;
+        }
+        out.push_str(grapheme);
+    }
+    out.push(ELLIPSIS);
+    out
+}
+
+/// CJK unified ideographs, including extension A
+pub fn is_kanji(ch: char) -> bool {
+    matches!(ch, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}')
+}
+
+pub fn is_hiragana(ch: char) -> bool {
+    matches!(ch, '\u{3041}'..='\u{309F}')
+}
+
+/// Katakana, halfwidth katakana and ー
+pub fn is_katakana(ch: char) -> bool {
+    matches!(ch, 'ー' | '\u{30A0}'..='\u{30FF}' | '\u{FF66}'..='\u{FF9F}')
+}
+
+/// Kana (including halfwidth katakana and ー), kanji and 々
+pub fn is_japanese_char(ch: char) -> bool {
+    ch == '々' || is_hiragana(ch) || is_katakana(ch) || is_kanji(ch)
+}
+
+/// CJK unified ideographs
+pub fn is_chinese_char(ch: char) -> bool {
+    is_kanji(ch)
+}
+
+/// Whether `text` has a character in the script of `language` ("ja", "zh")
+///
+/// Text in a language without a known script always matches.
+pub fn contains_target_script(text: &str, language: &str) -> bool {
+    let is_target: fn(char) -> bool = match language {
+        "ja" => is_japanese_char,
+        "zh" => is_chinese_char,
+        _ => return true,
+    };
+    text.chars().any(is_target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_script_detection() {
+        assert!(!contains_target_script("https://example.com/?q=1", "ja"));
+        assert!(!contains_target_script("fn main() {}", "ja"));
+        assert!(contains_target_script("食べる", "ja"));
+        assert!(contains_target_script("see カタカナ here", "ja"));
+        assert!(contains_target_script("ｶﾀｶﾅ", "ja"));
+        assert!(!contains_target_script("ひらがな", "zh"));
+        assert!(contains_target_script("anything", "xx"));
+    }
+
+    #[test]
+    fn test_short_text_unchanged() {
+        assert_eq!(truncate_display("Notepad", 40), "Notepad");
+        assert_eq!(truncate_display("", 5), "");
+    }
+
+    #[test]
+    fn test_long_text_gets_ellipsis() {
+        assert_eq!(truncate_display("abcdefgh", 5), "abcd…");
+    }
+
+    #[test]
+    fn test_wide_chars_count_double() {
+        // 6 kanji are 12 columns
+        assert_eq!(truncate_display("日本語の窓口", 7), "日本語…");
+        assert_eq!(truncate_display("日本語", 6), "日本語");
+    }
+
+    #[test]
+    fn test_combining_marks_stay_with_base() {
+        // e + combining acute, five times
+        let accented = "e\u{301}".repeat(5);
+        let cut = truncate_display(&accented, 3);
+        assert_eq!(cut, format!("{}…", "e\u{301}".repeat(2)));
+    }
+
+    #[test]
+    fn test_emoji_sequences_not_split() {
+        let family = "👨\u{200D}👩\u{200D}👧";
+        let flag = "🇯🇵";
+        let title = format!("{family}{flag}{family}");
+
+        let cut = truncate_display(&title, 5);
+        assert_eq!(cut, format!("{family}{flag}…"));
+        assert_eq!(truncate_display(&title, 4), format!("{family}…"));
+        assert_eq!(truncate_display(&title, 2), "…");
+    }
+}
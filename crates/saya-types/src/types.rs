@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone)]
@@ -10,18 +12,34 @@ pub enum AppEvent {
     UiEvent(UiEvent),
     ApiRequest(ApiRequest),
     TextInput(String),
+    LookupById(String),
+    LookupKanji(char),
+    /// Search-as-you-type query, only the latest one's results are shown
+    SearchQuery(String),
     RawTextInput {
         text: String,
         source: TextSource,
     },
     ShowResults(Vec<DisplayResult>),
+    AppendResults(Vec<DisplayResult>),
     CreateCard(DisplayResult),
+    /// Render the card for `CreateCard` without sending it to Anki
+    PreviewCard(DisplayResult),
+    CardPreview {
+        front: String,
+        back: String,
+    },
+    CopyToClipboard(String),
     TriggerOcr(CaptureRegion),
     TriggerAutoOcr(CaptureRegion),
     UpdateCaptureRegion(CaptureRegion),
     CaptureWindow {
         window_id: Option<u32>,
     },
+    /// Capture a region without OCR, answered with `CapturePreview`
+    PreviewCapture(CaptureRegion),
+    /// PNG of the region from `PreviewCapture`
+    CapturePreview(Vec<u8>),
     OcrStatusUpdate {
         status: String,
         capturing: bool,
@@ -31,8 +49,144 @@ pub enum AppEvent {
         text: String,
         from_lang: String,
         to_lang: String,
+        /// Other candidate translations, if the provider returned any
+        alternatives: Vec<String>,
     },
+    /// Translate this text regardless of `translator.scope`
+    TranslateText(String),
     HotkeyOcrTriggered,
+    ToggleClickThrough,
+    ClickThroughChanged {
+        enabled: bool,
+    },
+    /// Stop or restart acting on OCR hotkeys, auto OCR, clipboard and websocket text
+    SetWatchersPaused(bool),
+    WatchersPausedChanged {
+        paused: bool,
+    },
+    /// Whether AnkiConnect answers, and its API version when it does
+    AnkiStatus {
+        ok: bool,
+        version: Option<u32>,
+    },
+    DictionaryLoading,
+    /// `done` words parsed out of an estimated `total`
+    DictionaryProgress {
+        done: usize,
+        total: usize,
+    },
+    DictionaryReady {
+        count: usize,
+    },
+    SetupStep {
+        step: SetupStep,
+        status: SetupStatus,
+    },
+    /// A failure the user should see, alongside any status update
+    Error {
+        kind: ErrorKind,
+        message: String,
+    },
+}
+
+impl AppEvent {
+    /// Variant name for logging
+    ///
+    /// Deliberately matches every variant without a wildcard, so adding an
+    /// event fails to compile until it is named here.
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::ConfigChanged => "ConfigChanged",
+            AppEvent::ConfigUpdate { .. } => "ConfigUpdate",
+            AppEvent::UiEvent(_) => "UiEvent",
+            AppEvent::ApiRequest(_) => "ApiRequest",
+            AppEvent::TextInput(_) => "TextInput",
+            AppEvent::LookupById(_) => "LookupById",
+            AppEvent::SearchQuery(_) => "SearchQuery",
+            AppEvent::LookupKanji(_) => "LookupKanji",
+            AppEvent::RawTextInput { .. } => "RawTextInput",
+            AppEvent::ShowResults(_) => "ShowResults",
+            AppEvent::AppendResults(_) => "AppendResults",
+            AppEvent::CreateCard(_) => "CreateCard",
+            AppEvent::PreviewCard(_) => "PreviewCard",
+            AppEvent::CardPreview { .. } => "CardPreview",
+            AppEvent::CopyToClipboard(_) => "CopyToClipboard",
+            AppEvent::TriggerOcr(_) => "TriggerOcr",
+            AppEvent::TriggerAutoOcr(_) => "TriggerAutoOcr",
+            AppEvent::UpdateCaptureRegion(_) => "UpdateCaptureRegion",
+            AppEvent::CaptureWindow { .. } => "CaptureWindow",
+            AppEvent::PreviewCapture(_) => "PreviewCapture",
+            AppEvent::CapturePreview(_) => "CapturePreview",
+            AppEvent::OcrStatusUpdate { .. } => "OcrStatusUpdate",
+            AppEvent::BackendReady => "BackendReady",
+            AppEvent::ShowTranslation { .. } => "ShowTranslation",
+            AppEvent::TranslateText(_) => "TranslateText",
+            AppEvent::HotkeyOcrTriggered => "HotkeyOcrTriggered",
+            AppEvent::ToggleClickThrough => "ToggleClickThrough",
+            AppEvent::ClickThroughChanged { .. } => "ClickThroughChanged",
+            AppEvent::SetWatchersPaused(_) => "SetWatchersPaused",
+            AppEvent::WatchersPausedChanged { .. } => "WatchersPausedChanged",
+            AppEvent::AnkiStatus { .. } => "AnkiStatus",
+            AppEvent::DictionaryLoading => "DictionaryLoading",
+            AppEvent::DictionaryProgress { .. } => "DictionaryProgress",
+            AppEvent::DictionaryReady { .. } => "DictionaryReady",
+            AppEvent::SetupStep { .. } => "SetupStep",
+            AppEvent::Error { .. } => "Error",
+        }
+    }
+}
+
+/// What failed, for `AppEvent::Error`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    OcrFailed,
+    CaptureFailed,
+    TranslationFailed,
+    AnkiFailed,
+    DictionaryFailed,
+}
+
+impl ErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::OcrFailed => "OCR failed",
+            ErrorKind::CaptureFailed => "Capture failed",
+            ErrorKind::TranslationFailed => "Translation failed",
+            ErrorKind::AnkiFailed => "Anki failed",
+            ErrorKind::DictionaryFailed => "Dictionary failed",
+        }
+    }
+}
+
+/// First-run setup steps, in the order they run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupStep {
+    OcrLanguage,
+    Clipboard,
+    TranslatorKey,
+    AnkiUrl,
+    Complete,
+}
+
+impl SetupStep {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SetupStep::OcrLanguage => "OCR language",
+            SetupStep::Clipboard => "Clipboard access",
+            SetupStep::TranslatorKey => "DeepL API key",
+            SetupStep::AnkiUrl => "Anki URL",
+            SetupStep::Complete => "Setup complete",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetupStatus {
+    Passed,
+    Failed(String),
+    /// Optional step the user can fill in, naming the config field to set
+    NeedsInput(String),
+    Skipped,
 }
 
 #[derive(Debug, Clone)]
@@ -43,18 +197,121 @@ pub enum TextSource {
     Manual,
 }
 
+/// Definitions shown before a result needs expanding
+pub const PREVIEW_DEFINITIONS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum JlptLevel {
+    N5, // Beginner (~800 words)
+    N4, // Elementary (~1500 words)
+    N3, // Intermediate (~3750 words)
+    N2, // Upper intermediate (~6000 words)
+    N1, // Advanced (~10000 words)
+}
+
+impl JlptLevel {
+    /// Level named like "N3", in any case
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_uppercase().as_str() {
+            "N5" => Some(JlptLevel::N5),
+            "N4" => Some(JlptLevel::N4),
+            "N3" => Some(JlptLevel::N3),
+            "N2" => Some(JlptLevel::N2),
+            "N1" => Some(JlptLevel::N1),
+            _ => None,
+        }
+    }
+
+    /// Level from its badge, which looks like "🟢 N5"
+    pub fn from_badge(badge: &str) -> Option<Self> {
+        badge.split_whitespace().last().and_then(Self::from_name)
+    }
+
+    /// Get level string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JlptLevel::N5 => "N5",
+            JlptLevel::N4 => "N4",
+            JlptLevel::N3 => "N3",
+            JlptLevel::N2 => "N2",
+            JlptLevel::N1 => "N1",
+        }
+    }
+
+    /// Level number, 5 for N5
+    pub fn number(&self) -> u8 {
+        match self {
+            JlptLevel::N5 => 5,
+            JlptLevel::N4 => 4,
+            JlptLevel::N3 => 3,
+            JlptLevel::N2 => 2,
+            JlptLevel::N1 => 1,
+        }
+    }
+
+    /// Get level description
+    pub fn description(&self) -> &'static str {
+        match self {
+            JlptLevel::N5 => "N5 (Beginner)",
+            JlptLevel::N4 => "N4 (Elementary)",
+            JlptLevel::N3 => "N3 (Intermediate)",
+            JlptLevel::N2 => "N2 (Upper Intermediate)",
+            JlptLevel::N1 => "N1 (Advanced)",
+        }
+    }
+
+    /// Get color badge
+    pub fn badge(&self) -> String {
+        match self {
+            JlptLevel::N5 => "🟢 N5".to_string(),
+            JlptLevel::N4 => "🟡 N4".to_string(),
+            JlptLevel::N3 => "🟠 N3".to_string(),
+            JlptLevel::N2 => "🔴 N2".to_string(),
+            JlptLevel::N1 => "🟣 N1".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DisplayResult {
     pub term: String,
-    pub reading: String,
+    pub readings: Vec<String>,
+    pub definitions: Vec<String>,
+    /// Collapsed preview of the definitions, shown while `truncated`
     pub definition: String,
+    /// Every definition joined, for expanding the preview
+    pub full_definition: String,
+    /// Has more definitions than the collapsed preview shows
+    pub truncated: bool,
     pub frequency: Option<String>,
     pub pitch_accent: Option<String>,
     pub jlpt_level: Option<String>,
+    /// Level behind `jlpt_level`, kept when the badge is hidden
+    pub jlpt: Option<JlptLevel>,
     pub conjugation: Option<String>,
+    /// How likely the deconjugation behind `conjugation` is right, 0-1
+    pub conjugation_confidence: Option<f32>,
+    pub part_of_speech: Option<String>,
+    /// Domains such as "med, comp" that some meanings are specific to
+    pub fields: Option<String>,
+    /// Name of the dictionary the result came from
+    pub source: Option<String>,
+    /// Kanji stroke count and components, for single-kanji results
+    pub breakdown: Option<String>,
+    /// Example sentences as (Japanese, translation) pairs
+    pub examples: Vec<(String, String)>,
+    /// Dictionary entry ID, for fetching the full entry
+    pub entry_id: Option<String>,
+    /// Dictionary form the result was found as, whatever `term` displays
+    pub base_form: Option<String>,
+    /// Text as it was captured, before deconjugation
+    pub surface: Option<String>,
+    /// Metadata keys picked in `ui.show_badges`, shown as generic badges
+    pub metadata: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+/// Screen area in physical pixels, as the monitors report their bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CaptureRegion {
     pub x: i32,
     pub y: i32,
@@ -62,6 +319,68 @@ pub struct CaptureRegion {
     pub height: u32,
 }
 
+impl CaptureRegion {
+    /// Shrink the region so it lies within `bounds`, keeping it at least 1x1
+    pub fn clamp_to(self, bounds: CaptureRegion) -> CaptureRegion {
+        let right = bounds.x + bounds.width.max(1) as i32;
+        let bottom = bounds.y + bounds.height.max(1) as i32;
+
+        let x = self.x.clamp(bounds.x, right - 1);
+        let y = self.y.clamp(bounds.y, bottom - 1);
+        let x_end = (self.x + self.width as i32).clamp(x + 1, right);
+        let y_end = (self.y + self.height as i32).clamp(y + 1, bottom);
+
+        CaptureRegion {
+            x,
+            y,
+            width: (x_end - x) as u32,
+            height: (y_end - y) as u32,
+        }
+    }
+}
+
+/// Monitor a capture region is taken from, set by `ocr.capture_monitor`
+///
+/// `Auto` takes the monitor holding the region. A forced monitor reads the
+/// region relative to its own top-left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(from = "MonitorSetting", into = "MonitorSetting")]
+pub enum CaptureMonitor {
+    #[default]
+    Auto,
+    /// Position in `saya_ocr::list_monitors`
+    Index(usize),
+    Name(String),
+}
+
+/// `ocr.capture_monitor` as written: "auto", an index or a monitor name
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum MonitorSetting {
+    Index(usize),
+    Name(String),
+}
+
+impl From<MonitorSetting> for CaptureMonitor {
+    fn from(setting: MonitorSetting) -> Self {
+        match setting {
+            MonitorSetting::Index(index) => CaptureMonitor::Index(index),
+            MonitorSetting::Name(name) if name.eq_ignore_ascii_case("auto") => CaptureMonitor::Auto,
+            MonitorSetting::Name(name) => CaptureMonitor::Name(name),
+        }
+    }
+}
+
+impl From<CaptureMonitor> for MonitorSetting {
+    fn from(monitor: CaptureMonitor) -> Self {
+        match monitor {
+            CaptureMonitor::Auto => MonitorSetting::Name("auto".to_string()),
+            CaptureMonitor::Index(index) => MonitorSetting::Index(index),
+            CaptureMonitor::Name(name) => MonitorSetting::Name(name),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum UiEvent {
     SearchText(String),
@@ -73,3 +392,38 @@ pub enum UiEvent {
 
 #[derive(Debug, Clone)]
 pub enum ApiRequest {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGION: CaptureRegion = CaptureRegion {
+        x: 0,
+        y: 0,
+        width: 10,
+        height: 10,
+    };
+
+    #[test]
+    fn test_canonical_variants_are_named() {
+        let events = [
+            AppEvent::TriggerOcr(REGION),
+            AppEvent::TriggerAutoOcr(REGION),
+            AppEvent::UpdateCaptureRegion(REGION),
+            AppEvent::CaptureWindow { window_id: None },
+            AppEvent::HotkeyOcrTriggered,
+        ];
+        let names: Vec<&str> = events.iter().map(AppEvent::name).collect();
+
+        assert_eq!(
+            names,
+            [
+                "TriggerOcr",
+                "TriggerAutoOcr",
+                "UpdateCaptureRegion",
+                "CaptureWindow",
+                "HotkeyOcrTriggered",
+            ]
+        );
+    }
+}
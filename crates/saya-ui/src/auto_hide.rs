@@ -0,0 +1,109 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use kanal::Sender;
+use saya_types::{AppEvent, UiEvent};
+
+/// Hides the overlay once no new text has arrived for a while
+///
+/// Every event carrying new text restarts the countdown; the overlay shows
+/// itself again when that text is displayed.
+pub struct AutoHide {
+    delay: Option<Duration>,
+    generation: Arc<AtomicU64>,
+}
+
+impl AutoHide {
+    /// `delay_ms` of 0 never hides
+    pub fn new(delay_ms: u64) -> Self {
+        Self {
+            delay: (delay_ms > 0).then(|| Duration::from_millis(delay_ms)),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Look at an event on its way to the UI, restarting the countdown on new text
+    pub fn observe(&self, event: &AppEvent, to_ui: &Sender<AppEvent>) {
+        let Some(delay) = self.delay else {
+            return;
+        };
+        if !matches!(
+            event,
+            AppEvent::RawTextInput { .. } | AppEvent::ShowResults(_) | AppEvent::AppendResults(_)
+        ) {
+            return;
+        }
+
+        let armed = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let generation = self.generation.clone();
+        let to_ui = to_ui.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            if generation.load(Ordering::SeqCst) == armed {
+                tracing::debug!("[UI] Idle for {:?}, hiding overlay", delay);
+                let _ = to_ui.send(AppEvent::UiEvent(UiEvent::Hide));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use saya_types::TextSource;
+
+    fn text(text: &str) -> AppEvent {
+        AppEvent::RawTextInput {
+            text: text.to_string(),
+            source: TextSource::Manual,
+        }
+    }
+
+    fn hides(rx: &kanal::Receiver<AppEvent>) -> usize {
+        std::iter::from_fn(|| rx.try_recv().ok().flatten())
+            .filter(|event| matches!(event, AppEvent::UiEvent(UiEvent::Hide)))
+            .count()
+    }
+
+    #[tokio::test]
+    async fn test_hides_after_idle_period() {
+        let (tx, rx) = kanal::unbounded();
+        let auto_hide = AutoHide::new(50);
+
+        auto_hide.observe(&text("本"), &tx);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(hides(&rx), 0);
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert_eq!(hides(&rx), 1);
+    }
+
+    #[tokio::test]
+    async fn test_new_text_cancels_pending_hide() {
+        let (tx, rx) = kanal::unbounded();
+        let auto_hide = AutoHide::new(80);
+
+        auto_hide.observe(&text("本"), &tx);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        auto_hide.observe(&AppEvent::ShowResults(vec![]), &tx);
+
+        // The first countdown would have fired by now
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(hides(&rx), 0);
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert_eq!(hides(&rx), 1);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_and_unrelated_events_never_hide() {
+        let (tx, rx) = kanal::unbounded();
+
+        AutoHide::new(0).observe(&text("本"), &tx);
+        AutoHide::new(10).observe(&AppEvent::BackendReady, &tx);
+
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert_eq!(hides(&rx), 0);
+    }
+}
@@ -1,16 +1,19 @@
 use std::sync::Mutex;
 
-use saya_types::{AppEvent, DisplayResult, TextSource, UiEvent};
-use slint::{ComponentHandle, Weak};
+use saya_types::{AppEvent, DisplayResult, SetupStatus, SetupStep, TextSource, UiEvent};
+use slint::{ComponentHandle, Model, Weak};
 
+use crate::state::OverlayView;
 use crate::{DictResult, OcrWindow, OverlayWindow};
 
 pub fn handle_events(
     event: AppEvent,
     window_weak: Weak<OverlayWindow>,
     ocr_weak: Weak<OcrWindow>,
-    results_store: &Mutex<Vec<DisplayResult>>,
+    view: &Mutex<OverlayView>,
 ) {
+    view.lock().unwrap().apply(&event);
+
     match event {
         AppEvent::UiEvent(UiEvent::Show) => {
             if let Some(w) = window_weak.upgrade() {
@@ -51,26 +54,37 @@ pub fn handle_events(
         AppEvent::ShowResults(results) => {
             if let Some(w) = window_weak.upgrade() {
                 tracing::debug!("[SLINT] Showing {} results", results.len());
-                *results_store.lock().unwrap() = results.clone();
-
-                let slint_results: Vec<DictResult> = results
-                    .into_iter()
-                    .map(|r| DictResult {
-                        term: r.term.into(),
-                        reading: r.reading.into(),
-                        definition: r.definition.into(),
-                        frequency: r.frequency.unwrap_or_default().into(),
-                        pitch_accent: r.pitch_accent.unwrap_or_default().into(),
-                        jlpt_level: r.jlpt_level.unwrap_or_default().into(),
-                        conjugation: r.conjugation.unwrap_or_default().into(),
-                    })
-                    .collect();
+
+                let slint_results: Vec<DictResult> =
+                    results.into_iter().map(to_dict_result).collect();
 
                 let model = std::rc::Rc::new(slint::VecModel::from(slint_results));
                 w.set_results(model.into());
                 w.show().ok();
             }
         }
+        AppEvent::AppendResults(results) => {
+            let view = view.lock().unwrap();
+            if let Some(w) = window_weak.upgrade() {
+                tracing::debug!("[SLINT] Appending {} results", results.len());
+
+                let model = w.get_results();
+                match model.as_any().downcast_ref::<slint::VecModel<DictResult>>() {
+                    Some(vec_model) => {
+                        for r in results {
+                            vec_model.push(to_dict_result(r));
+                        }
+                    }
+                    None => {
+                        // Model was never populated by ShowResults, rebuild from the store
+                        let slint_results: Vec<DictResult> =
+                            view.results.iter().cloned().map(to_dict_result).collect();
+                        let model = std::rc::Rc::new(slint::VecModel::from(slint_results));
+                        w.set_results(model.into());
+                    }
+                }
+            }
+        }
         AppEvent::OcrStatusUpdate { status, capturing } => {
             if let Some(w) = ocr_weak.upgrade() {
                 tracing::debug!("[SLINT] OCR status: {} (capturing: {})", status, capturing);
@@ -85,16 +99,204 @@ pub fn handle_events(
                 w.set_status("Ready".into());
             }
         }
+        AppEvent::DictionaryLoading => {
+            if let Some(w) = ocr_weak.upgrade() {
+                tracing::debug!("[SLINT] Dictionary loading");
+                w.set_status("Loading dictionary...".into());
+            }
+        }
+        AppEvent::DictionaryProgress { done, total } => {
+            if let Some(w) = ocr_weak.upgrade() {
+                let percent = (done * 100).checked_div(total).unwrap_or(0).min(100);
+                w.set_status(format!("Loading dictionary... {}%", percent).into());
+            }
+        }
+        AppEvent::DictionaryReady { count } => {
+            if let Some(w) = ocr_weak.upgrade() {
+                tracing::debug!("[SLINT] Dictionary ready: {} entries", count);
+                w.set_status(format!("Dictionary ready ({} words)", count).into());
+            }
+        }
+        AppEvent::SetupStep { step, status } => {
+            if let Some(w) = ocr_weak.upgrade() {
+                tracing::debug!("[SLINT] Setup step {:?}: {:?}", step, status);
+                w.set_status(setup_message(step, &status).into());
+            }
+        }
+        AppEvent::Error { kind, message } => {
+            if let Some(w) = ocr_weak.upgrade() {
+                tracing::debug!("[SLINT] Error {:?}: {}", kind, message);
+                w.set_status(format!("{}: {}", kind.as_str(), message).into());
+                w.set_is_capturing(false);
+            }
+        }
+        AppEvent::ClickThroughChanged { enabled } => {
+            if let Some(w) = window_weak.upgrade() {
+                match crate::platform::set_click_through(w.window(), enabled) {
+                    Ok(()) => tracing::debug!("[SLINT] Click-through: {}", enabled),
+                    Err(e) => tracing::warn!("[SLINT] Failed to set click-through: {}", e),
+                }
+            }
+        }
+        AppEvent::WatchersPausedChanged { paused } => {
+            if let Some(w) = window_weak.upgrade() {
+                w.set_watchers_paused(paused);
+            }
+            if let Some(w) = ocr_weak.upgrade() {
+                let mut view = view.lock().unwrap();
+                if paused {
+                    view.status_before_pause
+                        .get_or_insert_with(|| w.get_status().to_string());
+                    w.set_status("Paused".into());
+                } else if let Some(status) = view.status_before_pause.take()
+                    && w.get_status() == "Paused"
+                {
+                    w.set_status(status.into());
+                }
+            }
+        }
+        AppEvent::AnkiStatus { ok, version } => {
+            if let Some(w) = window_weak.upgrade() {
+                tracing::debug!("[SLINT] Anki status: {} (version {:?})", ok, version);
+                w.set_anki_known(true);
+                w.set_anki_connected(ok);
+            }
+        }
+        AppEvent::CardPreview { front, back } => {
+            if let Some(w) = window_weak.upgrade() {
+                w.set_card_preview_front(front.into());
+                w.set_card_preview_back(back.into());
+                w.set_card_preview_visible(true);
+            }
+        }
         AppEvent::ShowTranslation {
             text,
             from_lang,
             to_lang,
+            alternatives,
         } => {
             if let Some(w) = window_weak.upgrade() {
                 tracing::debug!("[SLINT] Translation: {} -> {}", from_lang, to_lang);
                 w.set_translation(text.into());
+                let alternatives: Vec<slint::SharedString> =
+                    alternatives.into_iter().map(Into::into).collect();
+                w.set_translation_alternatives(
+                    std::rc::Rc::new(slint::VecModel::from(alternatives)).into(),
+                );
             }
         }
         _ => {}
     };
 }
+
+fn setup_message(step: SetupStep, status: &SetupStatus) -> String {
+    match status {
+        SetupStatus::Passed => format!("{}: OK", step.as_str()),
+        SetupStatus::Failed(reason) => format!("{}: {}", step.as_str(), reason),
+        SetupStatus::NeedsInput(hint) => format!("{}: set {} in settings", step.as_str(), hint),
+        SetupStatus::Skipped => format!("{}: skipped", step.as_str()),
+    }
+}
+
+/// Deconjugations less likely than this are grayed out
+const UNCERTAIN_CONJUGATION: f32 = 0.7;
+
+fn to_dict_result(r: DisplayResult) -> DictResult {
+    DictResult {
+        term: r.term.into(),
+        reading: r.readings.join(", ").into(),
+        definition: if r.truncated {
+            r.definition.into()
+        } else {
+            r.full_definition.clone().into()
+        },
+        full_definition: r.full_definition.into(),
+        truncated: r.truncated,
+        frequency: r.frequency.unwrap_or_default().into(),
+        pitch_accent: r.pitch_accent.unwrap_or_default().into(),
+        jlpt_level: r.jlpt_level.unwrap_or_default().into(),
+        conjugation: r.conjugation.unwrap_or_default().into(),
+        conjugation_uncertain: r
+            .conjugation_confidence
+            .is_some_and(|c| c < UNCERTAIN_CONJUGATION),
+        part_of_speech: r.part_of_speech.unwrap_or_default().into(),
+        fields: r.fields.unwrap_or_default().into(),
+        source: r.source.unwrap_or_default().into(),
+        breakdown: r.breakdown.unwrap_or_default().into(),
+        kanji: std::rc::Rc::new(slint::VecModel::from(kanji_in(&r.term))).into(),
+        example: r
+            .examples
+            .first()
+            .map(|(japanese, translation)| format!("{}\n{}", japanese, translation))
+            .unwrap_or_default()
+            .into(),
+        extra: std::rc::Rc::new(slint::VecModel::from(extra_badges(&r))).into(),
+    }
+}
+
+/// Generic metadata as "key: value" badges, in key order
+fn extra_badges(r: &DisplayResult) -> Vec<slint::SharedString> {
+    let mut entries: Vec<_> = r.metadata.iter().collect();
+    entries.sort();
+    entries
+        .into_iter()
+        .map(|(key, value)| format!("{}: {}", key.replace('_', " "), value).into())
+        .collect()
+}
+
+/// Kanji in `term`, each clickable for its breakdown
+fn kanji_in(term: &str) -> Vec<slint::SharedString> {
+    term.chars()
+        .filter(|c| saya_types::is_kanji(*c))
+        .map(|c| c.to_string().into())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(term: &str) -> DisplayResult {
+        DisplayResult {
+            term: term.to_string(),
+            readings: vec![],
+            definitions: vec![],
+            definition: String::new(),
+            full_definition: String::new(),
+            truncated: false,
+            frequency: None,
+            pitch_accent: None,
+            jlpt_level: None,
+            jlpt: None,
+            conjugation: None,
+            conjugation_confidence: None,
+            part_of_speech: None,
+            fields: None,
+            source: None,
+            breakdown: None,
+            examples: vec![],
+            entry_id: None,
+            base_form: None,
+            surface: None,
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_results_keep_raw_text() {
+        let view = Mutex::new(OverlayView::default());
+        let handle = |event| handle_events(event, Weak::default(), Weak::default(), &view);
+
+        handle(AppEvent::RawTextInput {
+            text: "猫が食べた".to_string(),
+            source: TextSource::Ocr,
+        });
+        handle(AppEvent::ShowResults(vec![result("猫")]));
+        handle(AppEvent::AppendResults(vec![result("食べる")]));
+
+        let view = view.lock().unwrap();
+        assert_eq!(view.raw_text, "猫が食べた");
+        let terms: Vec<&str> = view.results.iter().map(|r| r.term.as_str()).collect();
+        assert_eq!(terms, vec!["猫", "食べる"]);
+    }
+}
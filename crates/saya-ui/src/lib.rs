@@ -1,13 +1,18 @@
 use std::sync::{Arc, Mutex};
 
+use auto_hide::AutoHide;
 use events::handle_events;
 use kanal::{AsyncReceiver, AsyncSender, Receiver, Sender};
 use saya_config::Config;
-use saya_types::{AppEvent, CaptureRegion, DisplayResult};
+use saya_config::ui::UiConfig;
+use saya_types::{AppEvent, CaptureRegion, DisplayResult, truncate_display};
+use state::OverlayView;
 use tokio::sync::RwLock;
 
+pub mod auto_hide;
 pub mod bridge;
 pub mod events;
+pub mod platform;
 pub mod state;
 
 slint::include_modules!();
@@ -38,6 +43,23 @@ fn parse_color(hex: &str) -> Result<slint::Color, String> {
     Ok(slint::Color::from_argb_u8(a, r, g, b))
 }
 
+/// The text between two byte offsets of `text`, in either order, trimmed
+///
+/// `None` when the selection is empty or the offsets aren't on char boundaries.
+pub fn selected_text(text: &str, anchor: i32, cursor: i32) -> Option<String> {
+    let (start, end) = (anchor.min(cursor), anchor.max(cursor));
+    let selection = text.get(usize::try_from(start).ok()?..usize::try_from(end).ok()?)?;
+    let selection = selection.trim();
+    (!selection.is_empty()).then(|| selection.to_string())
+}
+
+/// Apply overlay opacity and text size from config
+fn apply_ui_config(window: &OverlayWindow, ui: &UiConfig) {
+    window.set_background_opacity(ui.clamped_opacity());
+    window.set_font_size_pt(ui.clamped_font_size());
+    window.set_show_raw_text(ui.show_raw_text);
+}
+
 pub async fn ui_loop(
     app_to_ui_rx: AsyncReceiver<AppEvent>,
     ui_to_app_tx: AsyncSender<AppEvent>,
@@ -48,8 +70,17 @@ pub async fn ui_loop(
     let (sync_tx, sync_rx) = kanal::unbounded::<AppEvent>();
     let (app_sync_tx, app_sync_rx) = kanal::unbounded::<AppEvent>();
 
+    let shared_config = config.clone();
     let config = config.read().await.clone();
-    let ui_thread = std::thread::spawn(move || run_slint_ui(sync_tx, app_sync_rx, &config));
+    let auto_hide = AutoHide::new(config.ui.auto_hide_ms);
+    let (exited_tx, mut exited_rx) = tokio::sync::oneshot::channel::<()>();
+    let ui_thread = std::thread::Builder::new()
+        .name("saya-ui".to_string())
+        .spawn(move || {
+            // Dropped on return and on unwind, so a panic ends the loop below too
+            let _exited = exited_tx;
+            run_slint_ui(sync_tx, app_sync_rx, &config, shared_config)
+        })?;
 
     let forward_to_ui = tokio::spawn({
         async move {
@@ -59,6 +90,7 @@ pub async fn ui_loop(
                     "[UI] Forwarding app->ui: {:?}",
                     std::mem::discriminant(&event)
                 );
+                auto_hide.observe(&event, &app_sync_tx);
                 if app_sync_tx.send(event).is_err() {
                     break;
                 }
@@ -67,30 +99,39 @@ pub async fn ui_loop(
     });
 
     tracing::info!("[UI] Forwarding events from UI to app");
-    while let Ok(event) = sync_rx.as_async().recv().await {
-        tracing::info!(
-            "[UI] Forwarding ui->app: {:?}",
-            std::mem::discriminant(&event)
-        );
-        if let Err(e) = ui_to_app_tx.send(event).await {
-            tracing::error!("[UI] Failed to forward event: {}", e);
-            break;
+    let forward_to_app = async {
+        while let Ok(event) = sync_rx.as_async().recv().await {
+            tracing::info!(
+                "[UI] Forwarding ui->app: {:?}",
+                std::mem::discriminant(&event)
+            );
+            if let Err(e) = ui_to_app_tx.send(event).await {
+                tracing::error!("[UI] Failed to forward event: {}", e);
+                break;
+            }
         }
+    };
+    tokio::select! {
+        _ = forward_to_app => {}
+        _ = &mut exited_rx => {}
     }
 
     forward_to_ui.abort();
-    if let Err(e) = ui_thread.join() {
-        tracing::error!("[UI] UI thread panicked: {:?}", e);
-    }
+    let result = match ui_thread.join() {
+        Ok(result) => result,
+        // Details were logged by the panic hook; failing here shuts the app down
+        Err(_) => Err(anyhow::anyhow!("UI thread panicked")),
+    };
 
     tracing::info!("UI loop exiting");
-    Ok(())
+    result
 }
 
 fn run_slint_ui(
     ui_to_app_tx: Sender<AppEvent>,
     app_to_ui_rx: Receiver<AppEvent>,
     config: &Config,
+    shared_config: Arc<RwLock<Config>>,
 ) -> anyhow::Result<()> {
     tracing::info!("[SLINT] UI thread starting");
 
@@ -103,6 +144,7 @@ fn run_slint_ui(
 
     ocr_window.set_auto_capturing_mode(ocr_auto);
     window.set_ocr_auto_mode(ocr_auto);
+    apply_ui_config(&window, &config.ui);
 
     // Set border colors from config
     if let Ok(color) = parse_color(&config.ocr.border_ready_color) {
@@ -132,18 +174,7 @@ fn run_slint_ui(
                 if let Some(win) = ocr_weak.upgrade() {
                     // Only send updates if auto mode is enabled
                     if win.get_auto_capturing_mode() {
-                        let pos = win.window().position();
-                        let size = win.window().size();
-
-                        let header_height = 32i32;
-                        let capture_height = size.height.saturating_sub(32);
-
-                        let region = CaptureRegion {
-                            x: pos.x,
-                            y: pos.y + header_height,
-                            width: size.width,
-                            height: capture_height,
-                        };
+                        let region = window_capture_region(win.window());
 
                         let _ = tx.send(AppEvent::UpdateCaptureRegion(region));
                     }
@@ -164,7 +195,7 @@ fn run_slint_ui(
                     .iter()
                     .map(|(id, title)| {
                         stored_ids.push(*id);
-                        title.chars().take(40).collect::<String>().into()
+                        truncate_display(title, WINDOW_TITLE_WIDTH).into()
                     })
                     .collect();
 
@@ -205,18 +236,11 @@ fn run_slint_ui(
 
         ocr_window.on_window_resized(move || {
             if let Some(win) = ocr_weak.upgrade() {
-                let pos = win.window().position();
-                let size = win.window().size();
-
-                let header_height = 32i32;
-                let capture_height = size.height.saturating_sub(32);
-
-                let region = CaptureRegion {
-                    x: pos.x,
-                    y: pos.y + header_height,
-                    width: size.width,
-                    height: capture_height,
-                };
+                let window = win.window();
+                let unclamped =
+                    capture_region(window.position(), window.size(), window.scale_factor());
+                win.set_region_too_small(below_min_capture_size(unclamped));
+                let region = clamp_capture_region(unclamped);
 
                 tracing::debug!("[SLINT] Window resized, updating region: {:?}", region);
                 let _ = tx.send(AppEvent::UpdateCaptureRegion(region));
@@ -249,18 +273,7 @@ fn run_slint_ui(
 
                     // If enabling auto mode, trigger auto OCR with current region
                     if new_mode {
-                        let pos = ocr_win.window().position();
-                        let size = ocr_win.window().size();
-
-                        let header_height = 32i32;
-                        let capture_height = size.height.saturating_sub(32);
-
-                        let region = CaptureRegion {
-                            x: pos.x,
-                            y: pos.y + header_height,
-                            width: size.width,
-                            height: capture_height,
-                        };
+                        let region = window_capture_region(ocr_win.window());
 
                         let _ = tx.send(AppEvent::TriggerAutoOcr(region));
                     }
@@ -275,6 +288,20 @@ fn run_slint_ui(
         });
     }
 
+    // Pause/resume watchers (from main window), updated on WatchersPausedChanged
+    {
+        let window_weak_clone = window_weak.clone();
+        let tx = ui_to_app_tx.clone();
+
+        window.on_toggle_watchers_paused(move || {
+            if let Some(win) = window_weak_clone.upgrade() {
+                let paused = !win.get_watchers_paused();
+                tracing::info!("[SLINT] Watchers paused: {}", paused);
+                let _ = tx.send(AppEvent::SetWatchersPaused(paused));
+            }
+        });
+    }
+
     // OCR capture/stop button (from main window)
     {
         let window_weak_clone = window_weak.clone();
@@ -293,18 +320,7 @@ fn run_slint_ui(
                 } else {
                     // Trigger single capture
                     if let Some(ocr_win) = ocr_weak.upgrade() {
-                        let pos = ocr_win.window().position();
-                        let size = ocr_win.window().size();
-
-                        let header_height = 32i32;
-                        let capture_height = size.height.saturating_sub(32);
-
-                        let region = CaptureRegion {
-                            x: pos.x,
-                            y: pos.y + header_height,
-                            width: size.width,
-                            height: capture_height,
-                        };
+                        let region = window_capture_region(ocr_win.window());
 
                         tracing::info!("[SLINT] Manual capture triggered");
                         let _ = tx.send(AppEvent::TriggerOcr(region));
@@ -326,12 +342,7 @@ fn run_slint_ui(
                 win.set_is_capturing(true);
                 win.set_status("".into());
 
-                let pos = win.window().position();
-                let size = win.window().size();
-
-                // Calculate capture zone (exclude header 32px)
-                let header_height = 32i32;
-                let capture_height = size.height.saturating_sub(32);
+                let region = window_capture_region(win.window());
 
                 let selected_idx = win.get_selected_window_index();
                 let window_id = if selected_idx >= 0 {
@@ -342,20 +353,14 @@ fn run_slint_ui(
 
                 tracing::info!(
                     "[SLINT] Capturing region: {}x{} at ({}, {}), window: {:?}",
-                    size.width,
-                    capture_height,
-                    pos.x,
-                    pos.y + header_height,
+                    region.width,
+                    region.height,
+                    region.x,
+                    region.y,
                     window_id
                 );
 
                 // Always send with region coordinates
-                let region = CaptureRegion {
-                    x: pos.x,
-                    y: pos.y + header_height,
-                    width: size.width,
-                    height: capture_height,
-                };
 
                 let _ = send_capture_region(region, tx.clone(), ocr_auto);
             }
@@ -369,7 +374,7 @@ fn run_slint_ui(
             .iter()
             .map(|(id, title)| {
                 stored_ids.push(*id);
-                title.chars().take(40).collect::<String>().into()
+                truncate_display(title, WINDOW_TITLE_WIDTH).into()
             })
             .collect();
 
@@ -381,7 +386,7 @@ fn run_slint_ui(
     ocr_window.show()?;
     tracing::debug!("[SLINT] OCR window shown");
 
-    let results_store = Arc::new(Mutex::new(Vec::<DisplayResult>::new()));
+    let view = Arc::new(Mutex::new(OverlayView::default()));
 
     // Show config overlay handler
     {
@@ -394,36 +399,110 @@ fn run_slint_ui(
         });
     }
 
+    // Result waiting for the user to confirm its card preview
+    let pending_card = Arc::new(Mutex::new(None::<DisplayResult>));
+
     {
-        let results_clone = results_store.clone();
+        let view = view.clone();
+        let pending_card = pending_card.clone();
         let tx = ui_to_app_tx.clone();
         window.on_add_to_anki(move |idx| {
-            let results = results_clone.lock().unwrap();
-            if let Some(result) = results.get(idx as usize) {
-                let result = result.clone();
-                if let Err(e) = tx.send(AppEvent::CreateCard(result)) {
-                    tracing::error!("[SLINT] Failed to send CreateCard: {}", e);
+            if let Some(result) = view.lock().unwrap().results.get(idx as usize) {
+                *pending_card.lock().unwrap() = Some(result.clone());
+                if let Err(e) = tx.send(AppEvent::PreviewCard(result.clone())) {
+                    tracing::error!("[SLINT] Failed to send PreviewCard: {}", e);
+                }
+            }
+        });
+    }
+
+    {
+        let pending_card = pending_card.clone();
+        let tx = ui_to_app_tx.clone();
+        window.on_confirm_card(move || {
+            if let Some(result) = pending_card.lock().unwrap().take()
+                && let Err(e) = tx.send(AppEvent::CreateCard(result))
+            {
+                tracing::error!("[SLINT] Failed to send CreateCard: {}", e);
+            }
+        });
+    }
+
+    {
+        let view = view.clone();
+        let tx = ui_to_app_tx.clone();
+        window.on_copy_term(move |idx| {
+            if let Some(result) = view.lock().unwrap().results.get(idx as usize) {
+                let term = result.term.clone();
+                if let Err(e) = tx.send(AppEvent::CopyToClipboard(term)) {
+                    tracing::error!("[SLINT] Failed to send CopyToClipboard: {}", e);
                 }
             }
         });
     }
 
+    {
+        let view = view.clone();
+        let tx = ui_to_app_tx.clone();
+        window.on_translate_term(move |idx| {
+            if let Some(result) = view.lock().unwrap().results.get(idx as usize) {
+                let term = result.term.clone();
+                if let Err(e) = tx.send(AppEvent::TranslateText(term)) {
+                    tracing::error!("[SLINT] Failed to send TranslateText: {}", e);
+                }
+            }
+        });
+    }
+
+    {
+        let tx = ui_to_app_tx.clone();
+        window.on_lookup_kanji(move |ch| {
+            if let Some(ch) = ch.chars().next()
+                && let Err(e) = tx.send(AppEvent::LookupKanji(ch))
+            {
+                tracing::error!("[SLINT] Failed to send LookupKanji: {}", e);
+            }
+        });
+    }
+
+    {
+        let tx = ui_to_app_tx.clone();
+        window.on_lookup_selection(move |text, anchor, cursor| {
+            if let Some(selection) = selected_text(&text, anchor, cursor)
+                && let Err(e) = tx.send(AppEvent::TextInput(selection))
+            {
+                tracing::error!("[SLINT] Failed to send TextInput: {}", e);
+            }
+        });
+    }
+
     {
         let window_weak = window_weak.clone();
         let ocr_weak = ocr_window_weak.clone();
-        let results_store = results_store.clone();
+        let view = view.clone();
 
         std::thread::spawn(move || {
             tracing::info!("[SLINT-RX] Event receiver thread started");
             while let Ok(event) = app_to_ui_rx.recv() {
                 tracing::debug!("[SLINT-RX] Received: {:?}", std::mem::discriminant(&event));
 
+                if matches!(event, AppEvent::ConfigChanged) {
+                    let ui = shared_config.blocking_read().ui.clone();
+                    let window_weak = window_weak.clone();
+                    let _ = slint::invoke_from_event_loop(move || {
+                        if let Some(w) = window_weak.upgrade() {
+                            apply_ui_config(&w, &ui);
+                        }
+                    });
+                    continue;
+                }
+
                 let window_weak = window_weak.clone();
                 let ocr_weak = ocr_weak.clone();
-                let results_store = results_store.clone();
+                let view = view.clone();
 
                 let _ = slint::invoke_from_event_loop(move || {
-                    handle_events(event, window_weak, ocr_weak, &results_store);
+                    handle_events(event, window_weak, ocr_weak, &view);
                 });
             }
             tracing::info!("[SLINT-RX] Event receiver thread stopped");
@@ -431,6 +510,11 @@ fn run_slint_ui(
     }
 
     window.show()?;
+    if config.ui.click_through
+        && let Err(e) = platform::set_click_through(window.window(), true)
+    {
+        tracing::warn!("[SLINT] Failed to enable click-through: {}", e);
+    }
     tracing::info!("[SLINT] Running event loop");
 
     slint::run_event_loop()?;
@@ -439,6 +523,61 @@ fn run_slint_ui(
     Ok(())
 }
 
+/// Columns of a window title shown in the OCR window list
+pub const WINDOW_TITLE_WIDTH: usize = 40;
+
+/// Height of the OCR window's header bar in logical px, which is left out of captures
+pub const CAPTURE_HEADER_HEIGHT: u32 = 32;
+
+/// Screen area under the OCR window, below its header
+///
+/// `position` and `size` are physical, so the header is scaled by the
+/// window's `scale_factor` to match.
+pub fn capture_region(
+    position: slint::PhysicalPosition,
+    size: slint::PhysicalSize,
+    scale_factor: f32,
+) -> CaptureRegion {
+    let header = (CAPTURE_HEADER_HEIGHT as f32 * scale_factor).round() as u32;
+    CaptureRegion {
+        x: position.x,
+        y: position.y + header as i32,
+        width: size.width,
+        height: size.height.saturating_sub(header),
+    }
+}
+
+/// Smallest region sent for capture, in pixels per side
+pub const MIN_CAPTURE_SIZE: u32 = 20;
+
+/// `region` grown to at least [`MIN_CAPTURE_SIZE`] on each side
+///
+/// Empty regions stay empty, so capturing them fails with `OcrError::EmptyRegion`
+/// instead of reading pixels the window doesn't cover.
+pub fn clamp_capture_region(region: CaptureRegion) -> CaptureRegion {
+    if region.width == 0 || region.height == 0 {
+        return region;
+    }
+    CaptureRegion {
+        width: region.width.max(MIN_CAPTURE_SIZE),
+        height: region.height.max(MIN_CAPTURE_SIZE),
+        ..region
+    }
+}
+
+/// Whether `region` had to be grown by [`clamp_capture_region`]
+pub fn below_min_capture_size(region: CaptureRegion) -> bool {
+    region.width < MIN_CAPTURE_SIZE || region.height < MIN_CAPTURE_SIZE
+}
+
+fn window_capture_region(window: &slint::Window) -> CaptureRegion {
+    clamp_capture_region(capture_region(
+        window.position(),
+        window.size(),
+        window.scale_factor(),
+    ))
+}
+
 pub fn send_capture_region(
     region: CaptureRegion,
     tx: Sender<AppEvent>,
@@ -456,3 +595,123 @@ pub fn send_capture_region(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slint::{PhysicalPosition, PhysicalSize};
+
+    #[test]
+    fn test_capture_region_excludes_header() {
+        let region = capture_region(
+            PhysicalPosition::new(100, 200),
+            PhysicalSize::new(640, 480),
+            1.0,
+        );
+        assert_eq!(
+            region,
+            CaptureRegion {
+                x: 100,
+                y: 232,
+                width: 640,
+                height: 448,
+            }
+        );
+    }
+
+    #[test]
+    fn test_capture_region_on_negative_monitor() {
+        let region = capture_region(
+            PhysicalPosition::new(-1920, -40),
+            PhysicalSize::new(300, 100),
+            1.0,
+        );
+        assert_eq!((region.x, region.y), (-1920, -8));
+        assert_eq!(region.height, 68);
+    }
+
+    #[test]
+    fn test_capture_region_scales_header_on_hidpi_monitor() {
+        let monitor = saya_ocr::MonitorInfo {
+            index: 0,
+            name: "Display 0".to_string(),
+            x: 0,
+            y: 0,
+            width: 2880,
+            height: 1620,
+            scale_factor: 1.5,
+            is_primary: true,
+        };
+        // The window's physical position and size, as Slint reports them at 150%
+        let region = capture_region(
+            PhysicalPosition::new(1500, 600),
+            PhysicalSize::new(900, 482),
+            1.5,
+        );
+        // The 32px header is 48 physical pixels tall
+        assert_eq!(monitor.crop_rect(region), (1500, 648, 900, 434));
+    }
+
+    #[test]
+    fn test_capture_region_smaller_than_header() {
+        for height in [0, 1, CAPTURE_HEADER_HEIGHT] {
+            let region = capture_region(
+                PhysicalPosition::new(0, 0),
+                PhysicalSize::new(200, height),
+                1.0,
+            );
+            assert_eq!(region.height, 0);
+            assert_eq!(region.width, 200);
+        }
+    }
+
+    #[test]
+    fn test_selected_text_in_either_direction() {
+        let text = "猫が食べた";
+        // "食べ" spans bytes 6..12
+        assert_eq!(selected_text(text, 6, 12).as_deref(), Some("食べ"));
+        assert_eq!(selected_text(text, 12, 6).as_deref(), Some("食べ"));
+        assert_eq!(selected_text(text, 3, 3), None);
+        // Inside a multi-byte char
+        assert_eq!(selected_text(text, 1, 6), None);
+        assert_eq!(selected_text(text, -1, 6), None);
+    }
+
+    #[test]
+    fn test_clamp_enforces_minimum_size() {
+        for (width, height) in [(1, 1), (5, 300), (300, 5), (MIN_CAPTURE_SIZE, 1)] {
+            let region = CaptureRegion {
+                x: 10,
+                y: 20,
+                width,
+                height,
+            };
+            let clamped = clamp_capture_region(region);
+            assert!(below_min_capture_size(region));
+            assert!(clamped.width >= MIN_CAPTURE_SIZE && clamped.height >= MIN_CAPTURE_SIZE);
+            assert!(!below_min_capture_size(clamped));
+            assert_eq!((clamped.x, clamped.y), (10, 20));
+        }
+
+        let large = capture_region(
+            PhysicalPosition::new(0, 0),
+            PhysicalSize::new(640, 480),
+            1.0,
+        );
+        assert_eq!(clamp_capture_region(large), large);
+    }
+
+    #[test]
+    fn test_clamp_leaves_empty_region_empty() {
+        for (width, height) in [(0, 0), (0, 300), (300, 0)] {
+            let region = CaptureRegion {
+                x: 10,
+                y: 20,
+                width,
+                height,
+            };
+            assert!(below_min_capture_size(region));
+            assert_eq!(clamp_capture_region(region), region);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+/// Make the window ignore mouse input so clicks reach whatever is behind it
+#[cfg(windows)]
+pub fn set_click_through(window: &slint::Window, enabled: bool) -> anyhow::Result<()> {
+    use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GWL_EXSTYLE, GetWindowLongPtrW, SetWindowLongPtrW, WS_EX_LAYERED, WS_EX_TRANSPARENT,
+    };
+
+    let handle = window.window_handle();
+    let raw = handle
+        .window_handle()
+        .map_err(|e| anyhow::anyhow!("window handle unavailable: {}", e))?
+        .as_raw();
+    let RawWindowHandle::Win32(win32) = raw else {
+        anyhow::bail!("not a Win32 window");
+    };
+    let hwnd = HWND(win32.hwnd.get() as *mut _);
+
+    // WS_EX_TRANSPARENT only passes clicks through on layered windows
+    unsafe {
+        let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        let style = if enabled {
+            style | (WS_EX_LAYERED.0 | WS_EX_TRANSPARENT.0) as isize
+        } else {
+            style & !(WS_EX_TRANSPARENT.0 as isize)
+        };
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, style);
+    }
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub fn set_click_through(_window: &slint::Window, enabled: bool) -> anyhow::Result<()> {
+    if enabled {
+        anyhow::bail!("click-through is only supported on Windows");
+    }
+    Ok(())
+}
@@ -2,7 +2,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
-use saya_types::DisplayResult;
+use saya_types::{AppEvent, DisplayResult};
 
 /// UI-specific state (separate from AppState)
 pub struct UiState {
@@ -19,6 +19,31 @@ impl UiState {
     }
 }
 
+/// What the overlay is showing, kept outside Slint for callbacks and tests
+#[derive(Debug, Default)]
+pub struct OverlayView {
+    /// Last hooked text, shown above the results
+    pub raw_text: String,
+    pub results: Vec<DisplayResult>,
+    /// Status shown before pausing, restored on resume
+    pub status_before_pause: Option<String>,
+}
+
+impl OverlayView {
+    /// Update the view for `event`, returning whether it was one the view tracks
+    ///
+    /// Results replace the old ones but never the raw text.
+    pub fn apply(&mut self, event: &AppEvent) -> bool {
+        match event {
+            AppEvent::RawTextInput { text, .. } => self.raw_text = text.clone(),
+            AppEvent::ShowResults(results) => self.results = results.clone(),
+            AppEvent::AppendResults(results) => self.results.extend(results.iter().cloned()),
+            _ => return false,
+        }
+        true
+    }
+}
+
 impl Default for UiState {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use saya_core::dictionary::{Dictionary, DictionaryEntry, DictionaryMetadata, Definition, SearchOptions};
+
+/// CC-CEDICT dictionary entry
+#[derive(Debug, Clone)]
+pub struct CedictEntry {
+    pub id: String,
+    pub traditional: String,
+    pub simplified: String,
+    pub pinyin: String,
+    pub meanings: Vec<String>,
+}
+
+impl DictionaryEntry for CedictEntry {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn headword(&self) -> String {
+        self.simplified.clone()
+    }
+
+    fn readings(&self) -> Vec<String> {
+        vec![self.pinyin.clone()]
+    }
+
+    fn definitions(&self) -> Vec<Definition> {
+        self.meanings.iter().map(|text| Definition {
+            text: text.clone(),
+            part_of_speech: vec![],
+            tags: vec![],
+        }).collect()
+    }
+
+    fn metadata(&self) -> serde_json::Value {
+        serde_json::json!({
+            "traditional": self.traditional,
+            "simplified": self.simplified,
+        })
+    }
+}
+
+/// CC-CEDICT dictionary indexed by both scripts
+pub struct Cedict {
+    entries: Vec<CedictEntry>,
+    index: HashMap<String, Vec<usize>>,
+    max_word_len: usize,
+}
+
+impl Cedict {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            index: HashMap::new(),
+            max_word_len: 0,
+        }
+    }
+
+    /// Load embedded dictionary subset
+    pub fn load_embedded() -> Self {
+        let dict = Self::parse(include_str!("../data/cedict_subset.u8"));
+        tracing::info!("Loaded {} CC-CEDICT entries", dict.entry_count());
+        dict
+    }
+
+    /// Parse CC-CEDICT text, skipping comments and malformed lines
+    pub fn parse(text: &str) -> Self {
+        let mut dict = Self::new();
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match parse_line(line, dict.entries.len()) {
+                Some(entry) => dict.insert(entry),
+                None => tracing::warn!("Skipping malformed CC-CEDICT line: {}", line),
+            }
+        }
+
+        dict
+    }
+
+    fn insert(&mut self, entry: CedictEntry) {
+        let entry_idx = self.entries.len();
+
+        for word in [&entry.simplified, &entry.traditional] {
+            self.max_word_len = self.max_word_len.max(word.chars().count());
+            let indices = self.index.entry(word.clone()).or_insert_with(Vec::new);
+            if !indices.contains(&entry_idx) {
+                indices.push(entry_idx);
+            }
+        }
+
+        self.entries.push(entry);
+    }
+
+    /// Get the number of entries in the dictionary
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Length in chars of the longest headword
+    pub fn max_word_len(&self) -> usize {
+        self.max_word_len
+    }
+
+    /// Whether `word` is a headword in either script
+    pub fn contains(&self, word: &str) -> bool {
+        self.index.contains_key(word)
+    }
+}
+
+impl Default for Cedict {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse `Traditional Simplified [pin1 yin1] /def/def/`
+fn parse_line(line: &str, idx: usize) -> Option<CedictEntry> {
+    let (words, rest) = line.split_once(" [")?;
+    let (traditional, simplified) = words.split_once(' ')?;
+    let (pinyin, definitions) = rest.split_once("] ")?;
+
+    let meanings: Vec<String> = definitions
+        .split('/')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(String::from)
+        .collect();
+
+    if meanings.is_empty() {
+        return None;
+    }
+
+    Some(CedictEntry {
+        id: format!("cedict-{}", idx),
+        traditional: traditional.to_string(),
+        simplified: simplified.to_string(),
+        pinyin: pinyin.to_string(),
+        meanings,
+    })
+}
+
+impl Dictionary for Cedict {
+    fn lookup_exact(&self, query: &str) -> Vec<Box<dyn DictionaryEntry>> {
+        self.index
+            .get(query)
+            .into_iter()
+            .flatten()
+            .filter_map(|&idx| self.entries.get(idx))
+            .map(|e| Box::new(e.clone()) as Box<dyn DictionaryEntry>)
+            .collect()
+    }
+
+    fn search(&self, query: &str, _options: SearchOptions) -> Vec<Box<dyn DictionaryEntry>> {
+        self.lookup_exact(query)
+    }
+
+    fn get_by_id(&self, id: &str) -> Option<Box<dyn DictionaryEntry>> {
+        self.entries.iter()
+            .find(|e| e.id == id)
+            .map(|e| Box::new(e.clone()) as Box<dyn DictionaryEntry>)
+    }
+
+    fn metadata(&self) -> DictionaryMetadata {
+        DictionaryMetadata {
+            name: "CC-CEDICT".to_string(),
+            version: "subset".to_string(),
+            language: "zh".to_string(),
+            entry_count: self.entries.len(),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod dictionary;
+pub mod processor;
+
+pub use dictionary::{Cedict, CedictEntry};
+pub use processor::ChineseProcessor;
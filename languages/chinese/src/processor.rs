@@ -0,0 +1,135 @@
+use saya_core::dictionary::DictionaryStatus;
+use saya_core::language::{LanguageProcessor, Token, LookupResult};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::dictionary::Cedict;
+
+/// Chinese language processor
+///
+/// Chinese has no inflection, so lookup is a plain dictionary match
+/// on the tokens produced by forward maximal matching.
+pub struct ChineseProcessor {
+    dictionary: Cedict,
+}
+
+impl ChineseProcessor {
+    /// Create a processor backed by the embedded CC-CEDICT subset
+    pub fn new() -> Self {
+        Self::with_dictionary(Cedict::load_embedded())
+    }
+
+    pub fn with_dictionary(dictionary: Cedict) -> Self {
+        Self { dictionary }
+    }
+}
+
+impl Default for ChineseProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageProcessor for ChineseProcessor {
+    fn language_code(&self) -> &str {
+        "zh"
+    }
+
+    fn normalize(&self, text: &str) -> String {
+        text.nfkc()
+            .filter(|c| !c.is_whitespace())
+            .collect()
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<Token> {
+        let normalized = self.normalize(text);
+        let chars: Vec<char> = normalized.chars().collect();
+        let max_len = self.dictionary.max_word_len().max(1);
+        let mut tokens = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            // Longest dictionary word starting here, else a single char
+            let len = (1..=max_len.min(chars.len() - i))
+                .rev()
+                .find(|&len| {
+                    let candidate: String = chars[i..i + len].iter().collect();
+                    self.dictionary.contains(&candidate)
+                })
+                .unwrap_or(1);
+
+            let surface: String = chars[i..i + len].iter().collect();
+            tokens.push(Token {
+                surface: surface.clone(),
+                normalized: surface,
+                position: i,
+            });
+            i += len;
+        }
+
+        tokens
+    }
+
+    fn lookup(&self, token: &Token) -> Vec<LookupResult> {
+        use saya_core::dictionary::Dictionary;
+
+        self.dictionary
+            .lookup_exact(&token.normalized)
+            .into_iter()
+            .map(|entry| entry.to_lookup_result())
+            .collect()
+    }
+
+    fn lookup_by_id(&self, id: &str) -> Option<LookupResult> {
+        use saya_core::dictionary::Dictionary;
+
+        self.dictionary.get_by_id(id).map(|entry| entry.to_lookup_result())
+    }
+
+    fn dictionary_status(&self) -> DictionaryStatus {
+        DictionaryStatus::from_count(self.dictionary.entry_count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn surfaces(processor: &ChineseProcessor, text: &str) -> Vec<String> {
+        processor.tokenize(text).into_iter().map(|t| t.surface).collect()
+    }
+
+    #[test]
+    fn test_tokenize_prefers_longest_match() {
+        let processor = ChineseProcessor::new();
+        assert_eq!(surfaces(&processor, "你好"), vec!["你好"]);
+        assert_eq!(surfaces(&processor, "我喜欢学习中文"), vec!["我", "喜欢", "学习", "中文"]);
+    }
+
+    #[test]
+    fn test_tokenize_unknown_chars_and_whitespace() {
+        let processor = ChineseProcessor::new();
+        assert_eq!(surfaces(&processor, "我 吃饭 嗎"), vec!["我", "吃饭", "嗎"]);
+    }
+
+    #[test]
+    fn test_lookup_simplified_and_traditional() {
+        let processor = ChineseProcessor::new();
+
+        let tokens = processor.tokenize("学习");
+        let results = processor.lookup(&tokens[0]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].term, "学习");
+        assert_eq!(results[0].readings, vec!["xue2 xi2"]);
+        assert!(results[0].definitions.contains(&"to study".to_string()));
+
+        let tokens = processor.tokenize("老師");
+        let results = processor.lookup(&tokens[0]);
+        assert_eq!(results[0].term, "老师");
+    }
+
+    #[test]
+    fn test_normalize_fullwidth() {
+        let processor = ChineseProcessor::new();
+        assert_eq!(processor.normalize("ＡＢＣ １２"), "ABC12");
+    }
+}
@@ -0,0 +1,64 @@
+//! Throughput of tokenize and normalize -> tokenize -> lookup
+//!
+//! Run with `cargo bench -p saya-lang-japanese`.
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use saya_core::language::LanguageProcessor;
+use saya_lang_japanese::JapaneseProcessor;
+
+const BASE: &str = "今日は学校で先生と日本語を勉強しました。時間があれば本を読みたいです。";
+
+/// Sample text of exactly `chars` characters, repeating a representative sentence
+fn sample(chars: usize) -> String {
+    BASE.chars().cycle().take(chars).collect()
+}
+
+const LENGTHS: [usize; 3] = [10, 50, 200];
+
+fn bench_tokenize(c: &mut Criterion) {
+    let processor = JapaneseProcessor::new();
+    let mut group = c.benchmark_group("tokenize");
+
+    for len in LENGTHS {
+        let text = sample(len);
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &text, |b, text| {
+            b.iter(|| processor.tokenize(black_box(text)))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_lookup(c: &mut Criterion) {
+    // Full embedded dictionary, loaded once outside the measured loop
+    let processor = JapaneseProcessor::new();
+    let mut group = c.benchmark_group("normalize_tokenize_lookup");
+
+    for len in LENGTHS {
+        let text = sample(len);
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &text, |b, text| {
+            b.iter(|| {
+                let normalized = processor.normalize(black_box(text));
+                processor
+                    .tokenize(&normalized)
+                    .iter()
+                    .map(|token| processor.lookup(token).len())
+                    .sum::<usize>()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_dictionary_load(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dictionary");
+    group.sample_size(10);
+    group.bench_function("load_embedded", |b| b.iter(JapaneseProcessor::new));
+    group.finish();
+}
+
+criterion_group!(benches, bench_tokenize, bench_lookup, bench_dictionary_load);
+criterion_main!(benches);
@@ -0,0 +1,182 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dictionary::JMdict;
+
+/// Bump whenever the cached layout of [`JMdict`] changes
+const CACHE_VERSION: u32 = 5;
+
+#[derive(Serialize)]
+struct CacheOut<'a> {
+    version: u32,
+    key: &'a str,
+    dict: &'a JMdict,
+}
+
+#[derive(Deserialize)]
+struct CacheIn {
+    version: u32,
+    key: String,
+    dict: JMdict,
+}
+
+/// Cache file for the dictionary `name` built with `gloss_langs`
+pub fn cache_path(cache_dir: &Path, name: &str, gloss_langs: &[&str]) -> PathBuf {
+    cache_dir.join(format!("{}-{}.bin", name, gloss_langs.join("-")))
+}
+
+/// Load the dictionary cached at `path`, or `build` it and write the cache
+///
+/// The cache is only used when it was written by this version of Saya for the
+/// same `key` and is newer than `source_modified`. Returns the dictionary and
+/// whether it came from the cache.
+pub fn load_or_build(
+    path: &Path,
+    key: &str,
+    source_modified: Option<SystemTime>,
+    build: impl FnOnce() -> Result<JMdict, Box<dyn Error>>,
+) -> Result<(JMdict, bool), Box<dyn Error>> {
+    let key = format!("{}:{}", env!("CARGO_PKG_VERSION"), key);
+
+    if let Some(dict) = read_cache(path, &key, source_modified) {
+        tracing::info!(
+            "Loaded {} dictionary entries from cache",
+            dict.entry_count()
+        );
+        return Ok((dict, true));
+    }
+
+    let dict = build()?;
+    if let Err(e) = write_cache(path, &key, &dict) {
+        tracing::warn!("Failed to write dictionary cache {}: {}", path.display(), e);
+    }
+    Ok((dict, false))
+}
+
+fn read_cache(path: &Path, key: &str, source_modified: Option<SystemTime>) -> Option<JMdict> {
+    let cached_at = fs::metadata(path).ok()?.modified().ok()?;
+    if source_modified.is_some_and(|modified| modified > cached_at) {
+        tracing::debug!(
+            "Dictionary cache {} is older than its source",
+            path.display()
+        );
+        return None;
+    }
+
+    let bytes = fs::read(path).ok()?;
+    match bincode::serde::decode_from_slice::<CacheIn, _>(&bytes, bincode::config::standard()) {
+        Ok((cache, _)) if cache.version == CACHE_VERSION && cache.key == key => Some(cache.dict),
+        Ok(_) => {
+            tracing::debug!(
+                "Dictionary cache {} is from another version",
+                path.display()
+            );
+            None
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Ignoring unreadable dictionary cache {}: {}",
+                path.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+fn write_cache(path: &Path, key: &str, dict: &JMdict) -> Result<(), Box<dyn Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let cache = CacheOut {
+        version: CACHE_VERSION,
+        key,
+        dict,
+    };
+    let bytes = bincode::serde::encode_to_vec(&cache, bincode::config::standard())?;
+
+    // Write then rename so an interrupted write can't leave a truncated cache
+    let partial = path.with_extension("tmp");
+    fs::write(&partial, bytes)?;
+    fs::rename(&partial, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use saya_core::dictionary::{Dictionary, DictionaryEntry};
+
+    use super::*;
+
+    const WORDS: &str = r#"{"words": [
+        {"id": "1", "kanji": [{"text": "水"}], "kana": [{"text": "みず"}], "sense": [
+            {"partOfSpeech": ["n"], "gloss": [{"lang": "eng", "text": "water"}]}
+        ]}
+    ]}"#;
+
+    fn temp_cache(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("saya-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        cache_path(&dir, "test", &["eng"])
+    }
+
+    fn definitions(dict: &JMdict, word: &str) -> Vec<String> {
+        dict.lookup_exact(word)
+            .iter()
+            .flat_map(|e| e.definitions())
+            .map(|d| d.text)
+            .collect()
+    }
+
+    #[test]
+    fn test_second_load_uses_cache() {
+        let path = temp_cache("hit");
+        let builds = Cell::new(0);
+        let build = || {
+            builds.set(builds.get() + 1);
+            JMdict::from_json(WORDS)
+        };
+
+        let (first, cached) = load_or_build(&path, "words", None, build).unwrap();
+        assert!(!cached);
+        assert!(path.exists());
+
+        let (second, cached) = load_or_build(&path, "words", None, build).unwrap();
+        assert!(cached);
+        assert_eq!(builds.get(), 1);
+        assert_eq!(second.entry_count(), first.entry_count());
+        assert_eq!(definitions(&second, "水"), definitions(&first, "水"));
+        assert_eq!(definitions(&second, "みず"), vec!["water"]);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_stale_or_mismatched_cache_rebuilds() {
+        let path = temp_cache("stale");
+        load_or_build(&path, "words", None, || JMdict::from_json(WORDS)).unwrap();
+
+        let (_, cached) = load_or_build(&path, "other", None, || JMdict::from_json(WORDS)).unwrap();
+        assert!(!cached);
+
+        let future = SystemTime::now() + std::time::Duration::from_secs(3600);
+        let (_, cached) =
+            load_or_build(&path, "other", Some(future), || JMdict::from_json(WORDS)).unwrap();
+        assert!(!cached);
+
+        fs::write(&path, b"garbage").unwrap();
+        let (dict, cached) =
+            load_or_build(&path, "other", None, || JMdict::from_json(WORDS)).unwrap();
+        assert!(!cached);
+        assert_eq!(dict.entry_count(), 1);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
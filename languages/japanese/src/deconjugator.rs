@@ -1,5 +1,75 @@
 use saya_core::language::DeconjugationResult;
 
+/// Godan i-column stems (連用形) and their dictionary endings
+const I_TO_U: &[(&str, &str)] = &[
+    ("い", "う"),
+    ("き", "く"),
+    ("ぎ", "ぐ"),
+    ("し", "す"),
+    ("ち", "つ"),
+    ("に", "ぬ"),
+    ("び", "ぶ"),
+    ("み", "む"),
+    ("り", "る"),
+];
+
+/// Godan e-column stems (仮定形) and their dictionary endings
+///
+/// れ comes last so the conditional can skip it, れば is matched on its own.
+const E_TO_U: &[(&str, &str)] = &[
+    ("え", "う"),
+    ("け", "く"),
+    ("げ", "ぐ"),
+    ("せ", "す"),
+    ("て", "つ"),
+    ("ね", "ぬ"),
+    ("べ", "ぶ"),
+    ("め", "む"),
+    ("れ", "る"),
+];
+
+/// Godan o-column volitional stems and their dictionary endings
+const O_TO_U: &[(&str, &str)] = &[
+    ("お", "う"),
+    ("こ", "く"),
+    ("ご", "ぐ"),
+    ("そ", "す"),
+    ("と", "つ"),
+    ("の", "ぬ"),
+    ("ぼ", "ぶ"),
+    ("も", "む"),
+    ("ろ", "る"),
+];
+
+/// Replace the stem's final kana using `table`, for godan verbs
+fn shift_ending(
+    stem: &str,
+    table: &[(&str, &str)],
+    conjugation_type: &str,
+    confidence: f32,
+) -> Vec<DeconjugationResult> {
+    table
+        .iter()
+        .filter_map(|(from, to)| {
+            let base_stem = stem.strip_suffix(from)?;
+            Some(DeconjugationResult {
+                base_form: format!("{}{}", base_stem, to),
+                conjugation_type: conjugation_type.to_string(),
+                confidence,
+            })
+        })
+        .collect()
+}
+
+/// Exact match on an irregular verb's conjugated form
+fn irregular(base_form: &str, form: &str) -> DeconjugationResult {
+    DeconjugationResult {
+        base_form: base_form.to_string(),
+        conjugation_type: format!("irregular verb {}, {}", base_form, form),
+        confidence: 1.0,
+    }
+}
+
 pub struct JapaneseDeconjugator;
 
 impl JapaneseDeconjugator {
@@ -29,6 +99,168 @@ impl JapaneseDeconjugator {
         // Try i-adjective conjugations
         results.extend(self.deconjugate_i_adjective(word));
 
+        // Try たい-form (desiderative)
+        results.extend(self.deconjugate_tai_form(word));
+
+        // Try ば-form (conditional)
+        results.extend(self.deconjugate_conditional(word));
+
+        // Try volitional forms
+        results.extend(self.deconjugate_volitional(word));
+
+        // Try colloquial contractions (ちゃう, とく)
+        results.extend(self.deconjugate_contractions(word));
+
+        results
+    }
+
+    /// Deconjugate たい-form verbs (食べたい → 食べる, 読みたい → 読む)
+    fn deconjugate_tai_form(&self, word: &str) -> Vec<DeconjugationResult> {
+        let mut results = Vec::new();
+
+        if let Some(stem) = word.strip_suffix("たい") {
+            // Ichidan verbs (食べたい → 食べる)
+            results.push(DeconjugationResult {
+                base_form: format!("{}る", stem),
+                conjugation_type: "ichidan verb, desiderative".to_string(),
+                confidence: 0.8,
+            });
+
+            // Godan verbs - i-column to u-column (読みたい → 読む)
+            results.extend(shift_ending(stem, I_TO_U, "godan verb, desiderative", 0.8));
+        }
+
+        // したい → する
+        if word == "したい" {
+            results.push(irregular("する", "desiderative"));
+        }
+
+        // 来たい → 来る
+        if word == "来たい" || word == "きたい" {
+            results.push(irregular("来る", "desiderative"));
+        }
+
+        results
+    }
+
+    /// Deconjugate ば-form conditionals (読めば → 読む, 食べれば → 食べる)
+    fn deconjugate_conditional(&self, word: &str) -> Vec<DeconjugationResult> {
+        let mut results = Vec::new();
+
+        let Some(stem) = word.strip_suffix("ば") else {
+            return results;
+        };
+
+        // i-adjectives (高ければ → 高い)
+        if let Some(adjective) = stem.strip_suffix("けれ") {
+            results.push(DeconjugationResult {
+                base_form: format!("{}い", adjective),
+                conjugation_type: "i-adjective, conditional".to_string(),
+                confidence: 0.8,
+            });
+        }
+
+        // れば is shared by ichidan and godan る verbs (食べれば, 帰れば)
+        if let Some(verb) = stem.strip_suffix("れ") {
+            results.push(DeconjugationResult {
+                base_form: format!("{}る", verb),
+                conjugation_type: "ichidan or godan る verb, conditional".to_string(),
+                confidence: 0.8,
+            });
+        }
+
+        // Godan verbs - e-column to u-column (読めば → 読む)
+        results.extend(shift_ending(
+            stem,
+            &E_TO_U[..E_TO_U.len() - 1],
+            "godan verb, conditional",
+            0.8,
+        ));
+
+        // すれば → する
+        if word == "すれば" {
+            results.push(irregular("する", "conditional"));
+        }
+
+        // 来れば → 来る
+        if word == "来れば" || word == "くれば" {
+            results.push(irregular("来る", "conditional"));
+        }
+
+        results
+    }
+
+    /// Deconjugate colloquial contractions of te-form + しまう/おく
+    ///
+    /// 食べちゃった is 食べてしまった and 読んどく is 読んでおく, so the
+    /// contraction is expanded back to the te-form and deconjugated from there.
+    fn deconjugate_contractions(&self, word: &str) -> Vec<DeconjugationResult> {
+        const CONTRACTIONS: &[(&str, &str, &str)] = &[
+            ("ちゃう", "て", "てしまう (ちゃう)"),
+            ("ちゃった", "て", "てしまった (ちゃった)"),
+            ("じゃう", "で", "でしまう (じゃう)"),
+            ("じゃった", "で", "でしまった (じゃった)"),
+            ("とく", "て", "ておく (とく)"),
+            ("といた", "て", "ておいた (といた)"),
+            ("どく", "で", "でおく (どく)"),
+            ("どいた", "で", "でおいた (どいた)"),
+        ];
+
+        let mut results = Vec::new();
+        for (suffix, te, label) in CONTRACTIONS {
+            if let Some(stem) = word.strip_suffix(suffix) {
+                let te_form = format!("{}{}", stem, te);
+                results.extend(self.deconjugate_te_form(&te_form).into_iter().map(|mut r| {
+                    r.conjugation_type = format!("{}, {}", r.conjugation_type, label);
+                    r
+                }));
+            }
+        }
+        results
+    }
+
+    /// Deconjugate volitional forms (食べよう → 食べる, 読もう → 読む)
+    fn deconjugate_volitional(&self, word: &str) -> Vec<DeconjugationResult> {
+        let mut results = Vec::new();
+
+        // Polite volitional (食べましょう → 食べます → 食べる)
+        if let Some(stem) = word.strip_suffix("ましょう") {
+            let masu_form = format!("{}ます", stem);
+            results.extend(
+                self.deconjugate_masu_form(&masu_form)
+                    .into_iter()
+                    .map(|mut r| {
+                        r.conjugation_type = format!("{}, volitional", r.conjugation_type);
+                        r
+                    }),
+            );
+            return results;
+        }
+
+        // Ichidan verbs (食べよう → 食べる)
+        if let Some(stem) = word.strip_suffix("よう") {
+            results.push(DeconjugationResult {
+                base_form: format!("{}る", stem),
+                conjugation_type: "ichidan verb, volitional".to_string(),
+                confidence: 0.8,
+            });
+        }
+
+        // Godan verbs - o-column + う to u-column (読もう → 読む)
+        if let Some(stem) = word.strip_suffix("う") {
+            results.extend(shift_ending(stem, O_TO_U, "godan verb, volitional", 0.8));
+        }
+
+        // しよう → する
+        if word == "しよう" {
+            results.push(irregular("する", "volitional"));
+        }
+
+        // 来よう → 来る
+        if word == "来よう" || word == "こよう" {
+            results.push(irregular("来る", "volitional"));
+        }
+
         results
     }
 
@@ -60,17 +292,6 @@ impl JapaneseDeconjugator {
                     });
                 }
             }
-            // んで → ぬ/ぶ/む (読んで → 読む)
-            if stem.ends_with("ん") {
-                for ending in &["ぬ", "ぶ", "む"] {
-                    let base = format!("{}{}", &stem[..stem.len() - 3], ending);
-                    results.push(DeconjugationResult {
-                        base_form: base,
-                        conjugation_type: "godan verb, te-form".to_string(),
-                        confidence: 0.6,
-                    });
-                }
-            }
             // いて → く (書いて → 書く)
             if stem.ends_with("い") {
                 let base = format!("{}く", &stem[..stem.len() - 3]);
@@ -99,6 +320,27 @@ impl JapaneseDeconjugator {
             });
         }
 
+        if let Some(stem) = word.strip_suffix("で") {
+            // んで → ぬ/ぶ/む (読んで → 読む)
+            if let Some(base_stem) = stem.strip_suffix("ん") {
+                for ending in &["ぬ", "ぶ", "む"] {
+                    results.push(DeconjugationResult {
+                        base_form: format!("{}{}", base_stem, ending),
+                        conjugation_type: "godan verb, te-form".to_string(),
+                        confidence: 0.6,
+                    });
+                }
+            }
+            // いで → ぐ (泳いで → 泳ぐ)
+            if let Some(base_stem) = stem.strip_suffix("い") {
+                results.push(DeconjugationResult {
+                    base_form: format!("{}ぐ", base_stem),
+                    conjugation_type: "godan verb, te-form".to_string(),
+                    confidence: 0.7,
+                });
+            }
+        }
+
         // Irregular: して → する
         if word == "して" {
             results.push(DeconjugationResult {
@@ -311,3 +553,85 @@ impl JapaneseDeconjugator {
         results
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deconjugates_to(word: &str, base_form: &str) -> bool {
+        JapaneseDeconjugator::new()
+            .deconjugate(word)
+            .iter()
+            .any(|r| r.base_form == base_form)
+    }
+
+    #[test]
+    fn test_tai_form() {
+        assert!(deconjugates_to("食べたい", "食べる"));
+        assert!(deconjugates_to("読みたい", "読む"));
+        assert!(deconjugates_to("買いたい", "買う"));
+        assert!(deconjugates_to("したい", "する"));
+        assert!(deconjugates_to("来たい", "来る"));
+    }
+
+    #[test]
+    fn test_conditional() {
+        assert!(deconjugates_to("読めば", "読む"));
+        assert!(deconjugates_to("書けば", "書く"));
+        assert!(deconjugates_to("食べれば", "食べる"));
+        assert!(deconjugates_to("帰れば", "帰る"));
+        assert!(deconjugates_to("すれば", "する"));
+        assert!(deconjugates_to("来れば", "来る"));
+        assert!(deconjugates_to("高ければ", "高い"));
+    }
+
+    #[test]
+    fn test_volitional() {
+        assert!(deconjugates_to("食べよう", "食べる"));
+        assert!(deconjugates_to("読もう", "読む"));
+        assert!(deconjugates_to("行こう", "行く"));
+        assert!(deconjugates_to("しよう", "する"));
+        assert!(deconjugates_to("来よう", "来る"));
+        assert!(deconjugates_to("食べましょう", "食べる"));
+    }
+
+    #[test]
+    fn test_irregular_forms_are_certain() {
+        let results = JapaneseDeconjugator::new().deconjugate("すれば");
+        let suru = results.iter().find(|r| r.base_form == "する").unwrap();
+        assert_eq!(suru.confidence, 1.0);
+        assert_eq!(suru.conjugation_type, "irregular verb する, conditional");
+    }
+
+    #[test]
+    fn test_te_form_with_de() {
+        assert!(deconjugates_to("読んで", "読む"));
+        assert!(deconjugates_to("泳いで", "泳ぐ"));
+        assert!(deconjugates_to("読んだ", "読む"));
+    }
+
+    #[test]
+    fn test_chau_contraction() {
+        let results = JapaneseDeconjugator::new().deconjugate("食べちゃった");
+        let taberu = results.iter().find(|r| r.base_form == "食べる").unwrap();
+        assert_eq!(
+            taberu.conjugation_type,
+            "ichidan verb, te-form, てしまった (ちゃった)"
+        );
+        assert!(deconjugates_to("食べちゃう", "食べる"));
+        assert!(deconjugates_to("しちゃう", "する"));
+    }
+
+    #[test]
+    fn test_jau_contraction() {
+        assert!(deconjugates_to("読んじゃう", "読む"));
+        assert!(deconjugates_to("死んじゃった", "死ぬ"));
+    }
+
+    #[test]
+    fn test_toku_contraction() {
+        assert!(deconjugates_to("食べとく", "食べる"));
+        assert!(deconjugates_to("買っといた", "買う"));
+        assert!(deconjugates_to("読んどく", "読む"));
+    }
+}
@@ -1,17 +1,33 @@
-use std::collections::HashMap;
-use saya_core::dictionary::{Dictionary, DictionaryEntry, DictionaryMetadata, Definition, SearchOptions};
-use serde::Deserialize;
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::io::{BufReader, Read};
+use std::ops::Bound;
+use std::rc::Rc;
+use saya_core::dictionary::{
+    Definition, Dictionary, DictionaryEntry, DictionaryMetadata, MatchType, SearchOptions,
+};
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
 
 /// JMdict dictionary entry
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JMdictEntry {
     pub id: String,
     pub kanji: Vec<String>,
     pub readings: Vec<String>,
     pub meanings: Vec<String>,
+    /// Misc and field tags of each meaning's sense, e.g. "arch" or "med"
+    pub meaning_tags: Vec<Vec<String>>,
+    /// Field tags across all senses, the domains a meaning belongs to
+    pub fields: Vec<String>,
     pub pos: Vec<String>,
     pub jlpt_level: Option<u8>,
     pub frequency_rank: Option<u32>,
+    /// Marked common by JMdict's priority tags
+    pub common: bool,
+    /// Name of the dictionary this entry was loaded from
+    pub source: String,
 }
 
 impl DictionaryEntry for JMdictEntry {
@@ -31,28 +47,29 @@ impl DictionaryEntry for JMdictEntry {
     }
 
     fn definitions(&self) -> Vec<Definition> {
-        self.meanings.iter().map(|text| Definition {
+        self.meanings.iter().enumerate().map(|(i, text)| Definition {
             text: text.clone(),
             part_of_speech: self.pos.clone(),
-            tags: vec![],
+            tags: self.meaning_tags.get(i).cloned().unwrap_or_default(),
         }).collect()
     }
 
+    fn source(&self) -> Option<String> {
+        Some(self.source.clone())
+    }
+
     fn metadata(&self) -> serde_json::Value {
         serde_json::json!({
             "kanji": self.kanji,
             "jlpt_level": self.jlpt_level,
             "frequency_rank": self.frequency_rank,
+            "common": self.common,
+            "fields": self.fields,
         })
     }
 }
 
 // JSON structures for parsing jmdict-simplified format
-#[derive(Debug, Deserialize)]
-struct JMdictJson {
-    words: Vec<JMdictJsonEntry>,
-}
-
 #[derive(Debug, Deserialize)]
 struct JMdictJsonEntry {
     id: String,
@@ -66,17 +83,50 @@ struct JMdictJsonEntry {
 #[derive(Debug, Deserialize)]
 struct KanjiElement {
     text: String,
+    #[serde(default)]
+    common: bool,
+    /// Raw ke_pri tags, for exports that keep them
+    #[serde(default)]
+    priority: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct KanaElement {
     text: String,
+    #[serde(default)]
+    common: bool,
+    /// Raw re_pri tags, for exports that keep them
+    #[serde(default)]
+    priority: Vec<String>,
+}
+
+/// Priority tags JMdict counts as common, the EDICT "(P)" marker
+const COMMON_PRIORITIES: &[&str] = &["news1", "ichi1", "spec1", "spec2", "gai1"];
+
+/// Rank given to common words without an nfXX tag
+const COMMON_RANK: u32 = 10000;
+
+/// Whether an element is common, and its rough frequency rank
+///
+/// nfXX tags put a word in the XXth block of 500 by newspaper frequency.
+fn priority_of(common: bool, priority: &[String]) -> (bool, Option<u32>) {
+    let common = common || priority.iter().any(|p| COMMON_PRIORITIES.contains(&p.as_str()));
+    let nf_rank = priority
+        .iter()
+        .filter_map(|p| p.strip_prefix("nf")?.parse::<u32>().ok())
+        .map(|block| block * 500)
+        .min();
+    (common, nf_rank.or(common.then_some(COMMON_RANK)))
 }
 
 #[derive(Debug, Deserialize)]
 struct Sense {
     #[serde(rename = "partOfSpeech", default)]
     part_of_speech: Vec<String>,
+    #[serde(default)]
+    misc: Vec<String>,
+    #[serde(default)]
+    field: Vec<String>,
     gloss: Vec<Gloss>,
 }
 
@@ -86,82 +136,335 @@ struct Gloss {
     text: String,
 }
 
+/// Gloss language used when none is configured or the configured one is missing
+pub const DEFAULT_GLOSS_LANG: &str = "eng";
+
+/// Source label for the bundled JMdict
+pub const DEFAULT_SOURCE: &str = "JMdict";
+
+/// Misc tags marking senses a learner rarely needs
+pub const RARE_SENSE_TAGS: &[&str] = &["arch", "dated", "obs", "obsc", "rare"];
+
+/// Glosses of each sense in the first language of `langs` it has, with the
+/// sense's misc and field tags
+fn glosses_in(senses: &[Sense], langs: &[&str]) -> Vec<(String, Vec<String>)> {
+    senses
+        .iter()
+        .flat_map(|sense| {
+            let lang = langs
+                .iter()
+                .find(|lang| sense.gloss.iter().any(|gloss| gloss.lang == **lang));
+            let tags: Vec<String> = sense.misc.iter().chain(&sense.field).cloned().collect();
+            sense
+                .gloss
+                .iter()
+                .filter(move |gloss| lang.is_some_and(|lang| gloss.lang == *lang))
+                .map(move |gloss| (gloss.text.clone(), tags.clone()))
+        })
+        .collect()
+}
+
+/// `definitions` without senses tagged as archaic, obsolete or rare
+///
+/// When every sense is tagged the first is kept, so a word never loses all meanings.
+pub fn common_senses(definitions: Vec<Definition>) -> Vec<Definition> {
+    let is_rare = |d: &Definition| d.tags.iter().any(|t| RARE_SENSE_TAGS.contains(&t.as_str()));
+    if definitions.iter().all(is_rare) {
+        return definitions.into_iter().take(1).collect();
+    }
+    definitions.into_iter().filter(|d| !is_rare(d)).collect()
+}
+
+/// Words parsed between progress reports
+pub const PROGRESS_INTERVAL: usize = 5000;
+
+/// Counts the bytes pulled from a reader, to estimate how far parsing got
+struct CountingReader<R> {
+    inner: R,
+    count: Rc<Cell<u64>>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.set(self.count.get() + n as u64);
+        Ok(n)
+    }
+}
+
+/// Calls back with `(words_parsed, total_estimate)` every `interval` words
+struct ProgressTracker<'a> {
+    callback: &'a mut dyn FnMut(usize, usize),
+    interval: usize,
+    bytes_read: Rc<Cell<u64>>,
+    total_bytes: u64,
+    parsed: usize,
+}
+
+impl ProgressTracker<'_> {
+    /// Total words, extrapolated from the bytes it took to parse the ones so far
+    fn estimate(&self) -> usize {
+        let read = self.bytes_read.get();
+        if read == 0 {
+            return self.parsed;
+        }
+        let estimate = self.parsed as u128 * self.total_bytes as u128 / read as u128;
+        (estimate as usize).max(self.parsed)
+    }
+
+    fn word_parsed(&mut self) {
+        self.parsed += 1;
+        if self.parsed % self.interval == 0 {
+            let total = self.estimate();
+            (self.callback)(self.parsed, total);
+        }
+    }
+
+    fn finish(&mut self) {
+        (self.callback)(self.parsed, self.parsed);
+    }
+}
+
+/// Adds entries to a [`JMdict`] one at a time as the `words` array is parsed
+struct JMdictBuilder<'a> {
+    gloss_langs: Vec<&'a str>,
+    dict: JMdict,
+    progress: Option<ProgressTracker<'a>>,
+}
+
+impl<'a> JMdictBuilder<'a> {
+    fn new(gloss_langs: &[&'a str]) -> Self {
+        let mut gloss_langs = gloss_langs.to_vec();
+        if !gloss_langs.contains(&DEFAULT_GLOSS_LANG) {
+            gloss_langs.push(DEFAULT_GLOSS_LANG);
+        }
+        Self {
+            gloss_langs,
+            dict: JMdict::new(),
+            progress: None,
+        }
+    }
+
+    fn with_progress(mut self, progress: ProgressTracker<'a>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Parse a jmdict-simplified object, keeping only its entries
+    fn build<'de, D: Deserializer<'de>>(mut self, deserializer: D) -> Result<JMdict, D::Error> {
+        deserializer.deserialize_map(WordsVisitor(&mut self))?;
+        if let Some(progress) = &mut self.progress {
+            progress.finish();
+        }
+        Ok(self.dict)
+    }
+
+    fn push(&mut self, json_entry: JMdictJsonEntry) {
+        if let Some(progress) = &mut self.progress {
+            progress.word_parsed();
+        }
+
+        // Commonness of the most common spelling or reading
+        let priorities = json_entry
+            .kanji
+            .iter()
+            .map(|k| priority_of(k.common, &k.priority))
+            .chain(json_entry.kana.iter().map(|k| priority_of(k.common, &k.priority)));
+        let (common, frequency_rank) = priorities.fold((false, None), |(common, rank), p| {
+            (common || p.0, rank.into_iter().chain(p.1).min())
+        });
+
+        // Extract kanji and readings
+        let kanji: Vec<String> = json_entry.kanji.into_iter().map(|k| k.text).collect();
+        let readings: Vec<String> = json_entry.kana.into_iter().map(|k| k.text).collect();
+
+        // Extract meanings in the preferred languages and POS
+        let (meanings, meaning_tags): (Vec<_>, Vec<_>) =
+            glosses_in(&json_entry.sense, &self.gloss_langs)
+                .into_iter()
+                .unzip();
+
+        // Skip entries with no usable meanings
+        if meanings.is_empty() {
+            return;
+        }
+
+        let mut fields: Vec<String> = Vec::new();
+        for field in json_entry.sense.iter().flat_map(|sense| &sense.field) {
+            if !fields.contains(field) {
+                fields.push(field.clone());
+            }
+        }
+
+        let pos = json_entry
+            .sense
+            .into_iter()
+            .flat_map(|sense| sense.part_of_speech)
+            .collect();
+
+        let entry_idx = self.dict.entries.len();
+        self.dict.entries.push(JMdictEntry {
+            id: json_entry.id,
+            kanji,
+            readings,
+            meanings,
+            meaning_tags,
+            fields,
+            pos,
+            jlpt_level: None,
+            frequency_rank,
+            common,
+            source: DEFAULT_SOURCE.to_string(),
+        });
+        self.dict.index_entry(entry_idx);
+    }
+}
+
+/// Finds `words` in the top-level object, skipping every other key
+struct WordsVisitor<'b, 'a>(&'b mut JMdictBuilder<'a>);
+
+impl<'de> Visitor<'de> for WordsVisitor<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a jmdict-simplified object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut found = false;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "words" {
+                map.next_value_seed(EntriesSeed(&mut *self.0))?;
+                found = true;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        if !found {
+            return Err(de::Error::missing_field("words"));
+        }
+        Ok(())
+    }
+}
+
+/// Hands each element of `words` to the builder without collecting them
+struct EntriesSeed<'b, 'a>(&'b mut JMdictBuilder<'a>);
+
+impl<'de> DeserializeSeed<'de> for EntriesSeed<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for EntriesSeed<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of JMdict words")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(entry) = seq.next_element::<JMdictJsonEntry>()? {
+            self.0.push(entry);
+        }
+        Ok(())
+    }
+}
+
 /// JMdict dictionary
+#[derive(Serialize, Deserialize)]
 pub struct JMdict {
     entries: Vec<JMdictEntry>,
-    kanji_index: HashMap<String, Vec<usize>>,
-    reading_index: HashMap<String, Vec<usize>>,
+    /// Sorted, so prefix searches only walk the matching keys
+    kanji_index: BTreeMap<String, Vec<usize>>,
+    reading_index: BTreeMap<String, Vec<usize>>,
 }
 
 impl JMdict {
     pub fn new() -> Self {
         Self {
             entries: Vec::new(),
-            kanji_index: HashMap::new(),
-            reading_index: HashMap::new(),
+            kanji_index: BTreeMap::new(),
+            reading_index: BTreeMap::new(),
         }
     }
 
     /// Load JMdict from JSON string (jmdict-simplified format)
     pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let data: JMdictJson = serde_json::from_str(json_str)?;
-
-        let mut entries = Vec::new();
-        let mut kanji_index: HashMap<String, Vec<usize>> = HashMap::new();
-        let mut reading_index: HashMap<String, Vec<usize>> = HashMap::new();
-
-        for json_entry in data.words {
-            // Extract kanji and readings
-            let kanji: Vec<String> = json_entry.kanji.iter().map(|k| k.text.clone()).collect();
-            let readings: Vec<String> = json_entry.kana.iter().map(|k| k.text.clone()).collect();
-
-            // Extract English meanings and POS
-            let mut meanings = Vec::new();
-            let mut pos = Vec::new();
-
-            for sense in &json_entry.sense {
-                // Only use English glosses
-                for gloss in &sense.gloss {
-                    if gloss.lang == "eng" {
-                        meanings.push(gloss.text.clone());
-                    }
-                }
-                // Collect POS tags
-                pos.extend(sense.part_of_speech.clone());
-            }
+        Self::from_json_with_lang(json_str, DEFAULT_GLOSS_LANG)
+    }
 
-            // Skip entries with no English meanings
-            if meanings.is_empty() {
-                continue;
-            }
+    /// Load JMdict keeping glosses in `gloss_lang` (ISO 639-2, e.g. "ger"),
+    /// falling back to English for senses without that language
+    pub fn from_json_with_lang(
+        json_str: &str,
+        gloss_lang: &str,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_json_with_langs(json_str, &[gloss_lang])
+    }
 
-            let entry = JMdictEntry {
-                id: json_entry.id,
-                kanji: kanji.clone(),
-                readings: readings.clone(),
-                meanings,
-                pos,
-                jlpt_level: None,
-                frequency_rank: None,
-            };
+    /// Load JMdict taking each sense's glosses from the first of `gloss_langs`
+    /// it has, then English
+    pub fn from_json_with_langs(
+        json_str: &str,
+        gloss_langs: &[&str],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut deserializer = serde_json::Deserializer::from_str(json_str);
+        let dict = JMdictBuilder::new(gloss_langs).build(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(dict)
+    }
 
-            let entry_idx = entries.len();
-            entries.push(entry);
+    /// Load JMdict from a reader, building entries as they are parsed
+    ///
+    /// Unlike [`JMdict::from_json`] the file never has to be in memory whole.
+    pub fn from_reader(reader: impl Read) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_reader_with_langs(reader, &[DEFAULT_GLOSS_LANG])
+    }
 
-            // Build indices
-            for k in kanji {
-                kanji_index.entry(k).or_insert_with(Vec::new).push(entry_idx);
-            }
-            for r in readings {
-                reading_index.entry(r).or_insert_with(Vec::new).push(entry_idx);
-            }
-        }
+    /// [`JMdict::from_reader`] with glosses as in [`JMdict::from_json_with_langs`]
+    pub fn from_reader_with_langs(
+        reader: impl Read,
+        gloss_langs: &[&str],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+        let dict = JMdictBuilder::new(gloss_langs).build(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(dict)
+    }
 
-        Ok(Self {
-            entries,
-            kanji_index,
-            reading_index,
-        })
+    /// [`JMdict::from_reader_with_langs`], calling `progress` with
+    /// `(words_parsed, total_estimate)` every `interval` words and once at the end
+    ///
+    /// `total_bytes` is the reader's length, used to estimate the total.
+    pub fn from_reader_with_progress(
+        reader: impl Read,
+        gloss_langs: &[&str],
+        total_bytes: u64,
+        interval: usize,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let bytes_read = Rc::new(Cell::new(0));
+        let reader = CountingReader {
+            inner: reader,
+            count: bytes_read.clone(),
+        };
+        let tracker = ProgressTracker {
+            callback: progress,
+            interval: interval.max(1),
+            bytes_read,
+            total_bytes,
+            parsed: 0,
+        };
+
+        let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+        let dict = JMdictBuilder::new(gloss_langs)
+            .with_progress(tracker)
+            .build(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(dict)
     }
 
     /// Get the number of entries in the dictionary
@@ -169,35 +472,74 @@ impl JMdict {
         self.entries.len()
     }
 
+    /// Label every entry as coming from `source`
+    pub fn with_source(mut self, source: &str) -> Self {
+        for entry in &mut self.entries {
+            entry.source = source.to_string();
+        }
+        self
+    }
+
     /// Merge another dictionary into this one
-    /// Entries from the other dictionary with the same ID will override existing ones
+    ///
+    /// Entries from different sources are all kept, even with the same ID.
+    /// An entry with the same source and ID replaces the existing one.
     pub fn merge(mut self, other: JMdict) -> Self {
-        use std::collections::HashSet;
-
-        // Collect IDs from base dictionary
-        let mut existing_ids: HashSet<String> = self.entries.iter().map(|e| e.id.clone()).collect();
-
-        // Add entries from other dictionary
-        for entry in other.entries.into_iter() {
-            if existing_ids.contains(&entry.id) {
-                // Override: remove old entry and add new one
-                self.entries.retain(|e| e.id != entry.id);
+        let mut positions: HashMap<(String, String), usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, e)| ((e.source.clone(), e.id.clone()), idx))
+            .collect();
+
+        for entry in other.entries {
+            let key = (entry.source.clone(), entry.id.clone());
+            match positions.get(&key) {
+                Some(&idx) => {
+                    // Readings may have changed, so re-index just this entry
+                    self.unindex_entry(idx);
+                    self.entries[idx] = entry;
+                    self.index_entry(idx);
+                }
+                None => {
+                    let idx = self.entries.len();
+                    positions.insert(key, idx);
+                    self.entries.push(entry);
+                    self.index_entry(idx);
+                }
             }
-            existing_ids.insert(entry.id.clone());
+        }
 
-            let entry_idx = self.entries.len();
-            self.entries.push(entry.clone());
+        self
+    }
 
-            // Update indices
-            for k in &entry.kanji {
-                self.kanji_index.entry(k.clone()).or_insert_with(Vec::new).push(entry_idx);
-            }
-            for r in &entry.readings {
-                self.reading_index.entry(r.clone()).or_insert_with(Vec::new).push(entry_idx);
-            }
+    /// Add the entry at `idx` to the kanji and reading indices
+    fn index_entry(&mut self, idx: usize) {
+        let entry = &self.entries[idx];
+        for k in &entry.kanji {
+            self.kanji_index.entry(k.clone()).or_default().push(idx);
+        }
+        for r in &entry.readings {
+            self.reading_index.entry(r.clone()).or_default().push(idx);
         }
+    }
 
-        self
+    /// Remove the entry at `idx` from the kanji and reading indices
+    fn unindex_entry(&mut self, idx: usize) {
+        let entry = &self.entries[idx];
+        for (index, keys) in [
+            (&mut self.kanji_index, &entry.kanji),
+            (&mut self.reading_index, &entry.readings),
+        ] {
+            for key in keys {
+                if let Some(indices) = index.get_mut(key) {
+                    indices.retain(|&i| i != idx);
+                    if indices.is_empty() {
+                        index.remove(key);
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -226,13 +568,41 @@ impl Dictionary for JMdict {
             .collect()
     }
 
-    fn search(&self, query: &str, _options: SearchOptions) -> Vec<Box<dyn DictionaryEntry>> {
-        self.lookup_exact(query)
+    fn search(&self, query: &str, options: SearchOptions) -> Vec<Box<dyn DictionaryEntry>> {
+        if !matches!(options.match_type, MatchType::Prefix) || query.is_empty() {
+            return self.lookup_exact(query);
+        }
+
+        // Shortest headwords first, so the exact match leads
+        let from = (Bound::Included(query), Bound::Unbounded);
+        let mut keys: Vec<(&String, &Vec<usize>)> = [&self.kanji_index, &self.reading_index]
+            .into_iter()
+            .flat_map(|index| {
+                index
+                    .range::<str, _>(from)
+                    .take_while(|(key, _)| key.starts_with(query))
+            })
+            .collect();
+        keys.sort_by_key(|(key, _)| (key.chars().count(), *key));
+
+        let mut seen = HashSet::new();
+        keys.into_iter()
+            .flat_map(|(_, indices)| indices)
+            .filter(|&&idx| seen.insert(idx))
+            .take(options.max_results)
+            .filter_map(|&idx| self.entries.get(idx))
+            .map(|e| Box::new(e.clone()) as Box<dyn DictionaryEntry>)
+            .collect()
     }
 
     fn get_by_id(&self, id: &str) -> Option<Box<dyn DictionaryEntry>> {
+        // Merged dictionaries can share raw ids, so match the source as well
         self.entries.iter()
-            .find(|e| e.id == id)
+            .find(|e| {
+                id.strip_prefix(e.source.as_str())
+                    .and_then(|rest| rest.strip_prefix(':'))
+                    == Some(e.id.as_str())
+            })
             .map(|e| Box::new(e.clone()) as Box<dyn DictionaryEntry>)
     }
 
@@ -245,3 +615,315 @@ impl Dictionary for JMdict {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MULTI_LANG: &str = r#"{"words": [
+        {"id": "1", "kanji": [{"text": "水"}], "kana": [{"text": "みず"}], "sense": [
+            {"partOfSpeech": ["n"], "gloss": [
+                {"lang": "eng", "text": "water"},
+                {"lang": "ger", "text": "Wasser"}
+            ]}
+        ]},
+        {"id": "2", "kanji": [{"text": "火"}], "kana": [{"text": "ひ"}], "sense": [
+            {"partOfSpeech": ["n"], "gloss": [{"lang": "eng", "text": "fire"}]}
+        ]}
+    ]}"#;
+
+    fn meanings(dict: &JMdict, word: &str) -> Vec<String> {
+        let entries = dict.lookup_exact(word);
+        entries[0].definitions().into_iter().map(|d| d.text).collect()
+    }
+
+    #[test]
+    fn test_selects_requested_gloss_language() {
+        let dict = JMdict::from_json_with_lang(MULTI_LANG, "ger").unwrap();
+        assert_eq!(meanings(&dict, "水"), vec!["Wasser"]);
+    }
+
+    #[test]
+    fn test_falls_back_to_english() {
+        let dict = JMdict::from_json_with_lang(MULTI_LANG, "ger").unwrap();
+        assert_eq!(dict.entry_count(), 2);
+        assert_eq!(meanings(&dict, "火"), vec!["fire"]);
+    }
+
+    #[test]
+    fn test_chain_falls_back_per_sense() {
+        let json = r#"{"words": [
+            {"id": "3", "kanji": [{"text": "木"}], "kana": [{"text": "き"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "tree"}, {"lang": "spa", "text": "árbol"}]},
+                {"gloss": [{"lang": "eng", "text": "wood"}, {"lang": "ger", "text": "Holz"}]}
+            ]}
+        ]}"#;
+
+        let dict = JMdict::from_json_with_langs(json, &["spa", "eng"]).unwrap();
+        assert_eq!(meanings(&dict, "木"), vec!["árbol", "wood"]);
+
+        let dict = JMdict::from_json_with_langs(MULTI_LANG, &["spa", "eng"]).unwrap();
+        assert_eq!(meanings(&dict, "水"), vec!["water"]);
+    }
+
+    #[test]
+    fn test_from_reader_matches_from_json() {
+        let parsed = JMdict::from_json(MULTI_LANG).unwrap();
+        let streamed = JMdict::from_reader(MULTI_LANG.as_bytes()).unwrap();
+
+        assert_eq!(streamed.entry_count(), parsed.entry_count());
+        assert_eq!(streamed.kanji_index.len(), parsed.kanji_index.len());
+        assert_eq!(streamed.reading_index.len(), parsed.reading_index.len());
+        assert_eq!(meanings(&streamed, "水"), meanings(&parsed, "水"));
+    }
+
+    #[test]
+    fn test_progress_counts_increase() {
+        let words: Vec<String> = (0..7)
+            .map(|i| {
+                format!(
+                    r#"{{"id": "{i}", "kana": [{{"text": "か{i}"}}], "sense": [{{"gloss": [{{"lang": "eng", "text": "word {i}"}}]}}]}}"#
+                )
+            })
+            .collect();
+        let json = format!(r#"{{"words": [{}]}}"#, words.join(","));
+
+        let mut reports = Vec::new();
+        let dict = JMdict::from_reader_with_progress(
+            json.as_bytes(),
+            &[DEFAULT_GLOSS_LANG],
+            json.len() as u64,
+            2,
+            &mut |done, total| reports.push((done, total)),
+        )
+        .unwrap();
+
+        assert_eq!(dict.entry_count(), 7);
+        let done: Vec<usize> = reports.iter().map(|&(done, _)| done).collect();
+        assert_eq!(done, vec![2, 4, 6, 7]);
+        assert!(reports.iter().all(|&(done, total)| total >= done));
+        assert_eq!(reports.last(), Some(&(7, 7)));
+    }
+
+    #[test]
+    fn test_from_reader_skips_other_keys() {
+        let json = r#"{"version": "3.5", "tags": {"n": "noun"}, "words": [
+            {"id": "1", "kana": [{"text": "みず"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "water"}]}
+            ]}
+        ]}"#;
+
+        let dict = JMdict::from_reader(json.as_bytes()).unwrap();
+        assert_eq!(dict.entry_count(), 1);
+        assert!(JMdict::from_reader(r#"{"version": "3.5"}"#.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_merge_keeps_both_sources() {
+        let tech = r#"{"words": [
+            {"id": "1", "kanji": [{"text": "水"}], "kana": [{"text": "すい"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "hydro- (prefix)"}]}
+            ]}
+        ]}"#;
+
+        let base = JMdict::from_json(MULTI_LANG).unwrap();
+        let tech = JMdict::from_json(tech).unwrap().with_source("Tech");
+        let merged = base.merge(tech);
+
+        let sources: Vec<String> = merged
+            .lookup_exact("水")
+            .iter()
+            .filter_map(|e| e.source())
+            .collect();
+        assert_eq!(sources, vec!["JMdict", "Tech"]);
+        assert_eq!(merged.lookup_exact("火").len(), 1);
+
+        // Both entries have id 1, the source tells them apart
+        let readings = |id: &str| merged.get_by_id(id).map(|e| e.readings());
+        assert_eq!(readings("JMdict:1"), Some(vec!["みず".to_string()]));
+        assert_eq!(readings("Tech:1"), Some(vec!["すい".to_string()]));
+        assert!(readings("1").is_none());
+    }
+
+    #[test]
+    fn test_merge_same_source_replaces_entry() {
+        let update = r#"{"words": [
+            {"id": "1", "kanji": [{"text": "水"}], "kana": [{"text": "みず"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "cold water"}]}
+            ]}
+        ]}"#;
+
+        let merged = JMdict::from_json(MULTI_LANG)
+            .unwrap()
+            .merge(JMdict::from_json(update).unwrap());
+
+        assert_eq!(merged.entry_count(), 2);
+        assert_eq!(meanings(&merged, "水"), vec!["cold water"]);
+        assert_eq!(meanings(&merged, "火"), vec!["fire"]);
+    }
+
+    #[test]
+    fn test_merge_updates_indices_in_place() {
+        let update = r#"{"words": [
+            {"id": "1", "kanji": [{"text": "氷"}], "kana": [{"text": "こおり"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "ice"}]}
+            ]},
+            {"id": "3", "kanji": [{"text": "水"}], "kana": [{"text": "みず"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "liquid"}]}
+            ]}
+        ]}"#;
+
+        let merged = JMdict::from_json(MULTI_LANG)
+            .unwrap()
+            .merge(JMdict::from_json(update).unwrap());
+
+        // Entry 1 moved from 水 to 氷, entry 3 is new under 水
+        assert_eq!(merged.entry_count(), 3);
+        assert_eq!(meanings(&merged, "水"), vec!["liquid"]);
+        assert_eq!(merged.lookup_exact("水").len(), 1);
+        assert_eq!(meanings(&merged, "こおり"), vec!["ice"]);
+        assert_eq!(meanings(&merged, "火"), vec!["fire"]);
+        assert!(merged.lookup_exact("みず").iter().all(|e| e.id() == "3"));
+    }
+
+    /// Every index points at an entry that has the indexed kanji or reading
+    fn assert_no_dangling_indices(dict: &JMdict) {
+        for (key, indices) in &dict.kanji_index {
+            for &idx in indices {
+                assert!(dict.entries[idx].kanji.contains(key), "{} -> {}", key, idx);
+            }
+        }
+        for (key, indices) in &dict.reading_index {
+            for &idx in indices {
+                assert!(dict.entries[idx].readings.contains(key), "{} -> {}", key, idx);
+            }
+        }
+    }
+
+    #[test]
+    fn test_override_leaves_no_dangling_indices() {
+        let base = r#"{"words": [
+            {"id": "1", "kanji": [{"text": "水"}], "kana": [{"text": "みず"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "water"}]}
+            ]}
+        ]}"#;
+        let override_ = r#"{"words": [
+            {"id": "1", "kanji": [{"text": "水"}], "kana": [{"text": "すい"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "Wednesday"}]}
+            ]}
+        ]}"#;
+
+        let merged = JMdict::from_json(base)
+            .unwrap()
+            .merge(JMdict::from_json(override_).unwrap());
+
+        assert_no_dangling_indices(&merged);
+        assert_eq!(meanings(&merged, "水"), vec!["Wednesday"]);
+        assert_eq!(meanings(&merged, "すい"), vec!["Wednesday"]);
+        assert!(merged.lookup_exact("みず").is_empty());
+    }
+
+    #[test]
+    fn test_prefix_search_shortest_first() {
+        let json = r#"{"words": [
+            {"id": "1", "kanji": [{"text": "食べ物"}], "kana": [{"text": "たべもの"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "food"}]}
+            ]},
+            {"id": "2", "kanji": [{"text": "食べる"}], "kana": [{"text": "たべる"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "to eat"}]}
+            ]},
+            {"id": "3", "kanji": [{"text": "水"}], "kana": [{"text": "みず"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "water"}]}
+            ]}
+        ]}"#;
+        let dict = JMdict::from_json(json).unwrap();
+        let prefix = |query: &str, max_results: usize| -> Vec<String> {
+            let options = SearchOptions {
+                max_results,
+                match_type: MatchType::Prefix,
+                ..Default::default()
+            };
+            dict.search(query, options).iter().map(|e| e.id()).collect()
+        };
+
+        assert_eq!(prefix("たべ", 10), vec!["2", "1"]);
+        assert_eq!(prefix("食べ", 1), vec!["2"]);
+        assert!(prefix("火", 10).is_empty());
+        // Exact search is unchanged
+        assert!(dict.search("たべ", SearchOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_default_is_english() {
+        let dict = JMdict::from_json(MULTI_LANG).unwrap();
+        assert_eq!(meanings(&dict, "水"), vec!["water"]);
+    }
+
+    #[test]
+    fn test_priority_tags_mark_common() {
+        let json = r#"{"words": [
+            {"id": "8", "kanji": [{"text": "新聞", "priority": ["news1", "nf05"]}],
+             "kana": [{"text": "しんぶん", "priority": ["news1"]}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "newspaper"}]}
+            ]},
+            {"id": "9", "kana": [{"text": "いつ", "common": true}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "when"}]}
+            ]},
+            {"id": "10", "kanji": [{"text": "鶍"}], "kana": [{"text": "いすか"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "crossbill"}]}
+            ]}
+        ]}"#;
+        let dict = JMdict::from_json(json).unwrap();
+        let metadata = |word: &str| dict.lookup_exact(word)[0].metadata();
+
+        assert_eq!(metadata("新聞")["common"], true);
+        assert_eq!(metadata("新聞")["frequency_rank"], 2500);
+        assert_eq!(metadata("いつ")["common"], true);
+        assert_eq!(metadata("いつ")["frequency_rank"], COMMON_RANK);
+        assert_eq!(metadata("鶍")["common"], false);
+        assert!(metadata("鶍")["frequency_rank"].is_null());
+    }
+
+    #[test]
+    fn test_field_tags_reach_definitions() {
+        let json = r#"{"words": [
+            {"id": "7", "kanji": [{"text": "鼠"}], "kana": [{"text": "ねずみ"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "mouse (animal)"}]},
+                {"field": ["comp"], "gloss": [{"lang": "eng", "text": "mouse (device)"}]}
+            ]}
+        ]}"#;
+        let dict = JMdict::from_json(json).unwrap();
+        let entry = &dict.lookup_exact("鼠")[0];
+
+        let definitions = entry.definitions();
+        assert!(definitions[0].tags.is_empty());
+        assert_eq!(definitions[1].tags, vec!["comp"]);
+        assert_eq!(entry.metadata()["fields"], serde_json::json!(["comp"]));
+    }
+
+    #[test]
+    fn test_sense_tags_kept_and_rare_senses_dropped() {
+        let json = r#"{"words": [
+            {"id": "5", "kanji": [{"text": "御座る"}], "kana": [{"text": "ござる"}], "sense": [
+                {"misc": ["arch"], "gloss": [{"lang": "eng", "text": "to be (archaic)"}]},
+                {"field": ["ling"], "gloss": [{"lang": "eng", "text": "to be (polite)"}]}
+            ]},
+            {"id": "6", "kana": [{"text": "なり"}], "sense": [
+                {"misc": ["obs"], "gloss": [{"lang": "eng", "text": "to be"}]}
+            ]}
+        ]}"#;
+        let dict = JMdict::from_json(json).unwrap();
+
+        let definitions = dict.lookup_exact("御座る")[0].definitions();
+        assert_eq!(definitions[0].tags, vec!["arch"]);
+        assert_eq!(definitions[1].tags, vec!["ling"]);
+
+        let texts = |definitions: Vec<Definition>| -> Vec<String> {
+            definitions.into_iter().map(|d| d.text).collect()
+        };
+        assert_eq!(texts(common_senses(definitions)), vec!["to be (polite)"]);
+        // The only meaning stays even though it's tagged
+        let only_rare = dict.lookup_exact("なり")[0].definitions();
+        assert_eq!(texts(common_senses(only_rare)), vec!["to be"]);
+    }
+}
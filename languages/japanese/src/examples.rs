@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Example sentences indexed by dictionary form
+///
+/// Reads the Tanaka/Tatoeba `examples.utf` format, where each `A:` line holds
+/// a sentence and its translation and the following `B:` line lists the words it
+/// contains in dictionary form:
+///
+/// ```text
+/// A: 彼は忙しい生活の中で家族と会うことがない。	He doesn't see his family in his busy life.#ID=303645_100000
+/// B: 彼(かれ)[01] は 忙しい 生活 の 中 で 家族 と 会う 事{こと} が ない
+/// ```
+#[derive(Debug, Default)]
+pub struct ExampleStore {
+    sentences: Vec<(String, String)>,
+    index: HashMap<String, Vec<usize>>,
+}
+
+impl ExampleStore {
+    pub fn load_from_file(path: &Path) -> Result<Self, std::io::Error> {
+        let text = std::fs::read_to_string(path)?;
+        let store = Self::parse(&text);
+        tracing::info!("Loaded {} example sentences from {}", store.len(), path.display());
+        Ok(store)
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let mut store = Self::default();
+        let mut current: Option<usize> = None;
+
+        for line in text.lines() {
+            if let Some(rest) = line.strip_prefix("A: ") {
+                let rest = rest.split("#ID=").next().unwrap_or(rest);
+                current = rest.split_once('\t').map(|(japanese, translation)| {
+                    store
+                        .sentences
+                        .push((japanese.trim().to_string(), translation.trim().to_string()));
+                    store.sentences.len() - 1
+                });
+            } else if let Some(words) = line.strip_prefix("B: ")
+                && let Some(idx) = current.take()
+            {
+                for word in words.split_whitespace() {
+                    let base = word
+                        .split(['(', '[', '{', '~'])
+                        .next()
+                        .unwrap_or(word);
+                    if base.is_empty() {
+                        continue;
+                    }
+                    let indices = store.index.entry(base.to_string()).or_default();
+                    if indices.last() != Some(&idx) {
+                        indices.push(idx);
+                    }
+                }
+            }
+        }
+
+        store
+    }
+
+    /// Example sentences using `term`, as (Japanese, translation) pairs
+    pub fn examples(&self, term: &str) -> Vec<(String, String)> {
+        self.index
+            .get(term)
+            .into_iter()
+            .flatten()
+            .filter_map(|&idx| self.sentences.get(idx).cloned())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sentences.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sentences.is_empty()
+    }
+}
+
+/// Example store that is only read from disk on first use
+pub struct LazyExamples {
+    path: PathBuf,
+    store: OnceLock<ExampleStore>,
+}
+
+impl LazyExamples {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            store: OnceLock::new(),
+        }
+    }
+
+    pub fn get(&self) -> &ExampleStore {
+        self.store.get_or_init(|| {
+            ExampleStore::load_from_file(&self.path).unwrap_or_else(|e| {
+                tracing::warn!("Failed to load examples from {}: {}", self.path.display(), e);
+                ExampleStore::default()
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: &str = "\
+A: 彼は忙しい生活の中で家族と会うことがない。\tHe doesn't see his family in his busy life.#ID=303645_100000
+B: 彼(かれ)[01] は 忙しい 生活 の 中 で 家族 と 会う 事{こと} が ない
+A: 家族と一緒に食べる。\tI eat with my family.#ID=1_2
+B: 家族 と 一緒 に 食べる{食べる}
+";
+
+    #[test]
+    fn test_examples_for_term() {
+        let store = ExampleStore::parse(EXAMPLES);
+        assert_eq!(store.len(), 2);
+
+        let family = store.examples("家族");
+        assert_eq!(family.len(), 2);
+        assert_eq!(
+            family[0],
+            (
+                "彼は忙しい生活の中で家族と会うことがない。".to_string(),
+                "He doesn't see his family in his busy life.".to_string()
+            )
+        );
+
+        assert_eq!(store.examples("彼").len(), 1);
+        assert_eq!(store.examples("事").len(), 1);
+        assert_eq!(store.examples("食べる")[0].1, "I eat with my family.");
+        assert!(store.examples("猫").is_empty());
+    }
+
+    #[test]
+    fn test_lazy_missing_file_is_empty() {
+        let lazy = LazyExamples::new("/nonexistent/examples.utf");
+        assert!(lazy.get().is_empty());
+    }
+}
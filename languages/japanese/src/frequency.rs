@@ -73,25 +73,24 @@ impl JapaneseFrequency {
 
     /// Get frequency level (Common, Uncommon, Rare)
     pub fn get_level(&self, word: &str) -> FrequencyLevel {
-        match self.get_rank(word) {
-            Some(rank) if rank <= 1000 => FrequencyLevel::VeryCommon,
-            Some(rank) if rank <= 5000 => FrequencyLevel::Common,
-            Some(rank) if rank <= 10000 => FrequencyLevel::Uncommon,
-            Some(_) => FrequencyLevel::Rare,
-            None => FrequencyLevel::Unknown,
-        }
+        FrequencyLevel::from_rank(self.get_rank(word))
     }
 
     /// Get star rating (1-5 stars based on frequency)
     pub fn get_stars(&self, word: &str) -> u8 {
-        match self.get_rank(word) {
-            Some(rank) if rank <= 500 => 5,
-            Some(rank) if rank <= 2000 => 4,
-            Some(rank) if rank <= 5000 => 3,
-            Some(rank) if rank <= 10000 => 2,
-            Some(_) => 1,
-            None => 0,
-        }
+        stars_for_rank(self.get_rank(word))
+    }
+}
+
+/// Star rating for a frequency rank, 0 when the rank is unknown
+pub fn stars_for_rank(rank: Option<u32>) -> u8 {
+    match rank {
+        Some(rank) if rank <= 500 => 5,
+        Some(rank) if rank <= 2000 => 4,
+        Some(rank) if rank <= 5000 => 3,
+        Some(rank) if rank <= 10000 => 2,
+        Some(_) => 1,
+        None => 0,
     }
 }
 
@@ -105,6 +104,16 @@ pub enum FrequencyLevel {
 }
 
 impl FrequencyLevel {
+    pub fn from_rank(rank: Option<u32>) -> Self {
+        match rank {
+            Some(rank) if rank <= 1000 => FrequencyLevel::VeryCommon,
+            Some(rank) if rank <= 5000 => FrequencyLevel::Common,
+            Some(rank) if rank <= 10000 => FrequencyLevel::Uncommon,
+            Some(_) => FrequencyLevel::Rare,
+            None => FrequencyLevel::Unknown,
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             FrequencyLevel::VeryCommon => "Very Common",
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+pub use saya_types::JlptLevel;
+
 /// JLPT level provider
 pub struct JlptLevels {
     levels: HashMap<String, JlptLevel>,
@@ -62,7 +64,7 @@ impl JlptLevels {
         for line in content.lines() {
             let parts: Vec<&str> = line.split('\t').collect();
             if parts.len() >= 2 {
-                if let Some(level) = JlptLevel::from_str(parts[1]) {
+                if let Some(level) = JlptLevel::from_name(parts[1]) {
                     levels.insert(parts[0].to_string(), level);
                 }
             }
@@ -86,59 +88,3 @@ impl JlptLevels {
         self.get_level(word).map(|l| l.badge())
     }
 }
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub enum JlptLevel {
-    N5, // Beginner (~800 words)
-    N4, // Elementary (~1500 words)
-    N3, // Intermediate (~3750 words)
-    N2, // Upper intermediate (~6000 words)
-    N1, // Advanced (~10000 words)
-}
-
-impl JlptLevel {
-    /// Parse level from string
-    pub fn from_str(s: &str) -> Option<Self> {
-        match s.to_uppercase().as_str() {
-            "N5" => Some(JlptLevel::N5),
-            "N4" => Some(JlptLevel::N4),
-            "N3" => Some(JlptLevel::N3),
-            "N2" => Some(JlptLevel::N2),
-            "N1" => Some(JlptLevel::N1),
-            _ => None,
-        }
-    }
-
-    /// Get level string
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            JlptLevel::N5 => "N5",
-            JlptLevel::N4 => "N4",
-            JlptLevel::N3 => "N3",
-            JlptLevel::N2 => "N2",
-            JlptLevel::N1 => "N1",
-        }
-    }
-
-    /// Get level description
-    pub fn description(&self) -> &'static str {
-        match self {
-            JlptLevel::N5 => "N5 (Beginner)",
-            JlptLevel::N4 => "N4 (Elementary)",
-            JlptLevel::N3 => "N3 (Intermediate)",
-            JlptLevel::N2 => "N2 (Upper Intermediate)",
-            JlptLevel::N1 => "N1 (Advanced)",
-        }
-    }
-
-    /// Get color badge
-    pub fn badge(&self) -> String {
-        match self {
-            JlptLevel::N5 => "🟢 N5".to_string(),
-            JlptLevel::N4 => "🟡 N4".to_string(),
-            JlptLevel::N3 => "🟠 N3".to_string(),
-            JlptLevel::N2 => "🔴 N2".to_string(),
-            JlptLevel::N1 => "🟣 N1".to_string(),
-        }
-    }
-}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+/// Breakdown of a single kanji
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KanjiInfo {
+    pub literal: char,
+    pub strokes: u8,
+    /// Radicals/components the kanji is built from
+    pub radicals: Vec<String>,
+    pub on_readings: Vec<String>,
+    pub kun_readings: Vec<String>,
+    pub meanings: Vec<String>,
+}
+
+/// Kanji dictionary provider
+pub struct KanjiDict {
+    kanji: HashMap<char, KanjiInfo>,
+}
+
+/// Built-in entries, same format as [`KanjiDict::load_from_file`]
+const DEFAULT_KANJI: &str = "\
+日\t4\t日\tニチ ジツ\tひ -び -か\tday; sun; Japan
+本\t5\t木 一\tホン\tもと\tbook; origin; main
+人\t2\t人\tジン ニン\tひと -り -と\tperson
+水\t4\t水\tスイ\tみず\twater
+火\t4\t火\tカ\tひ -び ほ-\tfire
+木\t4\t木\tボク モク\tき こ-\ttree; wood
+山\t3\t山\tサン セン\tやま\tmountain
+川\t3\t川\tセン\tかわ\triver; stream
+大\t3\t大\tダイ タイ\tおお- おお.きい\tlarge; big
+小\t3\t小\tショウ\tちい.さい こ- お-\tlittle; small
+学\t8\t⺍ 冖 子\tガク\tまな.ぶ\tstudy; learning
+生\t5\t生\tセイ ショウ\tい.きる う.まれる なま\tlife; genuine; birth
+先\t6\t⺧ 儿\tセン\tさき ま.ず\tbefore; ahead; previous
+食\t9\t人 良\tショク ジキ\tく.う た.べる\teat; food
+飲\t12\t飠 欠\tイン\tの.む\tdrink
+語\t14\t言 五 口\tゴ\tかた.る かた.らう\tword; speech; language
+漢\t13\t氵 艹 口 夫\tカン\t\tChina; Sino-
+字\t6\t宀 子\tジ\tあざ\tcharacter; letter
+";
+
+impl KanjiDict {
+    /// Create empty kanji database
+    pub fn new() -> Self {
+        Self {
+            kanji: HashMap::new(),
+        }
+    }
+
+    /// Create with some common kanji
+    pub fn with_defaults() -> Self {
+        Self::parse(DEFAULT_KANJI)
+    }
+
+    /// Load kanji from TSV file
+    ///
+    /// One kanji per line: `kanji\tstrokes\tradicals\ton\tkun\tmeanings`, where
+    /// radicals and readings are space separated and meanings are `;` separated.
+    pub fn load_from_file(path: &str) -> Result<Self, std::io::Error> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(Self::parse(&content))
+    }
+
+    fn parse(content: &str) -> Self {
+        let mut kanji = HashMap::new();
+
+        for line in content.lines() {
+            if let Some(info) = parse_line(line) {
+                kanji.insert(info.literal, info);
+            }
+        }
+
+        Self { kanji }
+    }
+
+    /// Get breakdown for a kanji
+    pub fn get(&self, ch: char) -> Option<&KanjiInfo> {
+        self.kanji.get(&ch)
+    }
+}
+
+impl Default for KanjiDict {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_line(line: &str) -> Option<KanjiInfo> {
+    let parts: Vec<&str> = line.split('\t').collect();
+    if parts.len() < 6 {
+        return None;
+    }
+
+    let mut chars = parts[0].chars();
+    let literal = chars.next()?;
+    if chars.next().is_some() {
+        return None;
+    }
+
+    let words = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+
+    Some(KanjiInfo {
+        literal,
+        strokes: parts[1].trim().parse().ok()?,
+        radicals: words(parts[2]),
+        on_readings: words(parts[3]),
+        kun_readings: words(parts[4]),
+        meanings: parts[5]
+            .split(';')
+            .map(str::trim)
+            .filter(|m| !m.is_empty())
+            .map(String::from)
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_kanji_breakdown() {
+        let dict = KanjiDict::with_defaults();
+        let info = dict.get('語').unwrap();
+
+        assert_eq!(info.strokes, 14);
+        assert_eq!(info.radicals, vec!["言", "五", "口"]);
+        assert_eq!(info.on_readings, vec!["ゴ"]);
+        assert_eq!(info.kun_readings, vec!["かた.る", "かた.らう"]);
+        assert_eq!(info.meanings, vec!["word", "speech", "language"]);
+    }
+
+    #[test]
+    fn test_unknown_and_malformed() {
+        let dict = KanjiDict::parse("漢字\t13\t\t\t\tx\n猫\tmany\t\t\t\tcat\n");
+        assert!(dict.get('漢').is_none());
+        assert!(dict.get('猫').is_none());
+        assert!(KanjiDict::with_defaults().get('猫').is_none());
+    }
+}
@@ -1,17 +1,24 @@
+pub mod cache;
 pub mod deconjugator;
 pub mod dictionary;
+pub mod examples;
 pub mod frequency;
 pub mod jlpt;
+pub mod kanji;
 pub mod loader;
 pub mod pitch_accent;
 pub mod processor;
+pub mod sentence;
 pub mod translator;
 
 pub use deconjugator::JapaneseDeconjugator;
 pub use dictionary::{JMdict, JMdictEntry};
+pub use examples::{ExampleStore, LazyExamples};
 pub use frequency::{FrequencyLevel, JapaneseFrequency};
 pub use jlpt::{JlptLevel, JlptLevels};
+pub use kanji::{KanjiDict, KanjiInfo};
 pub use loader::JMdictLoader;
 pub use pitch_accent::{JapanesePitchAccent, PitchPattern};
-pub use processor::JapaneseProcessor;
+pub use processor::{JapaneseProcessor, WordAnalysis};
+pub use sentence::split_sentences;
 pub use translator::JapaneseTranslator;
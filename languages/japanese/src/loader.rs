@@ -1,28 +1,131 @@
 use std::path::Path;
-use crate::dictionary::JMdict;
+use crate::cache::{cache_path, load_or_build};
+use crate::dictionary::{DEFAULT_GLOSS_LANG, JMdict, PROGRESS_INTERVAL};
+
+const EMBEDDED_JSON: &str = include_str!("../data/jmdict_eng.json");
 
 pub struct JMdictLoader;
 
 impl JMdictLoader {
     /// Load embedded dictionary data
     pub fn load_embedded() -> Result<JMdict, Box<dyn std::error::Error>> {
-        let json = include_str!("../data/jmdict_eng.json");
+        Self::load_embedded_with_lang(DEFAULT_GLOSS_LANG)
+    }
+
+    /// Load embedded dictionary data with glosses in `gloss_lang`
+    pub fn load_embedded_with_lang(gloss_lang: &str) -> Result<JMdict, Box<dyn std::error::Error>> {
+        Self::load_embedded_with_langs(&[gloss_lang])
+    }
+
+    /// Load embedded dictionary data, see [`JMdict::from_json_with_langs`]
+    pub fn load_embedded_with_langs(
+        gloss_langs: &[&str],
+    ) -> Result<JMdict, Box<dyn std::error::Error>> {
         tracing::info!("Loading embedded JMdict dictionary...");
-        let dict = JMdict::from_json(json)?;
+        let dict = JMdict::from_json_with_langs(EMBEDDED_JSON, gloss_langs)?;
+        tracing::info!("Loaded {} dictionary entries", dict.entry_count());
+        Ok(dict)
+    }
+
+    /// [`JMdictLoader::load_embedded_with_langs`], reporting progress as in
+    /// [`JMdictLoader::load_from_file_with_progress`]
+    pub fn load_embedded_with_progress(
+        gloss_langs: &[&str],
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<JMdict, Box<dyn std::error::Error>> {
+        tracing::info!("Loading embedded JMdict dictionary...");
+        let dict = JMdict::from_reader_with_progress(
+            EMBEDDED_JSON.as_bytes(),
+            gloss_langs,
+            EMBEDDED_JSON.len() as u64,
+            PROGRESS_INTERVAL,
+            progress,
+        )?;
         tracing::info!("Loaded {} dictionary entries", dict.entry_count());
         Ok(dict)
     }
 
     /// Load dictionary from file path
     pub fn load_from_file(path: &Path) -> Result<JMdict, Box<dyn std::error::Error>> {
+        Self::load_from_file_with_lang(path, DEFAULT_GLOSS_LANG)
+    }
+
+    /// Load dictionary from file path with glosses in `gloss_lang`
+    pub fn load_from_file_with_lang(
+        path: &Path,
+        gloss_lang: &str,
+    ) -> Result<JMdict, Box<dyn std::error::Error>> {
+        Self::load_from_file_with_langs(path, &[gloss_lang])
+    }
+
+    /// Load dictionary from file path, see [`JMdict::from_json_with_langs`]
+    pub fn load_from_file_with_langs(
+        path: &Path,
+        gloss_langs: &[&str],
+    ) -> Result<JMdict, Box<dyn std::error::Error>> {
+        Self::load_from_file_with_progress(path, gloss_langs, &mut |_, _| {})
+    }
+
+    /// Load dictionary from file path, calling `progress` with
+    /// `(words_parsed, total_estimate)` as it goes
+    pub fn load_from_file_with_progress(
+        path: &Path,
+        gloss_langs: &[&str],
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<JMdict, Box<dyn std::error::Error>> {
         tracing::info!("Loading JMdict from file: {}", path.display());
-        let json = std::fs::read_to_string(path)?;
-        let dict = JMdict::from_json(&json)?;
+        let file = std::fs::File::open(path)?;
+        let total_bytes = file.metadata()?.len();
+        let dict = JMdict::from_reader_with_progress(
+            file,
+            gloss_langs,
+            total_bytes,
+            PROGRESS_INTERVAL,
+            progress,
+        )?;
         tracing::info!("Loaded {} dictionary entries from file", dict.entry_count());
         Ok(dict)
     }
 
-    /// Merge two dictionaries (later entries override earlier ones by ID)
+    /// [`JMdictLoader::load_embedded_with_langs`], through a binary cache in `cache_dir`
+    ///
+    /// `progress` is only called when the cache can't be used.
+    pub fn load_embedded_cached(
+        gloss_langs: &[&str],
+        cache_dir: &Path,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<JMdict, Box<dyn std::error::Error>> {
+        let path = cache_path(cache_dir, "embedded", gloss_langs);
+        let key = format!("embedded:{}:{}", EMBEDDED_JSON.len(), gloss_langs.join(","));
+        let (dict, _) = load_or_build(&path, &key, None, || {
+            Self::load_embedded_with_progress(gloss_langs, progress)
+        })?;
+        Ok(dict)
+    }
+
+    /// [`JMdictLoader::load_from_file_with_langs`], through a binary cache in `cache_dir`
+    ///
+    /// The cache is rebuilt whenever the file is modified after it.
+    pub fn load_from_file_cached(
+        path: &Path,
+        gloss_langs: &[&str],
+        cache_dir: &Path,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<JMdict, Box<dyn std::error::Error>> {
+        let modified = std::fs::metadata(path)?.modified().ok();
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let cache = cache_path(cache_dir, &format!("file-{}", stem), gloss_langs);
+        let key = format!("{}:{}", path.display(), gloss_langs.join(","));
+        let (dict, _) = load_or_build(&cache, &key, modified, || {
+            Self::load_from_file_with_progress(path, gloss_langs, progress)
+        })?;
+        Ok(dict)
+    }
+
+    /// Merge two dictionaries, see [`JMdict::merge`]
     pub fn merge(base: JMdict, additional: JMdict) -> JMdict {
         base.merge(additional)
     }
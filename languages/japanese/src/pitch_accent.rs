@@ -18,41 +18,51 @@ impl JapanesePitchAccent {
         let mut accents = HashMap::new();
 
         // Common words with pitch accent patterns
-        // Format: (word, drop position) - 0 = heiban (flat), 1+ = odaka/atamadaka/nakadaka
+        // Format: (word, reading, drop position) - 0 = heiban (flat), 1+ = atamadaka/nakadaka/odaka
         let patterns = [
-            ("日本", 0),  // heiban
-            ("東京", 0),  // heiban
-            ("学校", 0),  // heiban
-            ("先生", 3),  // odaka
-            ("学生", 0),  // heiban
-            ("時間", 0),  // heiban
-            ("本", 1),    // atamadaka
-            ("水", 0),    // heiban
-            ("山", 0),    // heiban
-            ("川", 0),    // heiban
+            ("日本", "にほん", 2),      // nakadaka
+            ("東京", "とうきょう", 0),  // heiban
+            ("学校", "がっこう", 0),    // heiban
+            ("先生", "せんせい", 3),    // nakadaka
+            ("学生", "がくせい", 0),    // heiban
+            ("時間", "じかん", 0),      // heiban
+            ("本", "ほん", 1),          // atamadaka
+            ("水", "みず", 0),          // heiban
+            ("山", "やま", 2),          // odaka
+            ("川", "かわ", 2),          // odaka
+            ("橋", "はし", 2),          // odaka
+            ("男", "おとこ", 3),        // odaka
         ];
 
-        for (word, drop) in patterns {
-            accents.insert(word.to_string(), PitchPattern::from_drop_position(drop));
+        for (word, reading, drop) in patterns {
+            accents.insert(
+                word.to_string(),
+                PitchPattern::from_drop_position_with_length(drop, mora_count(reading)),
+            );
         }
 
         Self { accents }
     }
 
     /// Load pitch accent data from TSV file (word\treading\tdrop_position format)
+    ///
+    /// The reading column is optional (`word\tdrop_position`); without it
+    /// odaka can't be told apart from nakadaka until a reading is known.
     pub fn load_from_file(path: &str) -> Result<Self, std::io::Error> {
         let content = std::fs::read_to_string(path)?;
         let mut accents = HashMap::new();
 
         for line in content.lines() {
             let parts: Vec<&str> = line.split('\t').collect();
-            if parts.len() >= 2 {
-                if let Ok(drop) = parts[1].parse::<u8>() {
-                    accents.insert(
-                        parts[0].to_string(),
-                        PitchPattern::from_drop_position(drop),
-                    );
-                }
+            let pattern = match parts.as_slice() {
+                [_, reading, drop, ..] => drop.parse::<u8>().ok().map(|d| {
+                    PitchPattern::from_drop_position_with_length(d, mora_count(reading))
+                }),
+                [_, drop] => drop.parse::<u8>().ok().map(PitchPattern::from_drop_position),
+                _ => None,
+            };
+            if let Some(pattern) = pattern {
+                accents.insert(parts[0].to_string(), pattern);
             }
         }
 
@@ -64,10 +74,22 @@ impl JapanesePitchAccent {
         self.accents.get(word)
     }
 
+    /// Get pitch accent pattern for a word, classified against its reading
+    pub fn get_pattern_for_reading(&self, word: &str, reading: &str) -> Option<PitchPattern> {
+        self.get_pattern(word).map(|p| {
+            PitchPattern::from_drop_position_with_length(p.drop_position, mora_count(reading))
+        })
+    }
+
     /// Get pitch accent notation string
     pub fn get_notation(&self, word: &str) -> Option<String> {
         self.get_pattern(word).map(|p| p.to_notation())
     }
+
+    /// Get pitch accent notation string, classified against the reading
+    pub fn get_notation_for_reading(&self, word: &str, reading: &str) -> Option<String> {
+        self.get_pattern_for_reading(word, reading).map(|p| p.to_notation())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -79,7 +101,27 @@ pub struct PitchPattern {
 }
 
 impl PitchPattern {
+    /// Create pattern from drop position and the word's mora count
+    ///
+    /// A drop on the last mora is odaka; a drop before it is nakadaka.
+    pub fn from_drop_position_with_length(drop: u8, mora_count: u8) -> Self {
+        let pattern_type = match drop {
+            0 => PatternType::Heiban,
+            1 => PatternType::Atamadaka,
+            d if d == mora_count => PatternType::Odaka,
+            _ => PatternType::Nakadaka,
+        };
+
+        Self {
+            drop_position: drop,
+            pattern_type,
+        }
+    }
+
     /// Create pattern from drop position
+    ///
+    /// Without the mora count odaka can't be detected and is reported as nakadaka,
+    /// prefer [`PitchPattern::from_drop_position_with_length`].
     pub fn from_drop_position(drop: u8) -> Self {
         let pattern_type = match drop {
             0 => PatternType::Heiban,   // 平板型 (flat)
@@ -127,3 +169,59 @@ impl PatternType {
         }
     }
 }
+
+/// Count morae in a kana reading
+///
+/// Small ゃゅょ/ぁぃぅぇぉ combine with the preceding kana; っ, ん and ー each count.
+pub fn mora_count(reading: &str) -> u8 {
+    const SMALL: &[char] = &[
+        'ゃ', 'ゅ', 'ょ', 'ぁ', 'ぃ', 'ぅ', 'ぇ', 'ぉ', 'ゎ',
+        'ャ', 'ュ', 'ョ', 'ァ', 'ィ', 'ゥ', 'ェ', 'ォ', 'ヮ',
+    ];
+
+    reading
+        .chars()
+        .filter(|c| !SMALL.contains(c) && !c.is_whitespace())
+        .count()
+        .min(u8::MAX as usize) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mora_count() {
+        assert_eq!(mora_count("はし"), 2);
+        assert_eq!(mora_count("とうきょう"), 4);
+        assert_eq!(mora_count("がっこう"), 4);
+        assert_eq!(mora_count("コーヒー"), 4);
+    }
+
+    #[test]
+    fn test_odaka_vs_nakadaka() {
+        // 橋 はし: drop after the last mora
+        let hashi = PitchPattern::from_drop_position_with_length(2, mora_count("はし"));
+        assert_eq!(hashi.pattern_type, PatternType::Odaka);
+
+        // 日本 にほん: drop after the 2nd of 3 morae
+        let nihon = PitchPattern::from_drop_position_with_length(2, mora_count("にほん"));
+        assert_eq!(nihon.pattern_type, PatternType::Nakadaka);
+
+        let hon = PitchPattern::from_drop_position_with_length(1, mora_count("ほん"));
+        assert_eq!(hon.pattern_type, PatternType::Atamadaka);
+        let mizu = PitchPattern::from_drop_position_with_length(0, mora_count("みず"));
+        assert_eq!(mizu.pattern_type, PatternType::Heiban);
+    }
+
+    #[test]
+    fn test_reading_reclassifies_stored_drop() {
+        let accents = JapanesePitchAccent::with_defaults();
+        let pattern = accents.get_pattern_for_reading("橋", "はし").unwrap();
+        assert_eq!(pattern.pattern_type, PatternType::Odaka);
+        assert_eq!(
+            accents.get_pattern("先生").unwrap().pattern_type,
+            PatternType::Nakadaka
+        );
+    }
+}
@@ -1,11 +1,17 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use saya_core::language::{LanguageProcessor, Token, LookupResult};
+use saya_core::dictionary::{DictionaryEntry, DictionaryStatus};
+use saya_core::language::{DeconjugationResult, LanguageProcessor, Token, LookupResult};
+use saya_types::DisplayResult;
 use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::canonical_combining_class;
 
 use crate::deconjugator::JapaneseDeconjugator;
-use crate::dictionary::JMdict;
-use crate::frequency::JapaneseFrequency;
+use crate::dictionary::{DEFAULT_GLOSS_LANG, JMdict, common_senses};
+use crate::examples::LazyExamples;
+use crate::frequency::{FrequencyLevel, JapaneseFrequency, stars_for_rank};
 use crate::jlpt::JlptLevels;
+use crate::kanji::{KanjiDict, KanjiInfo};
 use crate::loader::JMdictLoader;
 use crate::pitch_accent::JapanesePitchAccent;
 
@@ -16,8 +22,47 @@ pub struct JapaneseProcessor {
     frequency: JapaneseFrequency,
     pitch_accent: JapanesePitchAccent,
     jlpt: JlptLevels,
+    examples: Option<LazyExamples>,
+    kanji: KanjiDict,
+    dictionary_status: DictionaryStatus,
+    min_deconj_confidence: f32,
+    hide_rare_senses: bool,
 }
 
+/// A word of a sentence split by [`JapaneseProcessor::analyze_sentence`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordAnalysis {
+    /// Character index of the first character, in the text as given
+    pub start: usize,
+    /// Character index just past the last character
+    pub end: usize,
+    pub surface: String,
+    /// Dictionary form, `None` for text the dictionary doesn't know
+    pub term: Option<String>,
+    pub reading: Option<String>,
+    pub definition: Option<String>,
+}
+
+/// Whether normalization joins `c` onto the char before it, like a dakuten
+fn is_combining(c: char) -> bool {
+    std::iter::once(c)
+        .nfkd()
+        .next()
+        .is_some_and(|d| canonical_combining_class(d) != 0)
+}
+
+/// Example sentences attached per result
+const MAX_EXAMPLES: usize = 2;
+
+/// Deconjugations below this are only tried when better guesses found nothing
+pub const DEFAULT_MIN_DECONJ_CONFIDENCE: f32 = 0.7;
+
+/// Conjugation layers stripped at most, e.g. 食べたくなかった is three
+const MAX_DECONJ_DEPTH: usize = 3;
+
+/// Longest token tried, in characters
+const MAX_TOKEN_CHARS: usize = 10;
+
 impl JapaneseProcessor {
     /// Create a new Japanese processor with default configuration (embedded dictionary)
     pub fn new() -> Self {
@@ -26,20 +71,85 @@ impl JapaneseProcessor {
 
     /// Create a new Japanese processor with additional dictionary paths
     pub fn with_additional_dicts(additional_paths: &[String]) -> Self {
+        Self::with_gloss_lang(additional_paths, DEFAULT_GLOSS_LANG)
+    }
+
+    /// Create a new Japanese processor whose glosses are in `gloss_lang`
+    pub fn with_gloss_lang(additional_paths: &[String], gloss_lang: &str) -> Self {
+        Self::with_gloss_langs(additional_paths, &[gloss_lang])
+    }
+
+    /// Create a new Japanese processor taking glosses from the first of `gloss_langs` available
+    pub fn with_gloss_langs(additional_paths: &[String], gloss_langs: &[&str]) -> Self {
         // Load embedded dictionary
-        let mut dict = JMdictLoader::load_embedded()
-            .unwrap_or_else(|e| {
-                tracing::error!("Failed to load embedded dictionary: {}", e);
+        let base = JMdictLoader::load_embedded_with_langs(gloss_langs).map_err(|e| e.to_string());
+        Self::from_base(base, additional_paths, gloss_langs, None, &mut |_, _| {})
+    }
+
+    /// Like [`JapaneseProcessor::with_gloss_langs`], keeping built dictionaries
+    /// in `cache_dir` so later launches skip parsing JSON
+    pub fn with_cache(additional_paths: &[String], gloss_langs: &[&str], cache_dir: &Path) -> Self {
+        Self::with_progress(
+            additional_paths,
+            gloss_langs,
+            Some(cache_dir),
+            &mut |_, _| {},
+        )
+    }
+
+    /// Load dictionaries, optionally cached, calling `progress` with
+    /// `(words_parsed, total_estimate)` for each one that has to be parsed
+    pub fn with_progress(
+        additional_paths: &[String],
+        gloss_langs: &[&str],
+        cache_dir: Option<&Path>,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Self {
+        let base = match cache_dir {
+            Some(dir) => JMdictLoader::load_embedded_cached(gloss_langs, dir, progress),
+            None => JMdictLoader::load_embedded_with_progress(gloss_langs, progress),
+        };
+        let base = base.map_err(|e| e.to_string());
+        Self::from_base(base, additional_paths, gloss_langs, cache_dir, progress)
+    }
+
+    /// Build around the loaded base dictionary, recording why it failed if it did
+    fn from_base(
+        base: Result<JMdict, String>,
+        additional_paths: &[String],
+        gloss_langs: &[&str],
+        cache_dir: Option<&Path>,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Self {
+        let (mut dict, failure) = match base {
+            Ok(dict) => (dict, None),
+            Err(reason) => {
+                tracing::error!("Failed to load embedded dictionary: {}", reason);
                 tracing::warn!("Starting with empty dictionary");
-                JMdict::new()
-            });
+                (JMdict::new(), Some(reason))
+            }
+        };
 
         // Load and merge additional dictionaries
         for path in additional_paths {
-            match JMdictLoader::load_from_file(Path::new(path)) {
+            let loaded = match cache_dir {
+                Some(dir) => {
+                    JMdictLoader::load_from_file_cached(Path::new(path), gloss_langs, dir, progress)
+                }
+                None => JMdictLoader::load_from_file_with_progress(
+                    Path::new(path),
+                    gloss_langs,
+                    progress,
+                ),
+            };
+            match loaded {
                 Ok(additional) => {
                     tracing::info!("Merging additional dictionary from: {}", path);
-                    dict = JMdictLoader::merge(dict, additional);
+                    let source = Path::new(path)
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| path.clone());
+                    dict = JMdictLoader::merge(dict, additional.with_source(&source));
                 }
                 Err(e) => {
                     tracing::warn!("Failed to load dictionary from {}: {}", path, e);
@@ -47,12 +157,357 @@ impl JapaneseProcessor {
             }
         }
 
+        let dictionary_status = match failure {
+            Some(reason) => DictionaryStatus::Failed { reason },
+            None => DictionaryStatus::from_count(dict.entry_count()),
+        };
+
         Self {
             dictionary: dict,
             deconjugator: JapaneseDeconjugator::new(),
             frequency: JapaneseFrequency::with_defaults(),
             pitch_accent: JapanesePitchAccent::with_defaults(),
             jlpt: JlptLevels::with_defaults(),
+            examples: None,
+            kanji: KanjiDict::with_defaults(),
+            dictionary_status,
+            min_deconj_confidence: DEFAULT_MIN_DECONJ_CONFIDENCE,
+            hide_rare_senses: false,
+        }
+    }
+
+    /// Only fall back to deconjugations below `confidence` when the rest find nothing
+    pub fn with_min_deconj_confidence(mut self, confidence: f32) -> Self {
+        self.min_deconj_confidence = confidence;
+        self
+    }
+
+    /// Leave archaic, obsolete and rare senses out of definitions
+    pub fn with_hide_rare_senses(mut self, hide: bool) -> Self {
+        self.hide_rare_senses = hide;
+        self
+    }
+
+    /// Lookup result for a dictionary entry, without the annotations
+    ///
+    /// Field tags (e.g. "med") of the definitions shown go in the `fields` metadata.
+    fn entry_result(&self, entry: &dyn DictionaryEntry) -> LookupResult {
+        let mut result = entry.to_lookup_result();
+        let mut definitions = entry.definitions();
+        if self.hide_rare_senses {
+            definitions = common_senses(definitions);
+            result.definitions = definitions.iter().map(|d| d.text.clone()).collect();
+        }
+
+        let metadata = entry.metadata();
+        let entry_fields: Vec<String> =
+            serde_json::from_value(metadata["fields"].clone()).unwrap_or_default();
+        let mut fields: Vec<String> = Vec::new();
+        for tag in definitions.iter().flat_map(|d| &d.tags) {
+            if entry_fields.contains(tag) && !fields.contains(tag) {
+                fields.push(tag.clone());
+            }
+        }
+        if !fields.is_empty() {
+            result.metadata.insert("fields".to_string(), fields.join(", "));
+        }
+
+        // Priority tags, used when the frequency list doesn't know the word
+        if let Some(rank) = metadata["frequency_rank"].as_u64() {
+            result.metadata.insert("priority_rank".to_string(), rank.to_string());
+        }
+        if metadata["common"].as_bool() == Some(true) {
+            result.metadata.insert("common".to_string(), "true".to_string());
+        }
+        result
+    }
+
+    /// Attach example sentences from a Tatoeba file, loaded on first lookup
+    pub fn with_examples(mut self, path: &str) -> Self {
+        self.examples = Some(LazyExamples::new(path));
+        self
+    }
+
+    /// Replace the built-in kanji data with a KANJIDIC-style TSV file
+    pub fn with_kanji_file(mut self, path: &str) -> Self {
+        match KanjiDict::load_from_file(path) {
+            Ok(kanji) => self.kanji = kanji,
+            Err(e) => tracing::warn!("Failed to load kanji data from {}: {}", path, e),
+        }
+        self
+    }
+
+    /// Stroke count, radicals, readings and meanings for a kanji
+    pub fn kanji_info(&self, ch: char) -> Option<&KanjiInfo> {
+        self.kanji.get(ch)
+    }
+
+    /// Display result for the longest word starting at character `position`
+    ///
+    /// `display` builds it from the top entry, e.g. saya-app's
+    /// `to_display_result` with the configured options.
+    pub fn best_lookup(
+        &self,
+        text: &str,
+        position: usize,
+        display: impl FnOnce(&LookupResult) -> DisplayResult,
+    ) -> Option<DisplayResult> {
+        self.best_match(text, position)
+            .map(|result| display(&result))
+    }
+
+    /// Top entry for the longest word starting at character `position`
+    ///
+    /// `position` indexes the normalized text. Conjugated forms count as
+    /// words, so position 0 of 食べました finds 食べる.
+    pub fn best_match(&self, text: &str, position: usize) -> Option<LookupResult> {
+        let chars: Vec<char> = self.normalize(text).chars().collect();
+        self.longest_match(&chars, position).map(|(_, result)| result)
+    }
+
+    /// Split `text` into words, taking the longest match at each position
+    ///
+    /// Words are matched in the normalized text, but spans index `text` as
+    /// given and cover all of it without overlapping. Whitespace normalizing
+    /// drops stays with the span before it, and runs of characters no word
+    /// starts at become one span without a term.
+    pub fn analyze_sentence(&self, text: &str) -> Vec<WordAnalysis> {
+        let raw: Vec<char> = text.chars().collect();
+        let indexed = self.normalize_indexed(text);
+        let chars: Vec<char> = indexed.iter().map(|&(c, _)| c).collect();
+
+        // Raw index of a normalized position, as a span boundary
+        let boundary = |position: usize| match indexed.get(position) {
+            _ if position == 0 => 0,
+            Some(&(_, raw_index)) => raw_index,
+            None => raw.len(),
+        };
+        let word = |start: usize, end: usize, result: Option<LookupResult>| {
+            let (start, end) = (boundary(start), boundary(end));
+            let (term, reading, definition) = match result {
+                Some(result) => (
+                    Some(result.term),
+                    result.readings.into_iter().next(),
+                    result.definitions.into_iter().next(),
+                ),
+                None => (None, None, None),
+            };
+            WordAnalysis {
+                start,
+                end,
+                surface: raw[start..end].iter().collect(),
+                term,
+                reading,
+                definition,
+            }
+        };
+
+        let mut words = Vec::new();
+        let mut unmatched_from = None;
+        let mut position = 0;
+
+        while position < chars.len() {
+            let Some((len, result)) = self.longest_match(&chars, position) else {
+                unmatched_from.get_or_insert(position);
+                position += 1;
+                continue;
+            };
+
+            if let Some(start) = unmatched_from.take() {
+                words.push(word(start, position, None));
+            }
+            words.push(word(position, position + len, Some(result)));
+            position += len;
+        }
+
+        if let Some(start) = unmatched_from {
+            words.push(word(start, chars.len(), None));
+        }
+        words
+    }
+
+    /// Normalized chars of `text`, each with the index of the raw char it came from
+    ///
+    /// Each char is normalized together with the combining marks after it, so
+    /// half-width ｶﾞ still becomes ガ.
+    fn normalize_indexed(&self, text: &str) -> Vec<(char, usize)> {
+        let raw: Vec<char> = text.chars().collect();
+        let mut normalized = Vec::new();
+        let mut start = 0;
+
+        while start < raw.len() {
+            let end = (start + 1..raw.len())
+                .find(|&i| !is_combining(raw[i]))
+                .unwrap_or(raw.len());
+            let unit: String = raw[start..end].iter().collect();
+            normalized.extend(self.normalize(&unit).chars().map(|c| (c, start)));
+            start = end;
+        }
+
+        normalized
+    }
+
+    /// Length and top entry of the longest word starting at `position` of `chars`
+    fn longest_match(&self, chars: &[char], position: usize) -> Option<(usize, LookupResult)> {
+        let rest = chars.get(position..)?;
+
+        (1..=rest.len().min(MAX_TOKEN_CHARS)).rev().find_map(|len| {
+            let surface: String = rest[..len].iter().collect();
+            let token = Token {
+                surface: surface.clone(),
+                normalized: surface,
+                position,
+            };
+            self.lookup(&token).into_iter().next().map(|result| (len, result))
+        })
+    }
+
+    /// Look up the base forms `word` may be conjugated from, most likely first
+    ///
+    /// Forms that aren't in the dictionary are deconjugated again, up to
+    /// [`MAX_DECONJ_DEPTH`] layers, so 食べなかった reaches 食べる through 食べない.
+    /// Chained results multiply the step confidences and list every step,
+    /// innermost first.
+    fn lookup_deconjugated(&self, word: &str) -> Vec<LookupResult> {
+        let mut seen = HashSet::from([word.to_string()]);
+        let mut frontier = vec![DeconjugationResult {
+            base_form: word.to_string(),
+            conjugation_type: String::new(),
+            confidence: 1.0,
+        }];
+
+        for _ in 0..MAX_DECONJ_DEPTH {
+            let mut candidates = Vec::new();
+            for form in &frontier {
+                for step in self.deconjugator.deconjugate(&form.base_form) {
+                    // Forms already tried would only repeat earlier work
+                    if !seen.insert(step.base_form.clone()) {
+                        continue;
+                    }
+                    let conjugation_type = if form.conjugation_type.is_empty() {
+                        step.conjugation_type
+                    } else {
+                        format!("{} + {}", step.conjugation_type, form.conjugation_type)
+                    };
+                    candidates.push(DeconjugationResult {
+                        base_form: step.base_form,
+                        conjugation_type,
+                        confidence: step.confidence * form.confidence,
+                    });
+                }
+            }
+
+            let results = self.lookup_candidates(word, &mut candidates);
+            if !results.is_empty() || candidates.is_empty() {
+                return results;
+            }
+            frontier = candidates;
+        }
+
+        Vec::new()
+    }
+
+    /// Look up one layer of deconjugation candidates, most likely first
+    ///
+    /// Guesses below `min_deconj_confidence` are only looked up when the
+    /// confident ones produce no dictionary hit.
+    fn lookup_candidates(
+        &self,
+        word: &str,
+        candidates: &mut Vec<DeconjugationResult>,
+    ) -> Vec<LookupResult> {
+        candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+        let (confident, guesses): (Vec<&DeconjugationResult>, Vec<_>) = candidates
+            .iter()
+            .partition(|d| d.confidence >= self.min_deconj_confidence);
+
+        let results = self.lookup_base_forms(word, &confident);
+        if !results.is_empty() {
+            return results;
+        }
+        self.lookup_base_forms(word, &guesses)
+    }
+
+    fn lookup_base_forms(
+        &self,
+        word: &str,
+        candidates: &[&DeconjugationResult],
+    ) -> Vec<LookupResult> {
+        use saya_core::dictionary::Dictionary;
+
+        let mut results = Vec::new();
+        for deconj in candidates {
+            for entry in self.dictionary.lookup_exact(&deconj.base_form) {
+                let mut result = self.entry_result(entry.as_ref());
+
+                // Add conjugation info
+                result.metadata.insert(
+                    "conjugation".to_string(),
+                    format!(
+                        "{} → {} ({})",
+                        word, deconj.base_form, deconj.conjugation_type
+                    ),
+                );
+                result.metadata.insert(
+                    "base_form".to_string(),
+                    deconj.base_form.clone(),
+                );
+                result.metadata.insert(
+                    "conjugation_confidence".to_string(),
+                    deconj.confidence.to_string(),
+                );
+
+                results.push(result);
+            }
+        }
+        results
+    }
+
+    /// Attach frequency, pitch accent, and JLPT metadata
+    fn annotate(&self, result: &mut LookupResult) {
+        let term = &result.term;
+
+        // Frequency data, falling back to the JMdict priority tags
+        let rank = self.frequency.get_rank(term).or_else(|| {
+            result.metadata.get("priority_rank").and_then(|r| r.parse().ok())
+        });
+        if let Some(rank) = rank {
+            result.metadata.insert("frequency_rank".to_string(), rank.to_string());
+        }
+        let level = FrequencyLevel::from_rank(rank);
+        result.metadata.insert("frequency_level".to_string(), level.as_str().to_string());
+        let stars = stars_for_rank(rank);
+        if stars > 0 {
+            result.metadata.insert("frequency_stars".to_string(), "★".repeat(stars as usize));
+        }
+
+        // Pitch accent, classified against the reading so odaka is detected
+        let notation = match result.readings.first() {
+            Some(reading) => self.pitch_accent.get_notation_for_reading(term, reading),
+            None => self.pitch_accent.get_notation(term),
+        };
+        if let Some(notation) = notation {
+            result.metadata.insert("pitch_accent".to_string(), notation);
+        }
+
+        // JLPT level
+        if let Some(badge) = self.jlpt.get_badge(term) {
+            result.metadata.insert("jlpt_level".to_string(), badge);
+        }
+
+        // Example sentences, one "japanese\ttranslation" pair per line
+        if let Some(examples) = &self.examples {
+            let lines: Vec<String> = examples
+                .get()
+                .examples(term)
+                .into_iter()
+                .take(MAX_EXAMPLES)
+                .map(|(japanese, translation)| format!("{}\t{}", japanese, translation))
+                .collect();
+            if !lines.is_empty() {
+                result.metadata.insert("examples".to_string(), lines.join("\n"));
+            }
         }
     }
 }
@@ -76,7 +531,7 @@ impl LanguageProcessor for JapaneseProcessor {
         let mut tokens = Vec::new();
 
         for i in 0..chars.len() {
-            for len in (1..=chars.len().saturating_sub(i).min(10)).rev() {
+            for len in (1..=chars.len().saturating_sub(i).min(MAX_TOKEN_CHARS)).rev() {
                 let surface: String = chars[i..i + len].iter().collect();
                 tokens.push(Token {
                     surface: surface.clone(),
@@ -97,63 +552,419 @@ impl LanguageProcessor for JapaneseProcessor {
             .dictionary
             .lookup_exact(&token.normalized)
             .into_iter()
-            .map(|entry| entry.to_lookup_result())
+            .map(|entry| self.entry_result(entry.as_ref()))
             .collect();
 
         // If direct lookup failed, try deconjugation
         if results.is_empty() {
-            let deconj_results = self.deconjugator.deconjugate(&token.normalized);
-
-            for deconj in deconj_results {
-                let base_results = self.dictionary.lookup_exact(&deconj.base_form);
-
-                for entry in base_results {
-                    let mut result = entry.to_lookup_result();
-
-                    // Add conjugation info
-                    result.metadata.insert(
-                        "conjugation".to_string(),
-                        format!(
-                            "{} → {} ({})",
-                            token.normalized, deconj.base_form, deconj.conjugation_type
-                        ),
-                    );
-                    result.metadata.insert(
-                        "base_form".to_string(),
-                        deconj.base_form.clone(),
-                    );
-
-                    results.push(result);
-                }
-            }
+            results = self.lookup_deconjugated(&token.normalized);
         }
 
         // Add frequency, pitch accent, and JLPT data to all results
         for result in &mut results {
-            let term = &result.term;
+            self.annotate(result);
+            result.metadata.insert("surface".to_string(), token.surface.clone());
+        }
 
-            // Frequency data
-            if let Some(rank) = self.frequency.get_rank(term) {
-                result.metadata.insert("frequency_rank".to_string(), rank.to_string());
-            }
-            let level = self.frequency.get_level(term);
-            result.metadata.insert("frequency_level".to_string(), level.as_str().to_string());
-            let stars = self.frequency.get_stars(term);
-            if stars > 0 {
-                result.metadata.insert("frequency_stars".to_string(), "★".repeat(stars as usize));
-            }
+        results
+    }
 
-            // Pitch accent
-            if let Some(notation) = self.pitch_accent.get_notation(term) {
-                result.metadata.insert("pitch_accent".to_string(), notation);
-            }
+    fn search_prefix(&self, prefix: &str, limit: usize) -> Vec<LookupResult> {
+        use saya_core::dictionary::{Dictionary, MatchType, SearchOptions};
 
-            // JLPT level
-            if let Some(badge) = self.jlpt.get_badge(term) {
-                result.metadata.insert("jlpt_level".to_string(), badge);
-            }
+        let options = SearchOptions {
+            max_results: limit,
+            match_type: MatchType::Prefix,
+            ..Default::default()
+        };
+        let prefix = self.normalize(prefix);
+        let mut results: Vec<LookupResult> = self
+            .dictionary
+            .search(&prefix, options)
+            .into_iter()
+            .map(|entry| self.entry_result(entry.as_ref()))
+            .collect();
+        for result in &mut results {
+            self.annotate(result);
         }
 
         results
     }
+
+    fn character_info(&self, ch: char) -> Option<LookupResult> {
+        let info = self.kanji_info(ch)?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("strokes".to_string(), info.strokes.to_string());
+        metadata.insert("radicals".to_string(), info.radicals.join(" "));
+        metadata.insert("on_readings".to_string(), info.on_readings.join(" "));
+        metadata.insert("kun_readings".to_string(), info.kun_readings.join(" "));
+
+        Some(LookupResult {
+            term: info.literal.to_string(),
+            readings: info.on_readings.iter().chain(&info.kun_readings).cloned().collect(),
+            definitions: info.meanings.clone(),
+            metadata,
+        })
+    }
+
+    fn lookup_by_id(&self, id: &str) -> Option<LookupResult> {
+        use saya_core::dictionary::Dictionary;
+
+        let entry = self.dictionary.get_by_id(id)?;
+        let mut result = self.entry_result(entry.as_ref());
+        self.annotate(&mut result);
+        Some(result)
+    }
+
+    fn dictionary_status(&self) -> DictionaryStatus {
+        self.dictionary_status.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broken_embedded_dictionary_reports_failure() {
+        let base = JMdict::from_json_with_lang("{ not json", DEFAULT_GLOSS_LANG)
+            .map_err(|e| e.to_string());
+
+        let processor =
+            JapaneseProcessor::from_base(base, &[], &[DEFAULT_GLOSS_LANG], None, &mut |_, _| {});
+
+        match processor.dictionary_status() {
+            DictionaryStatus::Failed { reason } => assert!(!reason.is_empty()),
+            other => panic!("expected failed status, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_missing_additional_dictionary_keeps_base_status() {
+        let processor = JapaneseProcessor::from_base(
+            Ok(JMdict::new()),
+            &["does/not/exist.json".to_string()],
+            &[DEFAULT_GLOSS_LANG],
+            None,
+            &mut |_, _| {},
+        );
+
+        assert_eq!(processor.dictionary_status(), DictionaryStatus::Empty);
+    }
+
+    const VERBS: &str = r#"{"words": [
+        {"id": "1", "kana": [{"text": "する"}], "sense": [
+            {"gloss": [{"lang": "eng", "text": "to do"}]}
+        ]},
+        {"id": "2", "kanji": [{"text": "知る"}], "kana": [{"text": "しる"}], "sense": [
+            {"gloss": [{"lang": "eng", "text": "to know"}]}
+        ]},
+        {"id": "3", "kanji": [{"text": "待つ"}], "kana": [{"text": "まつ"}], "sense": [
+            {"gloss": [{"lang": "eng", "text": "to wait"}]}
+        ]},
+        {"id": "4", "kanji": [{"text": "食べる"}], "kana": [{"text": "たべる"}], "sense": [
+            {"gloss": [{"lang": "eng", "text": "to eat"}]}
+        ]},
+        {"id": "5", "kanji": [{"text": "読む"}], "kana": [{"text": "よむ"}], "sense": [
+            {"gloss": [{"lang": "eng", "text": "to read"}]}
+        ]}
+    ]}"#;
+
+    fn processor() -> JapaneseProcessor {
+        let dict = JMdict::from_json(VERBS).map_err(|e| e.to_string());
+        JapaneseProcessor::from_base(dict, &[], &[DEFAULT_GLOSS_LANG], None, &mut |_, _| {})
+    }
+
+    fn base_forms(processor: &JapaneseProcessor, word: &str) -> Vec<String> {
+        let token = Token {
+            surface: word.to_string(),
+            normalized: word.to_string(),
+            position: 0,
+        };
+        processor
+            .lookup(&token)
+            .into_iter()
+            .filter_map(|r| r.metadata.get("base_form").cloned())
+            .collect()
+    }
+
+    #[test]
+    fn test_analyze_sentence_spans_cover_input() {
+        let json = r#"{"words": [
+            {"id": "1", "kanji": [{"text": "猫"}], "kana": [{"text": "ねこ"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "cat"}]}
+            ]},
+            {"id": "2", "kana": [{"text": "が"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "subject marker"}]}
+            ]},
+            {"id": "3", "kanji": [{"text": "食べる"}], "kana": [{"text": "たべる"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "to eat"}]}
+            ]}
+        ]}"#;
+        let dict = JMdict::from_json(json).map_err(|e| e.to_string());
+        let processor =
+            JapaneseProcessor::from_base(dict, &[], &[DEFAULT_GLOSS_LANG], None, &mut |_, _| {});
+
+        let text = "猫が食べた。ね";
+        let words = processor.analyze_sentence(text);
+
+        let mut end = 0;
+        for word in &words {
+            assert_eq!(word.start, end, "spans overlap or leave a gap");
+            assert!(word.end > word.start);
+            end = word.end;
+        }
+        assert_eq!(end, text.chars().count());
+
+        let surfaces: Vec<&str> = words.iter().map(|w| w.surface.as_str()).collect();
+        assert_eq!(surfaces, ["猫", "が", "食べた", "。ね"]);
+        assert_eq!(words[2].term.as_deref(), Some("食べる"));
+        assert_eq!(words[2].reading.as_deref(), Some("たべる"));
+        assert_eq!(words[0].definition.as_deref(), Some("cat"));
+        assert_eq!(words[3].term, None);
+    }
+
+    #[test]
+    fn test_analyze_sentence_spans_index_raw_text() {
+        let json = r#"{"words": [
+            {"id": "1", "kanji": [{"text": "猫"}], "kana": [{"text": "ねこ"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "cat"}]}
+            ]},
+            {"id": "2", "kanji": [{"text": "食べる"}], "kana": [{"text": "たべる"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "to eat"}]}
+            ]}
+        ]}"#;
+        let dict = JMdict::from_json(json).map_err(|e| e.to_string());
+        let processor =
+            JapaneseProcessor::from_base(dict, &[], &[DEFAULT_GLOSS_LANG], None, &mut |_, _| {});
+
+        // Normalized to 猫ガッツ食べた: the newline goes and ｶﾞ becomes one char
+        let text = "猫\nｶﾞｯﾂ食べた";
+        let words = processor.analyze_sentence(text);
+
+        let spans: Vec<(usize, usize)> = words.iter().map(|w| (w.start, w.end)).collect();
+        assert_eq!(spans, [(0, 2), (2, 6), (6, 9)]);
+        let raw: Vec<char> = text.chars().collect();
+        for word in &words {
+            assert_eq!(
+                word.surface,
+                raw[word.start..word.end].iter().collect::<String>()
+            );
+        }
+        assert_eq!(words[1].surface, "ｶﾞｯﾂ");
+        assert_eq!(words[2].term.as_deref(), Some("食べる"));
+    }
+
+    #[test]
+    fn test_hide_rare_senses_drops_tagged_definitions() {
+        let json = r#"{"words": [
+            {"id": "1", "kanji": [{"text": "御座る"}], "kana": [{"text": "ござる"}], "sense": [
+                {"misc": ["arch"], "gloss": [{"lang": "eng", "text": "to come"}]},
+                {"gloss": [{"lang": "eng", "text": "to be"}]}
+            ]}
+        ]}"#;
+        let dict = JMdict::from_json(json).map_err(|e| e.to_string());
+        let processor =
+            JapaneseProcessor::from_base(dict, &[], &[DEFAULT_GLOSS_LANG], None, &mut |_, _| {});
+        let token = Token {
+            surface: "御座る".to_string(),
+            normalized: "御座る".to_string(),
+            position: 0,
+        };
+
+        assert_eq!(
+            processor.lookup(&token)[0].definitions,
+            vec!["to come", "to be"]
+        );
+        let processor = processor.with_hide_rare_senses(true);
+        assert_eq!(processor.lookup(&token)[0].definitions, vec!["to be"]);
+        assert_eq!(
+            processor.lookup_by_id("JMdict:1").unwrap().definitions,
+            vec!["to be"]
+        );
+    }
+
+    #[test]
+    fn test_field_tags_in_result_metadata() {
+        let json = r#"{"words": [
+            {"id": "1", "kanji": [{"text": "鼠"}], "kana": [{"text": "ねずみ"}], "sense": [
+                {"misc": ["uk"], "gloss": [{"lang": "eng", "text": "mouse (animal)"}]},
+                {"field": ["comp"], "gloss": [{"lang": "eng", "text": "mouse (device)"}]}
+            ]}
+        ]}"#;
+        let dict = JMdict::from_json(json).map_err(|e| e.to_string());
+        let processor =
+            JapaneseProcessor::from_base(dict, &[], &[DEFAULT_GLOSS_LANG], None, &mut |_, _| {});
+
+        let result = processor.lookup_by_id("JMdict:1").unwrap();
+        assert_eq!(
+            result.metadata.get("fields").map(String::as_str),
+            Some("comp")
+        );
+    }
+
+    #[test]
+    fn test_priority_rank_without_frequency_entry() {
+        let json = r#"{"words": [
+            {"id": "1", "kanji": [{"text": "鶍", "priority": ["news1", "nf10"]}],
+             "kana": [{"text": "いすか"}], "sense": [
+                {"gloss": [{"lang": "eng", "text": "crossbill"}]}
+            ]}
+        ]}"#;
+        let dict = JMdict::from_json(json).map_err(|e| e.to_string());
+        let processor =
+            JapaneseProcessor::from_base(dict, &[], &[DEFAULT_GLOSS_LANG], None, &mut |_, _| {});
+
+        let result = processor.lookup_by_id("JMdict:1").unwrap();
+        let metadata = |key: &str| result.metadata.get(key).map(String::as_str);
+        assert_eq!(metadata("common"), Some("true"));
+        assert_eq!(metadata("frequency_rank"), Some("5000"));
+        assert_eq!(metadata("frequency_stars"), Some("★★★"));
+    }
+
+    #[test]
+    fn test_high_confidence_deconjugation_first() {
+        // する (1.0) before しる (0.8)
+        assert_eq!(base_forms(&processor(), "して"), vec!["する", "しる"]);
+    }
+
+    #[test]
+    fn test_low_confidence_suppressed_by_better_match() {
+        let processor = processor().with_min_deconj_confidence(0.9);
+        assert_eq!(base_forms(&processor, "して"), vec!["する"]);
+    }
+
+    #[test]
+    fn test_low_confidence_used_without_better_match() {
+        // 待って only has 0.6 godan guesses
+        assert_eq!(base_forms(&processor(), "待って"), vec!["待つ"]);
+    }
+
+    #[test]
+    fn test_confidence_only_on_deconjugated_results() {
+        let processor = processor();
+        let lookup = |word: &str| {
+            processor.lookup(&Token {
+                surface: word.to_string(),
+                normalized: word.to_string(),
+                position: 0,
+            })
+        };
+
+        let deconjugated = lookup("して");
+        assert_eq!(deconjugated[0].metadata["conjugation_confidence"], "1");
+        assert!(!lookup("する")[0].metadata.contains_key("conjugation_confidence"));
+    }
+
+    fn conjugation(processor: &JapaneseProcessor, word: &str) -> (String, String) {
+        let result = processor
+            .lookup(&Token {
+                surface: word.to_string(),
+                normalized: word.to_string(),
+                position: 0,
+            })
+            .remove(0);
+        (
+            result.metadata["base_form"].clone(),
+            result.metadata["conjugation"].clone(),
+        )
+    }
+
+    #[test]
+    fn test_two_step_chain() {
+        let (base, conjugation) = conjugation(&processor(), "食べなかった");
+        assert_eq!(base, "食べる");
+        assert_eq!(
+            conjugation,
+            "食べなかった → 食べる (ichidan verb, negative + i-adjective, past)"
+        );
+    }
+
+    #[test]
+    fn test_three_step_chain() {
+        let (base, conjugation) = conjugation(&processor(), "待っていなかった");
+        assert_eq!(base, "待つ");
+        assert_eq!(
+            conjugation,
+            "待っていなかった → 待つ \
+             (godan verb, te-form, continuous + ichidan verb, negative + i-adjective, past)"
+        );
+    }
+
+    #[test]
+    fn test_desiderative_negative_past() {
+        let (base, conjugation) = conjugation(&processor(), "食べたくなかった");
+        assert_eq!(base, "食べる");
+        assert_eq!(
+            conjugation,
+            "食べたくなかった → 食べる \
+             (ichidan verb, desiderative + i-adjective, negative + i-adjective, past)"
+        );
+    }
+
+    #[test]
+    fn test_negative_conditional() {
+        let (base, conjugation) = conjugation(&processor(), "読まなければ");
+        assert_eq!(base, "読む");
+        assert_eq!(
+            conjugation,
+            "読まなければ → 読む (godan verb, negative + i-adjective, conditional)"
+        );
+    }
+
+    #[test]
+    fn test_chain_stops_at_depth_limit() {
+        // Four layers: past, negative, negative, continuous
+        let processor = processor();
+        assert!(base_forms(&processor, "待っていなくなかった").is_empty());
+    }
+
+    fn best_term(processor: &JapaneseProcessor, text: &str, position: usize) -> Option<String> {
+        processor.best_match(text, position).map(|r| r.term)
+    }
+
+    #[test]
+    fn test_best_match_deconjugates_longest_word() {
+        let processor = processor();
+        assert_eq!(
+            best_term(&processor, "食べました", 0).as_deref(),
+            Some("食べる")
+        );
+        assert_eq!(
+            best_term(&processor, "今日は待った", 3).as_deref(),
+            Some("待つ")
+        );
+    }
+
+    #[test]
+    fn test_best_match_prefers_longest_match() {
+        let dict = JMdict::from_json(
+            r#"{"words": [
+                {"id": "1", "kanji": [{"text": "日本"}], "kana": [{"text": "にほん"}], "sense": [
+                    {"gloss": [{"lang": "eng", "text": "Japan"}]}
+                ]},
+                {"id": "2", "kanji": [{"text": "日本語"}], "kana": [{"text": "にほんご"}], "sense": [
+                    {"gloss": [{"lang": "eng", "text": "Japanese language"}]}
+                ]}
+            ]}"#,
+        )
+        .map_err(|e| e.to_string());
+        let processor =
+            JapaneseProcessor::from_base(dict, &[], &[DEFAULT_GLOSS_LANG], None, &mut |_, _| {});
+
+        assert_eq!(
+            best_term(&processor, "日本語を", 0).as_deref(),
+            Some("日本語")
+        );
+        assert_eq!(best_term(&processor, "日本へ", 0).as_deref(), Some("日本"));
+    }
+
+    #[test]
+    fn test_best_match_without_match() {
+        let processor = processor();
+        assert_eq!(best_term(&processor, "食べました", 5), None);
+        assert_eq!(best_term(&processor, "食べました", 9), None);
+        assert_eq!(best_term(&processor, "ぬ", 0), None);
+    }
 }
@@ -0,0 +1,84 @@
+/// Sentence-ending punctuation, full- and half-width
+const TERMINATORS: &[char] = &['。', '｡', '．', '！', '!', '？', '?', '…'];
+
+/// Closing brackets and quotes that belong to the sentence before them
+const CLOSERS: &[char] = &['」', '』', '）', ')', '】', '〉', '》', '"', '”', '\'', '’'];
+
+/// Split Japanese text into sentences
+///
+/// Splits after 。！？ (and their half-width forms) and on newlines. Terminators
+/// stay attached, together with any closing quotes that follow them, so
+/// 「行こう！」と言った。 stays one sentence up to 」 and then と言った。.
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\n' || c == '\r' {
+            push_sentence(&mut sentences, &mut current);
+            continue;
+        }
+
+        current.push(c);
+
+        if TERMINATORS.contains(&c) {
+            // Keep runs like ！？ and trailing closers with this sentence
+            while let Some(&next) = chars.peek() {
+                if TERMINATORS.contains(&next) || CLOSERS.contains(&next) {
+                    current.push(next);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            push_sentence(&mut sentences, &mut current);
+        }
+    }
+
+    push_sentence(&mut sentences, &mut current);
+    sentences
+}
+
+fn push_sentence(sentences: &mut Vec<String>, current: &mut String) {
+    let trimmed = current.trim();
+    if !trimmed.is_empty() {
+        sentences.push(trimmed.to_string());
+    }
+    current.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splits_keeping_terminators() {
+        assert_eq!(
+            split_sentences("今日は晴れです。明日は雨？うれしい！"),
+            vec!["今日は晴れです。", "明日は雨？", "うれしい！"]
+        );
+    }
+
+    #[test]
+    fn test_mixed_width_punctuation_and_newlines() {
+        assert_eq!(
+            split_sentences("本当!? そうか｡\n次の行  \r\n\n最後"),
+            vec!["本当!?", "そうか｡", "次の行", "最後"]
+        );
+    }
+
+    #[test]
+    fn test_quotes_stay_with_their_sentence() {
+        assert_eq!(
+            split_sentences("「行こう！」と彼は言った。『はい。』"),
+            vec!["「行こう！」", "と彼は言った。", "『はい。』"]
+        );
+    }
+
+    #[test]
+    fn test_empty_and_whitespace_only() {
+        assert!(split_sentences("").is_empty());
+        assert!(split_sentences("  \n　\n").is_empty());
+    }
+}
@@ -1,11 +1,27 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 use saya_translator::{LanguageCode, ProviderMetadata, TranslateError, Translation, Translator};
 
+/// Target languages DeepL accepts a `formality` for
+const FORMALITY_LANGUAGES: &[&str] = &["DE", "FR", "IT", "ES", "NL", "PL", "PT", "JA", "RU"];
+
+/// Whether DeepL takes a formality when translating into `target_lang`
+fn supports_formality(target_lang: &str) -> bool {
+    let primary = target_lang.split('-').next().unwrap_or_default();
+    FORMALITY_LANGUAGES
+        .iter()
+        .any(|lang| lang.eq_ignore_ascii_case(primary))
+}
+
 #[derive(Clone)]
 pub struct JapaneseTranslator {
     client: reqwest::Client,
     api_key: String,
     api_url: String,
+    glossary_id: Option<String>,
+    formality_alternatives: bool,
+    formality: Option<String>,
 }
 
 impl JapaneseTranslator {
@@ -14,33 +30,55 @@ impl JapaneseTranslator {
             client: reqwest::Client::new(),
             api_key,
             api_url,
+            glossary_id: None,
+            formality_alternatives: false,
+            formality: None,
         }
     }
-}
 
-#[async_trait]
-impl Translator for JapaneseTranslator {
-    async fn translate(
-        &self,
-        text: &str,
-        from: LanguageCode,
-        to: LanguageCode,
-    ) -> Result<Translation, TranslateError> {
-        if self.api_key.is_empty() {
-            return Err(TranslateError::AuthenticationError);
-        }
+    /// Give up on requests that take longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to build HTTP client with timeout: {}", e);
+                reqwest::Client::new()
+            });
+        self
+    }
 
-        let params = [
-            ("text", text),
-            ("source_lang", &from.to_uppercase()),
-            ("target_lang", &to.to_uppercase()),
-        ];
+    /// Apply a DeepL glossary created through their glossary API
+    pub fn with_glossary_id(mut self, glossary_id: String) -> Self {
+        self.glossary_id = Some(glossary_id);
+        self
+    }
+
+    /// Also request a formal rendering and offer it as an alternative
+    ///
+    /// Costs a second request per translation into a language with a formality
+    /// setting. With a formal `formality` configured the informal rendering is
+    /// offered instead.
+    pub fn with_formality_alternatives(mut self, enabled: bool) -> Self {
+        self.formality_alternatives = enabled;
+        self
+    }
 
+    /// Send `formality` (`more`, `less`, `prefer_more` or `prefer_less`) with requests
+    ///
+    /// Only sent for target languages that support it.
+    pub fn with_formality(mut self, formality: impl Into<String>) -> Self {
+        self.formality = Some(formality.into());
+        self
+    }
+
+    /// Send one translate request, returning the first translation's text
+    async fn request(&self, params: &[(&str, &str)]) -> Result<String, TranslateError> {
         let response = self
             .client
             .post(&self.api_url)
             .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
-            .form(&params)
+            .form(params)
             .send()
             .await?;
 
@@ -52,6 +90,24 @@ impl Translator for JapaneseTranslator {
             return Err(TranslateError::AuthenticationError);
         }
 
+        if response.status() == 400 {
+            let body = response.text().await.unwrap_or_default();
+            if body.contains("formality") {
+                let param = |name| {
+                    params
+                        .iter()
+                        .find(|(key, _)| *key == name)
+                        .map_or("", |(_, value)| *value)
+                };
+                return Err(TranslateError::ApiError(format!(
+                    "Formality '{}' isn't supported when translating into {}",
+                    param("formality"),
+                    param("target_lang")
+                )));
+            }
+            return Err(TranslateError::ApiError(format!("HTTP 400: {}", body)));
+        }
+
         if !response.status().is_success() {
             return Err(TranslateError::ApiError(format!(
                 "HTTP {}",
@@ -63,18 +119,67 @@ impl Translator for JapaneseTranslator {
             TranslateError::ApiError(format!("Failed to parse response: {}", e))
         })?;
 
-        let translated_text = json["translations"]
+        json["translations"]
             .get(0)
             .and_then(|t| t["text"].as_str())
-            .ok_or_else(|| TranslateError::ApiError("No translation in response".to_string()))?;
+            .map(str::to_string)
+            .ok_or_else(|| TranslateError::ApiError("No translation in response".to_string()))
+    }
+}
+
+#[async_trait]
+impl Translator for JapaneseTranslator {
+    async fn translate(
+        &self,
+        text: &str,
+        from: LanguageCode,
+        to: LanguageCode,
+    ) -> Result<Translation, TranslateError> {
+        if self.api_key.is_empty() {
+            return Err(TranslateError::AuthenticationError);
+        }
+
+        let source_lang = from.to_uppercase();
+        let target_lang = to.to_uppercase();
+        let mut params = vec![
+            ("text", text),
+            ("source_lang", source_lang.as_str()),
+            ("target_lang", target_lang.as_str()),
+        ];
+        if let Some(glossary_id) = &self.glossary_id {
+            params.push(("glossary_id", glossary_id.as_str()));
+        }
+        let has_formality = supports_formality(&target_lang);
+        let formality = self.formality.as_deref().filter(|_| has_formality);
+        let default_params = params.clone();
+        if let Some(formality) = formality {
+            params.push(("formality", formality));
+        }
+
+        let translated_text = self.request(&params).await?;
+
+        let mut alternatives = Vec::new();
+        if self.formality_alternatives && has_formality {
+            let alternative = match formality {
+                Some("more" | "prefer_more") => "prefer_less",
+                _ => "prefer_more",
+            };
+            let mut params = default_params;
+            params.push(("formality", alternative));
+            match self.request(&params).await {
+                Ok(formal) if formal != translated_text => alternatives.push(formal),
+                Ok(_) => {}
+                Err(e) => tracing::debug!("Formality alternative failed: {}", e),
+            }
+        }
 
         Ok(Translation {
-            text: translated_text.to_string(),
+            text: translated_text,
             from,
             to,
             provider: "deepl".to_string(),
             confidence: None,
-            alternatives: vec![],
+            alternatives,
         })
     }
 
@@ -131,3 +236,186 @@ impl Translator for JapaneseTranslator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hung_server_times_out() {
+        // Accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+
+        let translator = JapaneseTranslator::new("key".to_string(), url)
+            .with_timeout(Duration::from_millis(200));
+        let started = std::time::Instant::now();
+        let err = translator
+            .translate("本", "ja".to_string(), "en".to_string())
+            .await
+            .unwrap_err();
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(matches!(err, TranslateError::ApiError(reason) if reason == "timeout"));
+    }
+
+    /// Read one HTTP request, headers and body
+    async fn read_request(socket: &mut tokio::net::TcpStream) -> String {
+        use tokio::io::AsyncReadExt;
+
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        loop {
+            let Ok(n @ 1..) = socket.read(&mut buf).await else {
+                break;
+            };
+            request.extend_from_slice(&buf[..n]);
+
+            let text = String::from_utf8_lossy(&request);
+            if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+                let length = headers
+                    .lines()
+                    .find_map(|line| {
+                        line.to_ascii_lowercase()
+                            .strip_prefix("content-length:")?
+                            .trim()
+                            .parse()
+                            .ok()
+                    })
+                    .unwrap_or(0);
+                if body.len() >= length {
+                    break;
+                }
+            }
+        }
+        String::from_utf8_lossy(&request).into_owned()
+    }
+
+    /// Serve DeepL-like responses, recording each request body
+    async fn serve(respond: fn(&str) -> (u16, String)) -> (String, Arc<Mutex<Vec<String>>>) {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+        let recorded = bodies.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let request = read_request(&mut socket).await;
+                let body = request.split_once("\r\n\r\n").map_or("", |(_, b)| b);
+                recorded.lock().unwrap().push(body.to_string());
+
+                let (status, reply) = respond(body);
+                let response = format!(
+                    "HTTP/1.1 {} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    reply.len(),
+                    reply
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        (url, bodies)
+    }
+
+    fn translated(text: &str) -> (u16, String) {
+        let body = format!(r#"{{"translations":[{{"text":"{}"}}]}}"#, text);
+        (200, body)
+    }
+
+    #[tokio::test]
+    async fn test_formal_rendering_becomes_alternative() {
+        // Answers formal requests differently from the default ones
+        let (url, _) = serve(|body| {
+            if body.contains("formality=prefer_more") {
+                translated("Möchten Sie essen?")
+            } else {
+                translated("Willst du essen?")
+            }
+        })
+        .await;
+
+        let translator =
+            JapaneseTranslator::new("key".to_string(), url).with_formality_alternatives(true);
+        let translation = translator
+            .translate("食べる？", "ja".to_string(), "de".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(translation.text, "Willst du essen?");
+        assert_eq!(translation.alternatives, ["Möchten Sie essen?"]);
+    }
+
+    #[tokio::test]
+    async fn test_no_alternative_requested_without_formality() {
+        let (url, bodies) = serve(|_| translated("Want to eat?")).await;
+
+        let translator =
+            JapaneseTranslator::new("key".to_string(), url).with_formality_alternatives(true);
+        let translation = translator
+            .translate("食べる？", "ja".to_string(), "en-us".to_string())
+            .await
+            .unwrap();
+
+        assert!(translation.alternatives.is_empty());
+        assert_eq!(bodies.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_formality_sent_only_when_configured_and_supported() {
+        let (url, bodies) = serve(|_| translated("Hallo")).await;
+
+        let plain = JapaneseTranslator::new("key".to_string(), url.clone());
+        plain
+            .translate("こんにちは", "ja".to_string(), "de".to_string())
+            .await
+            .unwrap();
+        let formal = JapaneseTranslator::new("key".to_string(), url).with_formality("more");
+        formal
+            .translate("こんにちは", "ja".to_string(), "de".to_string())
+            .await
+            .unwrap();
+        formal
+            .translate("こんにちは", "ja".to_string(), "en".to_string())
+            .await
+            .unwrap();
+
+        let bodies = bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 3);
+        assert!(!bodies[0].contains("formality"));
+        assert!(bodies[1].contains("formality=more"));
+        // English has no formality setting
+        assert!(!bodies[2].contains("formality"));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_formality_has_clear_error() {
+        let (url, _) = serve(|_| {
+            (
+                400,
+                r#"{"message":"'formality' is not supported for given 'target_lang'."}"#
+                    .to_string(),
+            )
+        })
+        .await;
+
+        let translator = JapaneseTranslator::new("key".to_string(), url).with_formality("less");
+        let err = translator
+            .translate("本", "ja".to_string(), "pt-br".to_string())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "API error: Formality 'less' isn't supported when translating into PT-BR"
+        );
+    }
+}